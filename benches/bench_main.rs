@@ -3,5 +3,6 @@ use criterion::criterion_main;
 mod benchmarks;
 
 criterion_main! {
+    benchmarks::master_playlist::benches,
     benchmarks::media_playlist::benches,
 }
@@ -0,0 +1,78 @@
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+use criterion::{black_box, criterion_group, Criterion, Throughput};
+
+use hls_m3u8::tags::{ExtXMedia, VariantStream};
+use hls_m3u8::types::{MediaType, StreamData};
+use hls_m3u8::MasterPlaylist;
+
+fn create_manifest_data() -> Vec<u8> {
+    let mut builder = MasterPlaylist::builder();
+
+    builder.media(
+        (0..50)
+            .map(|i| {
+                ExtXMedia::builder()
+                    .media_type(MediaType::Audio)
+                    .group_id(format!("audio-{}", i))
+                    .name(format!("audio-{}", i))
+                    .uri(format!("audio/{}.m3u8", i))
+                    .build()
+                    .unwrap()
+            })
+            .collect::<Vec<_>>(),
+    );
+
+    builder.variant_streams(
+        (0..5000)
+            .map(|i| {
+                VariantStream::ExtXStreamInf {
+                    uri: format!("video/{}.m3u8", i).into(),
+                    frame_rate: None,
+                    audio: Some(format!("audio-{}", i % 50).into()),
+                    subtitles: None,
+                    closed_captions: None,
+                    stream_data: StreamData::builder()
+                        .bandwidth(150_000 + i)
+                        .codecs(&["avc1.42e00a", "mp4a.40.2"])
+                        .resolution((416, 234))
+                        .build()
+                        .unwrap(),
+                }
+            })
+            .collect::<Vec<_>>(),
+    );
+
+    builder.build().unwrap().to_string().into_bytes()
+}
+
+fn master_playlist_from_str(c: &mut Criterion) {
+    let data = String::from_utf8(create_manifest_data()).unwrap();
+
+    let mut group = c.benchmark_group("MasterPlaylist::from_str");
+
+    group.throughput(Throughput::Bytes(data.len() as u64));
+
+    group.bench_function("MasterPlaylist::from_str", |b| {
+        b.iter(|| MasterPlaylist::from_str(black_box(&data)).unwrap());
+    });
+
+    group.finish();
+}
+
+fn master_playlist_try_from(c: &mut Criterion) {
+    let data = String::from_utf8(create_manifest_data()).unwrap();
+
+    let mut group = c.benchmark_group("MasterPlaylist::try_from");
+
+    group.throughput(Throughput::Bytes(data.len() as u64));
+
+    group.bench_function("MasterPlaylist::try_from", |b| {
+        b.iter(|| MasterPlaylist::try_from(black_box(data.as_str())).unwrap());
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, master_playlist_from_str, master_playlist_try_from);
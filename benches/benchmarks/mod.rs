@@ -1 +1,2 @@
+pub mod master_playlist;
 pub mod media_playlist;
@@ -160,6 +160,19 @@ mod test {
         assert_eq!(pairs.next(), Some(("ABC", "12.3")));
         assert_eq!(pairs.next(), None);
 
+        // commas inside quotes are part of the value
+        let mut pairs = AttributePairs::new(concat!(
+            "BANDWIDTH=1280000,",
+            "CODECS=\"avc1.4d401f, mp4a.40.2\",",
+            "RESOLUTION=1280x720"
+        ));
+
+        assert_eq!((3, Some(3)), pairs.size_hint());
+        assert_eq!(pairs.next(), Some(("BANDWIDTH", "1280000")));
+        assert_eq!(pairs.next(), Some(("CODECS", "\"avc1.4d401f, mp4a.40.2\"")));
+        assert_eq!(pairs.next(), Some(("RESOLUTION", "1280x720")));
+        assert_eq!(pairs.next(), None);
+
         // stress test with foreign input
         // got it from https://generator.lorem-ipsum.info/_chinese
 
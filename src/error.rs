@@ -1,4 +1,6 @@
 use std::fmt;
+use std::io;
+use std::sync::Arc;
 
 #[cfg(feature = "backtrace")]
 use backtrace::Backtrace;
@@ -18,6 +20,12 @@ enum ErrorKind {
     #[error("invalid input")]
     InvalidInput,
 
+    #[error("invalid input {input:?}: {source}")]
+    InvalidValue {
+        input: String,
+        source: ::std::num::ParseIntError,
+    },
+
     #[error("{source}: {input:?}")]
     ParseIntError {
         input: String,
@@ -67,6 +75,31 @@ enum ErrorKind {
 
     #[error("{source}")]
     Hex { source: hex::FromHexError },
+
+    #[error("{source}")]
+    Io { source: IoError },
+
+    #[error("line {line}: {source}")]
+    Line { line: usize, source: Box<ErrorKind> },
+}
+
+/// An [`io::Error`], which can be cloned and compared (by its kind and its
+/// message), so that [`ErrorKind`] can still derive `Clone` and `PartialEq`.
+#[derive(Debug, Clone)]
+struct IoError(Arc<io::Error>);
+
+impl PartialEq for IoError {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.kind() == other.0.kind() && self.0.to_string() == other.0.to_string()
+    }
+}
+
+impl fmt::Display for IoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { self.0.fmt(f) }
+}
+
+impl std::error::Error for IoError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> { Some(&*self.0) }
 }
 
 /// The Error type of this library.
@@ -81,7 +114,9 @@ impl PartialEq for Error {
     fn eq(&self, other: &Self) -> bool { self.inner == other.inner }
 }
 
-impl std::error::Error for Error {}
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> { self.inner.source() }
+}
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { self.inner.fmt(f) }
@@ -128,6 +163,18 @@ impl Error {
 
     pub(crate) fn invalid_input() -> Self { Self::new(ErrorKind::InvalidInput) }
 
+    /// Like [`Error::invalid_input`], but keeps the invalid input and the
+    /// reason, why it could not be parsed.
+    pub(crate) fn invalid_value<T: fmt::Display>(
+        input: T,
+        source: ::std::num::ParseIntError,
+    ) -> Self {
+        Self::new(ErrorKind::InvalidValue {
+            input: input.to_string(),
+            source,
+        })
+    }
+
     pub(crate) fn parse_int<T: fmt::Display>(input: T, source: ::std::num::ParseIntError) -> Self {
         Self::new(ErrorKind::ParseIntError {
             input: input.to_string(),
@@ -180,12 +227,30 @@ impl Error {
         Self::custom(format!("Unexpected data in the line: {:?}", value))
     }
 
+    /// Attaches the (1-based) number of the line, at which the error occured.
+    pub(crate) fn at_line(self, line: usize) -> Self {
+        Self {
+            inner: ErrorKind::Line {
+                line,
+                source: Box::new(self.inner),
+            },
+            #[cfg(feature = "backtrace")]
+            backtrace: self.backtrace,
+        }
+    }
+
     // third party crates:
     #[cfg(feature = "chrono")]
     pub(crate) fn chrono(source: chrono::format::ParseError) -> Self {
         Self::new(ErrorKind::Chrono { source })
     }
 
+    pub(crate) fn io(source: io::Error) -> Self {
+        Self::new(ErrorKind::Io {
+            source: IoError(Arc::new(source)),
+        })
+    }
+
     pub(crate) fn hex(source: hex::FromHexError) -> Self {
         //
         Self::new(ErrorKind::Hex { source })
@@ -232,4 +297,50 @@ mod tests {
             "invalid digit found in string: \"1x\"".to_string()
         );
     }
+
+    #[test]
+    fn test_invalid_value() {
+        use std::error::Error as _;
+
+        let error = Error::invalid_value("abc", "abc".parse::<u64>().unwrap_err());
+
+        assert_eq!(
+            error.to_string(),
+            "invalid input \"abc\": invalid digit found in string".to_string()
+        );
+        assert!(error.source().is_some());
+    }
+
+    #[test]
+    fn test_io_error() {
+        use std::error::Error as _;
+
+        let error = Error::io(io::Error::new(io::ErrorKind::InvalidData, "invalid utf-8"));
+
+        assert_eq!(error.to_string(), "invalid utf-8".to_string());
+        assert_eq!(
+            error,
+            Error::io(io::Error::new(io::ErrorKind::InvalidData, "invalid utf-8"))
+        );
+
+        // the io error is kept as the source:
+        let source = error.source().unwrap().source().unwrap();
+        assert_eq!(
+            source.downcast_ref::<io::Error>().map(io::Error::kind),
+            Some(io::ErrorKind::InvalidData)
+        );
+
+        assert_ne!(
+            error,
+            Error::io(io::Error::new(io::ErrorKind::Other, "invalid utf-8"))
+        );
+    }
+
+    #[test]
+    fn test_at_line() {
+        assert_eq!(
+            Error::missing_value("URI").at_line(5).to_string(),
+            "line 5: a value is missing for the attribute URI".to_string()
+        );
+    }
 }
@@ -70,6 +70,12 @@
 //!   - Enables the backtrace feature for the `Error` type.
 //!   - This feature depends on the following dependencies:
 //!     - [`backtrace`]
+//! - [`base64`] (optional)
+//!   - Enables encoding the SCTE-35 payloads of an [`ExtXDateRange`] as base64
+//!     with [`ExtXDateRange::scte35_out_base64`] (and the corresponding
+//!     `scte35_cmd_base64` and `scte35_in_base64`).
+//!   - This feature depends on the following dependencies:
+//!     - [`base64`]
 //! - [`chrono`] (optional)
 //!   - Enables parsing dates and verifying them.
 //!   - This feature depends on the following dependencies:
@@ -81,6 +87,32 @@
 //!       `DateTime<FixedOffset>`
 //!     - [`ExtXDateRange::end_date`] will change from [`String`] to
 //!       `DateTime<FixedOffset>`
+//! - [`flate2`] (optional)
+//!   - Enables reading gzip-compressed playlists with
+//!     [`MediaPlaylist::from_gzip_reader`].
+//!   - This feature depends on the following dependencies:
+//!     - [`flate2`]
+//! - [`percent-encoding`] (optional)
+//!   - Enables decoding percent-encoded `URI`s with
+//!     [`MediaSegment::decoded_uri`].
+//!   - This feature depends on the following dependencies:
+//!     - [`percent-encoding`]
+//! - [`url`] (optional)
+//!   - Enables checking the `URI`s of a playlist with
+//!     [`MediaPlaylist::validate_uris`].
+//!   - This feature depends on the following dependencies:
+//!     - [`url`]
+//! - [`serde`] (optional)
+//!   - Implements `Serialize` and `Deserialize` for [`ExtXDateRange`], which
+//!     can be used to export the date ranges as JSON.
+//!   - Implements `Serialize` and `Deserialize` for [`ExtInf`],
+//!     [`ExtXByteRange`], [`ExtXKey`], [`ExtXMap`], [`ExtXProgramDateTime`] and
+//!     the types used by them (like [`ByteRange`] and [`DecryptionKey`]).
+//!     Durations are represented as strings of seconds (like `"4.4"`) and dates
+//!     as RFC 3339 strings.
+//!   - This feature depends on the following dependencies:
+//!     - [`serde`]
+//!     - [`base64`]
 //!
 //! They are configured in your `Cargo.toml` and can be enabled like this
 //!
@@ -96,12 +128,33 @@
 //! crate::tags::ExtXDateRange::end_date
 //! [`chrono`]: https://github.com/chronotope/chrono
 //! [`backtrace`]: https://github.com/rust-lang/backtrace-rs
+//! [`base64`]: https://github.com/marshallpierce/rust-base64
+//! [`flate2`]: https://github.com/rust-lang/flate2-rs
+//! [`percent-encoding`]: https://github.com/servo/rust-url
+//! [`serde`]: https://github.com/serde-rs/serde
+//! [`url`]: https://github.com/servo/rust-url
+//! [`ExtXDateRange`]: crate::tags::ExtXDateRange
+//! [`ExtInf`]: crate::tags::ExtInf
+//! [`ExtXByteRange`]: crate::tags::ExtXByteRange
+//! [`ExtXKey`]: crate::tags::ExtXKey
+//! [`ExtXMap`]: crate::tags::ExtXMap
+//! [`ExtXProgramDateTime`]: crate::tags::ExtXProgramDateTime
+//! [`ByteRange`]: crate::types::ByteRange
+//! [`DecryptionKey`]: crate::types::DecryptionKey
+//! [`MediaPlaylist::from_gzip_reader`]:
+//! crate::MediaPlaylist::from_gzip_reader
+//! [`MediaSegment::decoded_uri`]: crate::MediaSegment::decoded_uri
+//! [`MediaPlaylist::validate_uris`]: crate::MediaPlaylist::validate_uris
+//! [`ExtXDateRange::scte35_out_base64`]:
+//! crate::tags::ExtXDateRange::scte35_out_base64
 //! [HLS]: https://tools.ietf.org/html/rfc8216
 
 pub use error::Error;
 pub use master_playlist::MasterPlaylist;
 pub use media_playlist::MediaPlaylist;
 pub use media_segment::MediaSegment;
+pub use parse_options::ParseOptions;
+pub use warning::Warning;
 
 /// Builder structs
 pub mod builder {
@@ -114,6 +167,7 @@ pub mod builder {
         // master playlist
         pub use crate::tags::master_playlist::media::ExtXMediaBuilder;
         pub use crate::tags::master_playlist::session_data::ExtXSessionDataBuilder;
+        pub use crate::tags::master_playlist::variant_stream::ExtXStreamInfBuilder;
 
         // media segment
         pub use crate::tags::media_segment::date_range::ExtXDateRangeBuilder;
@@ -138,8 +192,11 @@ mod line;
 mod master_playlist;
 mod media_playlist;
 mod media_segment;
+mod parse_options;
 mod traits;
+mod warning;
 
 pub use error::Result;
+pub use line::peek_version;
 pub use stable_vec;
 pub use traits::*;
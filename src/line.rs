@@ -1,58 +1,155 @@
 use core::convert::TryFrom;
 use core::iter::FusedIterator;
+use std::borrow::Cow;
 
 use derive_more::Display;
 
 use crate::tags;
-use crate::types::PlaylistType;
-use crate::Error;
+use crate::types::{PlaylistType, ProtocolVersion};
+use crate::{Error, ParseOptions, Warning};
 
 #[derive(Debug, Clone)]
 pub(crate) struct Lines<'a> {
-    lines: ::core::iter::FilterMap<::core::str::Lines<'a>, fn(&'a str) -> Option<&'a str>>,
+    lines: ::core::iter::Enumerate<::core::str::Lines<'a>>,
+    options: ParseOptions,
+    /// The non-fatal issues, that have been encountered so far.
+    pub(crate) warnings: Vec<Warning>,
 }
 
-impl<'a> Iterator for Lines<'a> {
-    type Item = crate::Result<Line<'a>>;
+impl<'a> Lines<'a> {
+    pub(crate) fn with_options(buffer: &'a str, options: ParseOptions) -> Self {
+        Self {
+            lines: buffer.lines().enumerate(),
+            options,
+            warnings: vec![],
+        }
+    }
+
+    /// Returns the next non-empty line together with its (1-based) line
+    /// number.
+    fn next_line(&mut self) -> Option<(usize, &'a str)> {
+        self.lines
+            .by_ref()
+            .map(|(i, line)| (i + 1, line.trim()))
+            .find(|(_, line)| !line.is_empty())
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let line = self.lines.next()?;
+    /// Like [`Iterator::next`], but also returns the line number, at which
+    /// the returned [`Line`] starts.
+    pub(crate) fn next_numbered(&mut self) -> Option<(usize, crate::Result<Line<'a>>)> {
+        let (number, line) = self.next_line()?;
 
+        Some((number, self.parse_line(line)))
+    }
+
+    fn parse_line(&mut self, line: &'a str) -> crate::Result<Line<'a>> {
         if line.starts_with(tags::VariantStream::PREFIX_EXTXSTREAMINF) {
-            let uri = self.lines.next()?;
+            // the uri of the variant stream is on the next line and must not be
+            // silently dropped, when the playlist ends with the tag
+            let uri = match self.next_line() {
+                Some((_, uri)) => uri,
+                None => return Err(Error::missing_value("URI")),
+            };
 
-            Some(
-                tags::VariantStream::try_from(format!("{}\n{}", line, uri).as_str())
-                    .map(tags::VariantStream::into_owned)
-                    .map(|v| Line::Tag(Tag::VariantStream(v))),
+            tags::VariantStream::parse(
+                format!("{}\n{}", line, uri).as_str(),
+                self.options,
+                &mut self.warnings,
             )
+            .map(tags::VariantStream::into_owned)
+            .map(|v| Line::Tag(Tag::VariantStream(v)))
+        } else if line.starts_with(tags::VariantStream::PREFIX_EXTXIFRAME) {
+            tags::VariantStream::parse(line, self.options, &mut self.warnings)
+                .map(|v| Line::Tag(Tag::VariantStream(v)))
+        } else if line.starts_with(tags::ExtXDateRange::PREFIX) {
+            tags::ExtXDateRange::parse(line, self.options, &mut self.warnings)
+                .map(|v| Line::Tag(Tag::ExtXDateRange(v)))
         } else if line.starts_with("#EXT") {
-            Some(Tag::try_from(line).map(Line::Tag))
+            Tag::try_from(line).map(Line::Tag)
         } else if line.starts_with('#') {
-            Some(Ok(Line::Comment(line)))
+            Ok(Line::Comment(Cow::Borrowed(line)))
         } else {
-            Some(Ok(Line::Uri(line)))
+            Ok(Line::Uri(Cow::Borrowed(line)))
         }
     }
 }
 
+impl<'a> Iterator for Lines<'a> {
+    type Item = crate::Result<Line<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> { self.next_numbered().map(|(_, line)| line) }
+}
+
 impl<'a> FusedIterator for Lines<'a> {}
 
 impl<'a> From<&'a str> for Lines<'a> {
-    fn from(buffer: &'a str) -> Self {
-        Self {
-            lines: buffer
-                .lines()
-                .filter_map(|line| Some(line.trim()).filter(|v| !v.is_empty())),
+    fn from(buffer: &'a str) -> Self { Self::with_options(buffer, ParseOptions::strict()) }
+}
+
+/// Returns the [`ProtocolVersion`] of a playlist, without parsing the entire
+/// playlist.
+///
+/// Only the lines up to the first `EXT-X-VERSION` tag are inspected. If the
+/// playlist has no such tag, [`ProtocolVersion::V1`] is returned.
+///
+/// This returns `None`, if the input does not start with `#EXTM3U` or if the
+/// `EXT-X-VERSION` tag is invalid.
+///
+/// # Example
+///
+/// ```
+/// use hls_m3u8::peek_version;
+/// use hls_m3u8::types::ProtocolVersion;
+///
+/// assert_eq!(
+///     peek_version("#EXTM3U\n#EXT-X-VERSION:4\n#EXT-X-TARGETDURATION:10\n"),
+///     Some(ProtocolVersion::V4)
+/// );
+///
+/// assert_eq!(
+///     peek_version("#EXTM3U\n#EXT-X-TARGETDURATION:10\n"),
+///     Some(ProtocolVersion::V1)
+/// );
+///
+/// assert_eq!(peek_version("#EXTM3U\n#EXT-X-VERSION:99\n"), None);
+/// assert_eq!(peek_version("#EXT-X-VERSION:4\n"), None);
+/// ```
+#[must_use]
+pub fn peek_version(input: &str) -> Option<ProtocolVersion> {
+    let mut lines = input.lines().map(str::trim);
+
+    if lines.next()? != tags::ExtM3u::PREFIX {
+        return None;
+    }
+
+    for line in lines {
+        if line.starts_with(tags::ExtXVersion::PREFIX) {
+            return tags::ExtXVersion::try_from(line)
+                .ok()
+                .map(tags::ExtXVersion::version);
         }
     }
+
+    Some(ProtocolVersion::V1)
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) enum Line<'a> {
     Tag(Tag<'a>),
-    Comment(&'a str),
-    Uri(&'a str),
+    Comment(Cow<'a, str>),
+    Uri(Cow<'a, str>),
+}
+
+impl<'a> Line<'a> {
+    /// Makes the line independent of the buffer, from which it has been
+    /// parsed.
+    pub(crate) fn into_owned(self) -> Line<'static> {
+        match self {
+            Self::Tag(tag) => Line::Tag(tag.into_owned()),
+            Self::Comment(value) => Line::Comment(Cow::Owned(value.into_owned())),
+            Self::Uri(value) => Line::Uri(Cow::Owned(value.into_owned())),
+        }
+    }
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -62,24 +159,68 @@ pub(crate) enum Tag<'a> {
     ExtXVersion(tags::ExtXVersion),
     ExtInf(tags::ExtInf<'a>),
     ExtXByteRange(tags::ExtXByteRange),
+    ExtXBitrate(tags::ExtXBitrate),
     ExtXDiscontinuity(tags::ExtXDiscontinuity),
+    ExtXGap(tags::ExtXGap),
     ExtXKey(tags::ExtXKey<'a>),
     ExtXMap(tags::ExtXMap<'a>),
+    ExtXPart(tags::ExtXPart<'a>),
     ExtXProgramDateTime(tags::ExtXProgramDateTime<'a>),
     ExtXDateRange(tags::ExtXDateRange<'a>),
     ExtXTargetDuration(tags::ExtXTargetDuration),
     ExtXMediaSequence(tags::ExtXMediaSequence),
     ExtXDiscontinuitySequence(tags::ExtXDiscontinuitySequence),
     ExtXEndList(tags::ExtXEndList),
+    ExtXAllowCache(tags::ExtXAllowCache),
+    ExtXPartInf(tags::ExtXPartInf),
+    ExtXServerControl(tags::ExtXServerControl),
     PlaylistType(PlaylistType),
     ExtXIFramesOnly(tags::ExtXIFramesOnly),
     ExtXMedia(tags::ExtXMedia<'a>),
+    ExtXContentSteering(tags::ExtXContentSteering<'a>),
     ExtXSessionData(tags::ExtXSessionData<'a>),
     ExtXSessionKey(tags::ExtXSessionKey<'a>),
     ExtXIndependentSegments(tags::ExtXIndependentSegments),
     ExtXStart(tags::ExtXStart),
+    ExtXDefine(tags::ExtXDefine<'a>),
     VariantStream(tags::VariantStream<'a>),
-    Unknown(&'a str),
+    Unknown(Cow<'a, str>),
+}
+
+impl<'a> Tag<'a> {
+    pub(crate) fn into_owned(self) -> Tag<'static> {
+        match self {
+            Self::ExtXVersion(t) => Tag::ExtXVersion(t),
+            Self::ExtInf(t) => Tag::ExtInf(t.into_owned()),
+            Self::ExtXByteRange(t) => Tag::ExtXByteRange(t),
+            Self::ExtXBitrate(t) => Tag::ExtXBitrate(t),
+            Self::ExtXDiscontinuity(t) => Tag::ExtXDiscontinuity(t),
+            Self::ExtXGap(t) => Tag::ExtXGap(t),
+            Self::ExtXKey(t) => Tag::ExtXKey(t.into_owned()),
+            Self::ExtXMap(t) => Tag::ExtXMap(t.into_owned()),
+            Self::ExtXPart(t) => Tag::ExtXPart(t.into_owned()),
+            Self::ExtXProgramDateTime(t) => Tag::ExtXProgramDateTime(t.into_owned()),
+            Self::ExtXDateRange(t) => Tag::ExtXDateRange(t.into_owned()),
+            Self::ExtXTargetDuration(t) => Tag::ExtXTargetDuration(t),
+            Self::ExtXMediaSequence(t) => Tag::ExtXMediaSequence(t),
+            Self::ExtXDiscontinuitySequence(t) => Tag::ExtXDiscontinuitySequence(t),
+            Self::ExtXEndList(t) => Tag::ExtXEndList(t),
+            Self::ExtXAllowCache(t) => Tag::ExtXAllowCache(t),
+            Self::ExtXPartInf(t) => Tag::ExtXPartInf(t),
+            Self::ExtXServerControl(t) => Tag::ExtXServerControl(t),
+            Self::PlaylistType(t) => Tag::PlaylistType(t),
+            Self::ExtXIFramesOnly(t) => Tag::ExtXIFramesOnly(t),
+            Self::ExtXMedia(t) => Tag::ExtXMedia(t.into_owned()),
+            Self::ExtXContentSteering(t) => Tag::ExtXContentSteering(t.into_owned()),
+            Self::ExtXSessionData(t) => Tag::ExtXSessionData(t.into_owned()),
+            Self::ExtXSessionKey(t) => Tag::ExtXSessionKey(t.into_owned()),
+            Self::ExtXIndependentSegments(t) => Tag::ExtXIndependentSegments(t),
+            Self::ExtXStart(t) => Tag::ExtXStart(t),
+            Self::ExtXDefine(t) => Tag::ExtXDefine(t.into_owned()),
+            Self::VariantStream(t) => Tag::VariantStream(t.into_owned()),
+            Self::Unknown(value) => Tag::Unknown(Cow::Owned(value.into_owned())),
+        }
+    }
 }
 
 impl<'a> TryFrom<&'a str> for Tag<'a> {
@@ -92,14 +233,24 @@ impl<'a> TryFrom<&'a str> for Tag<'a> {
             TryFrom::try_from(input).map(Self::ExtInf)
         } else if input.starts_with(tags::ExtXByteRange::PREFIX) {
             TryFrom::try_from(input).map(Self::ExtXByteRange)
+        } else if input.starts_with(tags::ExtXBitrate::PREFIX) {
+            TryFrom::try_from(input).map(Self::ExtXBitrate)
         } else if input.starts_with(tags::ExtXDiscontinuitySequence::PREFIX) {
             TryFrom::try_from(input).map(Self::ExtXDiscontinuitySequence)
         } else if input.starts_with(tags::ExtXDiscontinuity::PREFIX) {
             TryFrom::try_from(input).map(Self::ExtXDiscontinuity)
+        } else if input.starts_with(tags::ExtXGap::PREFIX) {
+            TryFrom::try_from(input).map(Self::ExtXGap)
         } else if input.starts_with(tags::ExtXKey::PREFIX) {
             TryFrom::try_from(input).map(Self::ExtXKey)
         } else if input.starts_with(tags::ExtXMap::PREFIX) {
             TryFrom::try_from(input).map(Self::ExtXMap)
+        } else if input.starts_with(tags::ExtXPartInf::PREFIX) {
+            TryFrom::try_from(input).map(Self::ExtXPartInf)
+        } else if input.starts_with(tags::ExtXPart::PREFIX) {
+            TryFrom::try_from(input).map(Self::ExtXPart)
+        } else if input.starts_with(tags::ExtXServerControl::PREFIX) {
+            TryFrom::try_from(input).map(Self::ExtXServerControl)
         } else if input.starts_with(tags::ExtXProgramDateTime::PREFIX) {
             TryFrom::try_from(input).map(Self::ExtXProgramDateTime)
         } else if input.starts_with(tags::ExtXTargetDuration::PREFIX) {
@@ -110,12 +261,16 @@ impl<'a> TryFrom<&'a str> for Tag<'a> {
             TryFrom::try_from(input).map(Self::ExtXMediaSequence)
         } else if input.starts_with(tags::ExtXEndList::PREFIX) {
             TryFrom::try_from(input).map(Self::ExtXEndList)
+        } else if input.starts_with(tags::ExtXAllowCache::PREFIX) {
+            TryFrom::try_from(input).map(Self::ExtXAllowCache)
         } else if input.starts_with(PlaylistType::PREFIX) {
             TryFrom::try_from(input).map(Self::PlaylistType)
         } else if input.starts_with(tags::ExtXIFramesOnly::PREFIX) {
             TryFrom::try_from(input).map(Self::ExtXIFramesOnly)
         } else if input.starts_with(tags::ExtXMedia::PREFIX) {
             TryFrom::try_from(input).map(Self::ExtXMedia)
+        } else if input.starts_with(tags::ExtXContentSteering::PREFIX) {
+            TryFrom::try_from(input).map(Self::ExtXContentSteering)
         } else if input.starts_with(tags::VariantStream::PREFIX_EXTXIFRAME)
             || input.starts_with(tags::VariantStream::PREFIX_EXTXSTREAMINF)
         {
@@ -128,8 +283,10 @@ impl<'a> TryFrom<&'a str> for Tag<'a> {
             TryFrom::try_from(input).map(Self::ExtXIndependentSegments)
         } else if input.starts_with(tags::ExtXStart::PREFIX) {
             TryFrom::try_from(input).map(Self::ExtXStart)
+        } else if input.starts_with(tags::ExtXDefine::PREFIX) {
+            TryFrom::try_from(input).map(Self::ExtXDefine)
         } else {
-            Ok(Self::Unknown(input))
+            Ok(Self::Unknown(Cow::Borrowed(input)))
         }
     }
 }
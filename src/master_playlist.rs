@@ -1,18 +1,19 @@
 use std::borrow::Cow;
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 use std::convert::TryFrom;
 use std::fmt;
+use std::str::FromStr;
 
 use derive_builder::Builder;
 
 use crate::line::{Line, Lines, Tag};
 use crate::tags::{
-    ExtM3u, ExtXIndependentSegments, ExtXMedia, ExtXSessionData, ExtXSessionKey, ExtXStart,
-    ExtXVersion, VariantStream,
+    ExtM3u, ExtXContentSteering, ExtXDefine, ExtXIndependentSegments, ExtXMedia, ExtXSessionData,
+    ExtXSessionKey, ExtXStart, ExtXVersion, VariantStream,
 };
 use crate::types::{ClosedCaptions, MediaType, ProtocolVersion};
 use crate::utils::{tag, BoolExt};
-use crate::{Error, RequiredVersion};
+use crate::{Error, ParseOptions, RequiredVersion, Warning};
 
 /// The master playlist describes all of the available variants for your
 /// content.
@@ -93,6 +94,10 @@ use crate::{Error, RequiredVersion};
 /// # Ok::<(), Box<dyn ::std::error::Error>>(())
 /// ```
 ///
+/// The alternate format (`{:#}`) is passed to the [`ExtXMedia`] and
+/// [`ExtXStart`] tags, so that attributes, which are equal to their default
+/// value (like `DEFAULT=NO`), are written explicitly.
+///
 /// [`MediaPlaylist`]: crate::MediaPlaylist
 #[derive(Builder, Default, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[builder(build_fn(validate = "Self::validate"))]
@@ -120,6 +125,22 @@ pub struct MasterPlaylist<'a> {
     /// the start.
     #[builder(default)]
     pub start: Option<ExtXStart>,
+    /// Provides the steering manifest, which determines the order in which
+    /// the pathways of the [`VariantStream`]s should be used.
+    ///
+    /// ### Note
+    ///
+    /// This field is optional.
+    #[builder(default)]
+    pub content_steering: Option<ExtXContentSteering<'a>>,
+    /// A list of all [`ExtXDefine`] tags, which provide the variables of this
+    /// [`MasterPlaylist`].
+    ///
+    /// ### Note
+    ///
+    /// This field is optional.
+    #[builder(default)]
+    pub defines: Vec<ExtXDefine<'a>>,
     /// A list of all [`ExtXMedia`] tags, which describe an alternative
     /// rendition.
     ///
@@ -242,6 +263,162 @@ impl<'a> MasterPlaylist<'a> {
         })
     }
 
+    /// Returns all streams, which have a codec, that starts with the given
+    /// prefix (for example `hvc1` for HEVC or `avc1` for AVC).
+    ///
+    /// This can be used to filter the streams by the capabilities of a
+    /// device.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::MasterPlaylist;
+    /// use std::convert::TryFrom;
+    ///
+    /// let master_playlist = MasterPlaylist::try_from(concat!(
+    ///     "#EXTM3U\n",
+    ///     "#EXT-X-STREAM-INF:BANDWIDTH=1280000,CODECS=\"avc1.640028,mp4a.40.2\"\n",
+    ///     "avc.m3u8\n",
+    ///     "#EXT-X-STREAM-INF:BANDWIDTH=1280000,CODECS=\"hvc1.2.4.L123.B0,mp4a.40.2\"\n",
+    ///     "hevc.m3u8\n",
+    /// ))?;
+    ///
+    /// assert_eq!(master_playlist.variants_with_video_codec("hvc1").count(), 1);
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    pub fn variants_with_video_codec<'b>(
+        &'b self,
+        prefix: &'b str,
+    ) -> impl Iterator<Item = &VariantStream<'a>> + 'b {
+        self.variant_streams.iter().filter(move |stream| {
+            stream.codecs().map_or(false, |codecs| {
+                codecs.iter().any(|codec| codec.starts_with(prefix))
+            })
+        })
+    }
+
+    /// Returns the forced subtitle rendition for the given language, which
+    /// only contains the subtitles for foreign dialogue (for example, if a
+    /// character speaks a different language than the rest of the content).
+    ///
+    /// The language is compared case-insensitively, because language tags
+    /// are not case sensitive.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::MasterPlaylist;
+    /// use std::convert::TryFrom;
+    ///
+    /// let master_playlist = MasterPlaylist::try_from(concat!(
+    ///     "#EXTM3U\n",
+    ///     "#EXT-X-MEDIA:TYPE=SUBTITLES,GROUP-ID=\"subs\",NAME=\"English\",",
+    ///     "LANGUAGE=\"en\",URI=\"en.m3u8\"\n",
+    ///     "#EXT-X-MEDIA:TYPE=SUBTITLES,GROUP-ID=\"subs\",NAME=\"English (forced)\",",
+    ///     "LANGUAGE=\"en\",FORCED=YES,URI=\"en-forced.m3u8\"\n",
+    /// ))?;
+    ///
+    /// assert_eq!(
+    ///     master_playlist.forced_subtitle("en").map(|m| m.uri()),
+    ///     Some(Some(&"en-forced.m3u8".into()))
+    /// );
+    /// assert_eq!(master_playlist.forced_subtitle("fr"), None);
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    #[must_use]
+    pub fn forced_subtitle(&self, language: &str) -> Option<&ExtXMedia<'a>> {
+        self.media.iter().find(|media| {
+            media.media_type == MediaType::Subtitles
+                && media.is_forced
+                && media
+                    .language()
+                    .map_or(false, |value| value.eq_ignore_ascii_case(language))
+        })
+    }
+
+    /// Returns all [`ExtXMedia`] tags with the [`MediaType::Video`], which are
+    /// alternative renditions of the video (for example different camera
+    /// angles).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::MasterPlaylist;
+    /// use std::convert::TryFrom;
+    ///
+    /// let master_playlist = MasterPlaylist::try_from(concat!(
+    ///     "#EXTM3U\n",
+    ///     "#EXT-X-MEDIA:TYPE=VIDEO,GROUP-ID=\"cam\",NAME=\"Main\",",
+    ///     "DEFAULT=YES,URI=\"main.m3u8\"\n",
+    ///     "#EXT-X-MEDIA:TYPE=VIDEO,GROUP-ID=\"cam\",NAME=\"Pit\",URI=\"pit.m3u8\"\n",
+    ///     "#EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"aac\",NAME=\"English\"\n",
+    /// ))?;
+    ///
+    /// assert_eq!(
+    ///     master_playlist
+    ///         .video_renditions()
+    ///         .map(|m| m.name().as_ref())
+    ///         .collect::<Vec<_>>(),
+    ///     vec!["Main", "Pit"]
+    /// );
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    pub fn video_renditions(&self) -> impl Iterator<Item = &ExtXMedia<'a>> {
+        self.media
+            .iter()
+            .filter(|media| media.media_type == MediaType::Video)
+    }
+
+    /// Returns all video renditions (see [`MasterPlaylist::video_renditions`])
+    /// with the given `group_id`.
+    pub fn video_renditions_in_group<'b>(
+        &'b self,
+        group_id: &'b str,
+    ) -> impl Iterator<Item = &ExtXMedia<'a>> + 'b {
+        self.video_renditions()
+            .filter(move |media| media.group_id() == group_id)
+    }
+
+    /// Returns the first video rendition in the group with the given
+    /// `group_id`, which has the given characteristic (a Uniform Type
+    /// Identifier like `public.accessibility.describes-video`) in its
+    /// [`ExtXMedia::characteristics`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::MasterPlaylist;
+    /// use std::convert::TryFrom;
+    ///
+    /// let master_playlist = MasterPlaylist::try_from(concat!(
+    ///     "#EXTM3U\n",
+    ///     "#EXT-X-MEDIA:TYPE=VIDEO,GROUP-ID=\"cam\",NAME=\"Main\",URI=\"main.m3u8\"\n",
+    ///     "#EXT-X-MEDIA:TYPE=VIDEO,GROUP-ID=\"cam\",NAME=\"Sign language\",",
+    ///     "CHARACTERISTICS=\"com.example.sign-language\",URI=\"sign.m3u8\"\n",
+    /// ))?;
+    ///
+    /// assert_eq!(
+    ///     master_playlist
+    ///         .video_rendition_with_characteristic("cam", "com.example.sign-language")
+    ///         .map(|m| m.name().as_ref()),
+    ///     Some("Sign language")
+    /// );
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    #[must_use]
+    pub fn video_rendition_with_characteristic(
+        &self,
+        group_id: &str,
+        characteristic: &str,
+    ) -> Option<&ExtXMedia<'a>> {
+        self.video_renditions().find(|media| {
+            media.group_id() == group_id
+                && media.characteristics().map_or(false, |value| {
+                    value.split(',').any(|v| v.trim() == characteristic)
+                })
+        })
+    }
+
     /// Returns all streams, which have no group id.
     pub fn unassociated_streams(&self) -> impl Iterator<Item = &VariantStream<'a>> {
         self.variant_streams.iter().filter(|stream| {
@@ -272,6 +449,250 @@ impl<'a> MasterPlaylist<'a> {
             .filter(move |media| stream.is_associated(media))
     }
 
+    /// Returns `true`, if the audio of the provided stream is demuxed (the
+    /// audio is in separate [`MediaPlaylist`]s) and `false` if the audio is
+    /// muxed into the stream itself.
+    ///
+    /// The audio is demuxed, if the stream has an audio group id and at least
+    /// one [`ExtXMedia`] tag of that group has a uri.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::MasterPlaylist;
+    /// use std::convert::TryFrom;
+    ///
+    /// let master_playlist = MasterPlaylist::try_from(concat!(
+    ///     "#EXTM3U\n",
+    ///     "#EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"aac\",NAME=\"English\",URI=\"eng.m3u8\"\n",
+    ///     "#EXT-X-STREAM-INF:BANDWIDTH=1280000,AUDIO=\"aac\"\n",
+    ///     "video.m3u8\n",
+    ///     "#EXT-X-STREAM-INF:BANDWIDTH=640000\n",
+    ///     "muxed.m3u8\n",
+    /// ))?;
+    ///
+    /// assert!(master_playlist.is_audio_demuxed(&master_playlist.variant_streams[0]));
+    /// assert!(!master_playlist.is_audio_demuxed(&master_playlist.variant_streams[1]));
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    ///
+    /// [`MediaPlaylist`]: crate::MediaPlaylist
+    #[must_use]
+    pub fn is_audio_demuxed(&self, stream: &VariantStream<'_>) -> bool {
+        if let VariantStream::ExtXStreamInf { audio: Some(_), .. } = stream {
+            self.associated_with(stream)
+                .any(|media| media.media_type == MediaType::Audio && media.uri().is_some())
+        } else {
+            false
+        }
+    }
+
+    /// Checks, that all [`VariantStream`]s, which share an audio group,
+    /// declare the same audio codecs.
+    ///
+    /// Switching between [`VariantStream`]s with different audio codecs is not
+    /// seamless, so a [`Warning::InconsistentAudioCodecs`] is returned for
+    /// every audio group, whose [`VariantStream`]s disagree. Streams without a
+    /// `CODECS` attribute are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::MasterPlaylist;
+    /// use hls_m3u8::Warning;
+    /// use std::convert::TryFrom;
+    ///
+    /// let master_playlist = MasterPlaylist::try_from(concat!(
+    ///     "#EXTM3U\n",
+    ///     "#EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"audio\",NAME=\"English\",URI=\"en.m3u8\"\n",
+    ///     "#EXT-X-STREAM-INF:BANDWIDTH=1280000,CODECS=\"avc1.640028,mp4a.40.2\",",
+    ///     "AUDIO=\"audio\"\n",
+    ///     "low.m3u8\n",
+    ///     "#EXT-X-STREAM-INF:BANDWIDTH=2560000,CODECS=\"avc1.640028,ec-3\",",
+    ///     "AUDIO=\"audio\"\n",
+    ///     "high.m3u8\n",
+    /// ))?;
+    ///
+    /// assert_eq!(
+    ///     master_playlist.validate_codec_consistency(),
+    ///     vec![Warning::InconsistentAudioCodecs {
+    ///         group: "audio".to_string(),
+    ///         codecs: vec!["mp4a.40.2".to_string(), "ec-3".to_string()],
+    ///     }]
+    /// );
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    #[must_use]
+    pub fn validate_codec_consistency(&self) -> Vec<Warning> {
+        // the prefixes of the sample entries of common audio codecs
+        const AUDIO_CODECS: &[&str] = &["mp4a", "ac-3", "ec-3", "ac-4", "Opus", "fLaC", "alac"];
+
+        let mut groups: Vec<(&str, Vec<BTreeSet<&str>>)> = vec![];
+
+        for stream in &self.variant_streams {
+            if let VariantStream::ExtXStreamInf {
+                audio: Some(group),
+                stream_data,
+                ..
+            } = stream
+            {
+                let codecs = {
+                    if let Some(codecs) = stream_data.codecs() {
+                        codecs
+                            .iter()
+                            .map(AsRef::as_ref)
+                            .filter(|codec| AUDIO_CODECS.iter().any(|p| codec.starts_with(p)))
+                            .collect::<BTreeSet<_>>()
+                    } else {
+                        continue;
+                    }
+                };
+
+                if let Some((_, sets)) = groups.iter_mut().find(|(id, _)| id == group) {
+                    sets.push(codecs);
+                } else {
+                    groups.push((group, vec![codecs]));
+                }
+            }
+        }
+
+        let mut result = vec![];
+
+        for (group, sets) in groups {
+            if sets.iter().all(|codecs| codecs == &sets[0]) {
+                continue;
+            }
+
+            let mut codecs: Vec<String> = vec![];
+
+            for codec in sets.iter().flat_map(|codecs| codecs.iter()) {
+                if !codecs.iter().any(|c| c == codec) {
+                    codecs.push((*codec).to_string());
+                }
+            }
+
+            result.push(Warning::InconsistentAudioCodecs {
+                group: group.to_string(),
+                codecs,
+            });
+        }
+
+        result
+    }
+
+    /// Groups the [`VariantStream`]s by their [`StreamData::pathway_id`].
+    ///
+    /// [`VariantStream`]s without a pathway belong to the default pathway
+    /// `"."`. The pathway of the [`ExtXContentSteering`] tag, which should be
+    /// used until the steering manifest has been loaded, is returned first.
+    /// The other pathways are returned in the order in which they appear in
+    /// the playlist.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::MasterPlaylist;
+    /// use std::convert::TryFrom;
+    ///
+    /// let master_playlist = MasterPlaylist::try_from(concat!(
+    ///     "#EXTM3U\n",
+    ///     "#EXT-X-CONTENT-STEERING:SERVER-URI=\"manifest.json\",PATHWAY-ID=\"CDN-B\"\n",
+    ///     "#EXT-X-STREAM-INF:BANDWIDTH=1280000,PATHWAY-ID=\"CDN-A\"\n",
+    ///     "https://a.example.com/hi.m3u8\n",
+    ///     "#EXT-X-STREAM-INF:BANDWIDTH=1280000,PATHWAY-ID=\"CDN-B\"\n",
+    ///     "https://b.example.com/hi.m3u8\n",
+    /// ))?;
+    ///
+    /// let pathways = master_playlist.variants_by_pathway();
+    ///
+    /// assert_eq!(pathways[0].0, "CDN-B");
+    /// assert_eq!(pathways[1].0, "CDN-A");
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    ///
+    /// [`StreamData::pathway_id`]: crate::types::StreamData::pathway_id
+    #[must_use]
+    pub fn variants_by_pathway(&self) -> Vec<(&str, Vec<&VariantStream<'a>>)> {
+        let mut result: Vec<(&str, Vec<&VariantStream<'a>>)> = vec![];
+
+        if let Some(pathway_id) = self
+            .content_steering
+            .as_ref()
+            .and_then(|value| value.pathway_id.as_ref())
+        {
+            result.push((pathway_id, vec![]));
+        }
+
+        for stream in &self.variant_streams {
+            let pathway_id = stream.pathway_id().map_or(".", |value| value.as_ref());
+
+            if let Some((_, streams)) = result.iter_mut().find(|(id, _)| *id == pathway_id) {
+                streams.push(stream);
+            } else {
+                result.push((pathway_id, vec![stream]));
+            }
+        }
+
+        result
+    }
+
+    /// Replaces the `URI` of every [`ExtXSessionKey`] with the result of the
+    /// provided function.
+    ///
+    /// This is the counterpart to [`MediaPlaylist::rewrite_key_uris`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::MasterPlaylist;
+    /// use std::convert::TryFrom;
+    ///
+    /// let mut master_playlist = MasterPlaylist::try_from(concat!(
+    ///     "#EXTM3U\n",
+    ///     "#EXT-X-SESSION-KEY:METHOD=AES-128,URI=\"https://example.com/key\"\n",
+    /// ))?;
+    ///
+    /// master_playlist.rewrite_key_uris(|uri| uri.replace("example.com", "proxy.example.com"));
+    ///
+    /// assert_eq!(
+    ///     master_playlist.session_keys[0].0.uri(),
+    ///     "https://proxy.example.com/key"
+    /// );
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    ///
+    /// [`MediaPlaylist::rewrite_key_uris`]: crate::MediaPlaylist::rewrite_key_uris
+    pub fn rewrite_key_uris<F: FnMut(&str) -> String>(&mut self, mut f: F) {
+        for session_key in &mut self.session_keys {
+            let uri = f(session_key.0.uri());
+            session_key.0.set_uri(uri);
+        }
+    }
+
+    /// Serializes the [`MasterPlaylist`] into UTF-8 encoded bytes, which can
+    /// for example be used as the body of a http response.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::MasterPlaylist;
+    /// use std::convert::TryFrom;
+    ///
+    /// let master_playlist = MasterPlaylist::try_from(concat!(
+    ///     "#EXTM3U\n",
+    ///     "#EXT-X-STREAM-INF:BANDWIDTH=1280000\n",
+    ///     "low.m3u8\n",
+    /// ))?;
+    ///
+    /// assert_eq!(
+    ///     master_playlist.to_bytes(),
+    ///     b"#EXTM3U\n#EXT-X-STREAM-INF:BANDWIDTH=1280000\nlow.m3u8\n".to_vec()
+    /// );
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> { self.to_string().into_bytes() }
+
     /// Makes the struct independent of its lifetime, by taking ownership of all
     /// internal [`Cow`]s.
     ///
@@ -284,6 +705,8 @@ impl<'a> MasterPlaylist<'a> {
         MasterPlaylist {
             has_independent_segments: self.has_independent_segments,
             start: self.start,
+            content_steering: self.content_steering.map(ExtXContentSteering::into_owned),
+            defines: self.defines.into_iter().map(|v| v.into_owned()).collect(),
             media: self.media.into_iter().map(|v| v.into_owned()).collect(),
             variant_streams: self
                 .variant_streams
@@ -315,6 +738,8 @@ impl<'a> RequiredVersion for MasterPlaylist<'a> {
             self.has_independent_segments
                 .athen_some(ExtXIndependentSegments),
             self.start,
+            self.content_steering,
+            self.defines,
             self.media,
             self.variant_streams,
             self.session_data,
@@ -339,6 +764,18 @@ impl<'a> MasterPlaylistBuilder<'a> {
     fn validate_variants(&self, variant_streams: &[VariantStream<'_>]) -> crate::Result<()> {
         let mut closed_captions_none = false;
 
+        // collecting the groups once keeps the validation linear, even for
+        // playlists with thousands of variant streams
+        let media_groups = self
+            .media
+            .iter()
+            .flatten()
+            .map(|media| (media.media_type, media.group_id().as_ref()))
+            .collect::<HashSet<_>>();
+
+        let check_media_group =
+            |media_type: MediaType, group_id: &str| media_groups.contains(&(media_type, group_id));
+
         for variant in variant_streams {
             match &variant {
                 VariantStream::ExtXStreamInf {
@@ -349,19 +786,19 @@ impl<'a> MasterPlaylistBuilder<'a> {
                     ..
                 } => {
                     if let Some(group_id) = &audio {
-                        if !self.check_media_group(MediaType::Audio, group_id) {
+                        if !check_media_group(MediaType::Audio, group_id) {
                             return Err(Error::unmatched_group(group_id));
                         }
                     }
 
                     if let Some(group_id) = &stream_data.video() {
-                        if !self.check_media_group(MediaType::Video, group_id) {
+                        if !check_media_group(MediaType::Video, group_id) {
                             return Err(Error::unmatched_group(group_id));
                         }
                     }
 
                     if let Some(group_id) = &subtitles {
-                        if !self.check_media_group(MediaType::Subtitles, group_id) {
+                        if !check_media_group(MediaType::Subtitles, group_id) {
                             return Err(Error::unmatched_group(group_id));
                         }
                     }
@@ -373,7 +810,7 @@ impl<'a> MasterPlaylistBuilder<'a> {
                                     return Err(Error::custom("ClosedCaptions has to be `None`"));
                                 }
 
-                                if !self.check_media_group(MediaType::ClosedCaptions, group_id) {
+                                if !check_media_group(MediaType::ClosedCaptions, group_id) {
                                     return Err(Error::unmatched_group(group_id));
                                 }
                             }
@@ -388,7 +825,7 @@ impl<'a> MasterPlaylistBuilder<'a> {
 
                 VariantStream::ExtXIFrame { stream_data, .. } => {
                     if let Some(group_id) = stream_data.video() {
-                        if !self.check_media_group(MediaType::Video, group_id) {
+                        if !check_media_group(MediaType::Video, group_id) {
                             return Err(Error::unmatched_group(group_id));
                         }
                     }
@@ -414,16 +851,6 @@ impl<'a> MasterPlaylistBuilder<'a> {
 
         Ok(())
     }
-
-    fn check_media_group<T: AsRef<str>>(&self, media_type: MediaType, group_id: T) -> bool {
-        if let Some(value) = &self.media {
-            value.iter().any(|media| {
-                media.media_type == media_type && media.group_id().as_ref() == group_id.as_ref()
-            })
-        } else {
-            false
-        }
-    }
 }
 
 impl<'a> RequiredVersion for MasterPlaylistBuilder<'a> {
@@ -437,6 +864,8 @@ impl<'a> RequiredVersion for MasterPlaylistBuilder<'a> {
                 .unwrap_or(false)
                 .athen_some(ExtXIndependentSegments),
             self.start.flatten(),
+            self.content_steering.clone().flatten(),
+            self.defines,
             self.media,
             self.variant_streams,
             self.session_data,
@@ -453,8 +882,11 @@ impl<'a> fmt::Display for MasterPlaylist<'a> {
             writeln!(f, "{}", ExtXVersion::new(self.required_version()))?;
         }
 
+        // the formatter is passed on, so that the alternate format applies to
+        // the tags as well
         for value in &self.media {
-            writeln!(f, "{}", value)?;
+            fmt::Display::fmt(value, f)?;
+            writeln!(f)?;
         }
 
         for value in &self.variant_streams {
@@ -474,6 +906,15 @@ impl<'a> fmt::Display for MasterPlaylist<'a> {
         }
 
         if let Some(value) = &self.start {
+            fmt::Display::fmt(value, f)?;
+            writeln!(f)?;
+        }
+
+        if let Some(value) = &self.content_steering {
+            writeln!(f, "{}", value)?;
+        }
+
+        for value in &self.defines {
             writeln!(f, "{}", value)?;
         }
 
@@ -485,20 +926,52 @@ impl<'a> fmt::Display for MasterPlaylist<'a> {
     }
 }
 
-impl<'a> TryFrom<&'a str> for MasterPlaylist<'a> {
-    type Error = Error;
-
-    fn try_from(input: &'a str) -> Result<Self, Self::Error> {
+impl<'a> MasterPlaylist<'a> {
+    /// Parses a [`MasterPlaylist`] with the given [`ParseOptions`] and returns
+    /// it together with all [`Warning`]s, that have been encountered.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::MasterPlaylist;
+    /// use hls_m3u8::{ParseOptions, Warning};
+    ///
+    /// let input = concat!(
+    ///     "#EXTM3U\n",
+    ///     "#EXT-X-STREAM-INF:RESOLUTION=1280x720\n",
+    ///     "http://example.com/720p.m3u8\n",
+    /// );
+    ///
+    /// // a variant stream without a bandwidth is invalid:
+    /// assert!(MasterPlaylist::parse_with(input, ParseOptions::strict()).is_err());
+    ///
+    /// let (master_playlist, warnings) = MasterPlaylist::parse_with(input, ParseOptions::lenient())?;
+    ///
+    /// assert_eq!(master_playlist.variant_streams[0].bandwidth(), 0);
+    /// assert_eq!(warnings.len(), 1);
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function fails, if the input is not a valid [`MasterPlaylist`].
+    pub fn parse_with(
+        input: &'a str,
+        options: ParseOptions,
+    ) -> crate::Result<(Self, Vec<Warning>)> {
         let input = tag(input, ExtM3u::PREFIX)?;
         let mut builder = Self::builder();
 
+        let mut defines = vec![];
         let mut media = vec![];
         let mut variant_streams = vec![];
         let mut session_data = vec![];
         let mut session_keys = vec![];
         let mut unknown_tags = vec![];
 
-        for line in Lines::from(input) {
+        let mut lines = Lines::with_options(input, options);
+
+        for line in &mut lines {
             match line? {
                 Line::Tag(tag) => {
                     match tag {
@@ -510,15 +983,21 @@ impl<'a> TryFrom<&'a str> for MasterPlaylist<'a> {
                         }
                         Tag::ExtInf(_)
                         | Tag::ExtXByteRange(_)
+                        | Tag::ExtXBitrate(_)
                         | Tag::ExtXDiscontinuity(_)
+                        | Tag::ExtXGap(_)
                         | Tag::ExtXKey(_)
                         | Tag::ExtXMap(_)
+                        | Tag::ExtXPart(_)
                         | Tag::ExtXProgramDateTime(_)
                         | Tag::ExtXDateRange(_)
                         | Tag::ExtXTargetDuration(_)
                         | Tag::ExtXMediaSequence(_)
                         | Tag::ExtXDiscontinuitySequence(_)
                         | Tag::ExtXEndList(_)
+                        | Tag::ExtXAllowCache(_)
+                        | Tag::ExtXPartInf(_)
+                        | Tag::ExtXServerControl(_)
                         | Tag::PlaylistType(_)
                         | Tag::ExtXIFramesOnly(_) => {
                             return Err(Error::unexpected_tag(tag));
@@ -541,10 +1020,16 @@ impl<'a> TryFrom<&'a str> for MasterPlaylist<'a> {
                         Tag::ExtXStart(t) => {
                             builder.start(t);
                         }
+                        Tag::ExtXContentSteering(t) => {
+                            builder.content_steering(t);
+                        }
+                        Tag::ExtXDefine(t) => {
+                            defines.push(t);
+                        }
                         Tag::Unknown(value) => {
                             // [6.3.1. General Client Responsibilities]
                             // > ignore any unrecognized tags.
-                            unknown_tags.push(Cow::Borrowed(value));
+                            unknown_tags.push(value);
                         }
                     }
                 }
@@ -555,13 +1040,32 @@ impl<'a> TryFrom<&'a str> for MasterPlaylist<'a> {
             }
         }
 
+        builder.defines(defines);
         builder.media(media);
         builder.variant_streams(variant_streams);
         builder.session_data(session_data);
         builder.session_keys(session_keys);
         builder.unknown_tags(unknown_tags);
 
-        builder.build().map_err(Error::builder)
+        let master_playlist = builder.build().map_err(Error::builder)?;
+
+        Ok((master_playlist, lines.warnings))
+    }
+}
+
+impl FromStr for MasterPlaylist<'static> {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Ok(MasterPlaylist::try_from(input)?.into_owned())
+    }
+}
+
+impl<'a> TryFrom<&'a str> for MasterPlaylist<'a> {
+    type Error = Error;
+
+    fn try_from(input: &'a str) -> Result<Self, Self::Error> {
+        Self::parse_with(input, ParseOptions::strict()).map(|(master_playlist, _)| master_playlist)
     }
 }
 
@@ -637,6 +1141,425 @@ mod tests {
         assert_eq!(audio_streams.next(), None);
     }
 
+    #[test]
+    fn test_validate_codec_consistency() {
+        let master_playlist = MasterPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"aac\",NAME=\"English\",URI=\"aac.m3u8\"\n",
+            "#EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"mixed\",NAME=\"English\",URI=\"mixed.m3u8\"\n",
+            "#EXT-X-STREAM-INF:BANDWIDTH=1280000,CODECS=\"avc1.640028,mp4a.40.2\",AUDIO=\"aac\"\n",
+            "aac-low.m3u8\n",
+            "#EXT-X-STREAM-INF:BANDWIDTH=2560000,CODECS=\"hvc1.2.4.L123.B0,mp4a.40.2\",",
+            "AUDIO=\"aac\"\n",
+            "aac-high.m3u8\n",
+            "#EXT-X-STREAM-INF:BANDWIDTH=1280000,CODECS=\"avc1.640028,mp4a.40.2\",",
+            "AUDIO=\"mixed\"\n",
+            "mixed-low.m3u8\n",
+            "#EXT-X-STREAM-INF:BANDWIDTH=2560000,AUDIO=\"mixed\"\n",
+            "mixed-unknown.m3u8\n",
+            "#EXT-X-STREAM-INF:BANDWIDTH=5120000,CODECS=\"avc1.640028,ec-3\",AUDIO=\"mixed\"\n",
+            "mixed-high.m3u8\n",
+        ))
+        .unwrap();
+
+        assert_eq!(
+            master_playlist.validate_codec_consistency(),
+            vec![Warning::InconsistentAudioCodecs {
+                group: "mixed".to_string(),
+                codecs: vec!["mp4a.40.2".to_string(), "ec-3".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_to_bytes() {
+        let master_playlist = MasterPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"audio\",NAME=\"Français\",URI=\"fr.m3u8\"\n",
+            "#EXT-X-STREAM-INF:BANDWIDTH=1280000,AUDIO=\"audio\"\n",
+            "low.m3u8\n",
+        ))
+        .unwrap();
+
+        assert_eq!(
+            master_playlist.to_bytes(),
+            master_playlist.to_string().into_bytes()
+        );
+    }
+
+    #[test]
+    fn test_variants_by_pathway() {
+        let master_playlist = MasterPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-CONTENT-STEERING:SERVER-URI=\"/steering\",PATHWAY-ID=\"CDN-B\"\n",
+            "#EXT-X-STREAM-INF:BANDWIDTH=1280000,PATHWAY-ID=\"CDN-A\"\n",
+            "https://a.example.com/hi.m3u8\n",
+            "#EXT-X-STREAM-INF:BANDWIDTH=640000,PATHWAY-ID=\"CDN-A\"\n",
+            "https://a.example.com/lo.m3u8\n",
+            "#EXT-X-STREAM-INF:BANDWIDTH=1280000,PATHWAY-ID=\"CDN-B\"\n",
+            "https://b.example.com/hi.m3u8\n",
+            "#EXT-X-STREAM-INF:BANDWIDTH=640000,PATHWAY-ID=\"CDN-B\"\n",
+            "https://b.example.com/lo.m3u8\n",
+        ))
+        .unwrap();
+
+        assert_eq!(
+            master_playlist.content_steering,
+            Some(ExtXContentSteering {
+                server_uri: "/steering".into(),
+                pathway_id: Some("CDN-B".into()),
+            })
+        );
+
+        assert_eq!(
+            master_playlist
+                .variants_by_pathway()
+                .into_iter()
+                .map(|(pathway_id, streams)| {
+                    (
+                        pathway_id,
+                        streams
+                            .into_iter()
+                            .map(|stream| {
+                                match stream {
+                                    VariantStream::ExtXStreamInf { uri, .. }
+                                    | VariantStream::ExtXIFrame { uri, .. } => uri.as_ref(),
+                                }
+                            })
+                            .collect::<Vec<_>>(),
+                    )
+                })
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "CDN-B",
+                    vec![
+                        "https://b.example.com/hi.m3u8",
+                        "https://b.example.com/lo.m3u8"
+                    ]
+                ),
+                (
+                    "CDN-A",
+                    vec![
+                        "https://a.example.com/hi.m3u8",
+                        "https://a.example.com/lo.m3u8"
+                    ]
+                ),
+            ]
+        );
+
+        // the tag is written back:
+        assert_eq!(
+            MasterPlaylist::try_from(master_playlist.to_string().as_str()).unwrap(),
+            master_playlist
+        );
+
+        // streams without a pathway belong to the default pathway:
+        let master_playlist = MasterPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-STREAM-INF:BANDWIDTH=1280000\n",
+            "https://example.com/hi.m3u8\n",
+        ))
+        .unwrap();
+
+        assert_eq!(master_playlist.variants_by_pathway()[0].0, ".");
+    }
+
+    #[test]
+    fn test_rewrite_key_uris() {
+        let mut master_playlist = MasterPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-SESSION-KEY:METHOD=AES-128,URI=\"https://example.com/a\"\n",
+            "#EXT-X-SESSION-KEY:METHOD=SAMPLE-AES,URI=\"skd://b\"\n",
+        ))
+        .unwrap();
+
+        master_playlist.rewrite_key_uris(|uri| format!("https://license.example.com/?uri={}", uri));
+
+        assert_eq!(
+            master_playlist
+                .session_keys
+                .iter()
+                .map(|key| key.0.uri().as_ref())
+                .collect::<Vec<_>>(),
+            vec![
+                "https://license.example.com/?uri=https://example.com/a",
+                "https://license.example.com/?uri=skd://b"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_is_audio_demuxed() {
+        // audio is muxed into the variant streams, the group only describes the
+        // renditions:
+        let muxed = MasterPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"aac\",NAME=\"English\",DEFAULT=YES,",
+            "AUTOSELECT=YES,LANGUAGE=\"en\"\n",
+            "#EXT-X-STREAM-INF:BANDWIDTH=1280000,CODECS=\"avc1.42e00a,mp4a.40.2\",AUDIO=\"aac\"\n",
+            "low/video.m3u8\n",
+            "#EXT-X-STREAM-INF:BANDWIDTH=640000,CODECS=\"avc1.42e00a,mp4a.40.2\"\n",
+            "lower/video.m3u8\n",
+        ))
+        .unwrap();
+
+        assert!(muxed
+            .variant_streams
+            .iter()
+            .all(|stream| !muxed.is_audio_demuxed(stream)));
+
+        // audio is in separate media playlists:
+        let demuxed = MasterPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"aac\",NAME=\"English\",DEFAULT=YES,",
+            "AUTOSELECT=YES,LANGUAGE=\"en\",URI=\"audio/eng.m3u8\"\n",
+            "#EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"aac\",NAME=\"Deutsch\",",
+            "LANGUAGE=\"de\",URI=\"audio/deu.m3u8\"\n",
+            "#EXT-X-STREAM-INF:BANDWIDTH=1280000,CODECS=\"avc1.42e00a,mp4a.40.2\",AUDIO=\"aac\"\n",
+            "low/video.m3u8\n",
+            "#EXT-X-I-FRAME-STREAM-INF:BANDWIDTH=86000,URI=\"low/iframe.m3u8\"\n",
+        ))
+        .unwrap();
+
+        assert!(demuxed.is_audio_demuxed(&demuxed.variant_streams[0]));
+        assert!(!demuxed.is_audio_demuxed(&demuxed.variant_streams[1]));
+    }
+
+    #[test]
+    fn test_parser_missing_bandwidth() {
+        let input = concat!(
+            "#EXTM3U\n",
+            "#EXT-X-STREAM-INF:CODECS=\"avc1.42e00a,mp4a.40.2\",RESOLUTION=416x234\n",
+            "http://example.com/low/index.m3u8\n",
+            "#EXT-X-STREAM-INF:BANDWIDTH=240000\n",
+            "http://example.com/lo_mid/index.m3u8\n",
+        );
+
+        // strict:
+        assert_eq!(
+            MasterPlaylist::parse_with(input, ParseOptions::strict()),
+            Err(Error::missing_value("BANDWIDTH"))
+        );
+        assert!(MasterPlaylist::try_from(input).is_err());
+
+        // lenient:
+        let (master_playlist, warnings) =
+            MasterPlaylist::parse_with(input, ParseOptions::lenient()).unwrap();
+
+        assert_eq!(
+            master_playlist
+                .variant_streams
+                .iter()
+                .map(|stream| stream.bandwidth())
+                .collect::<Vec<_>>(),
+            vec![0, 240_000]
+        );
+
+        assert_eq!(
+            warnings,
+            vec![Warning::MissingAttribute {
+                tag: "#EXT-X-STREAM-INF".to_string(),
+                attribute: "BANDWIDTH".to_string(),
+                default: "0".to_string(),
+            }]
+        );
+
+        // a valid playlist has no warnings:
+        assert_eq!(
+            MasterPlaylist::parse_with(
+                concat!(
+                    "#EXTM3U\n",
+                    "#EXT-X-STREAM-INF:BANDWIDTH=240000\n",
+                    "http://example.com/lo_mid/index.m3u8\n",
+                ),
+                ParseOptions::lenient()
+            )
+            .unwrap()
+            .1,
+            vec![]
+        );
+    }
+
+    #[test]
+    fn test_parser_unknown_attribute() {
+        let input = concat!(
+            "#EXTM3U\n",
+            "#EXT-X-STREAM-INF:BANDWIDTH=240000,X-VENDOR-ID=42\n",
+            "http://example.com/lo_mid/index.m3u8\n",
+            "#EXT-X-I-FRAME-STREAM-INF:BANDWIDTH=86000,URI=\"low/iframe.m3u8\",X-VENDOR-ID=43\n",
+        );
+
+        // unknown attributes are ignored in both modes:
+        let (strict, warnings) = MasterPlaylist::parse_with(input, ParseOptions::strict()).unwrap();
+
+        assert_eq!(warnings, vec![]);
+
+        let (lenient, warnings) =
+            MasterPlaylist::parse_with(input, ParseOptions::lenient()).unwrap();
+
+        assert_eq!(strict, lenient);
+
+        // but are only reported in lenient mode:
+        assert_eq!(
+            warnings,
+            vec![
+                Warning::UnknownAttribute {
+                    tag: "#EXT-X-STREAM-INF".to_string(),
+                    attribute: "X-VENDOR-ID".to_string(),
+                },
+                Warning::UnknownAttribute {
+                    tag: "#EXT-X-I-FRAME-STREAM-INF".to_string(),
+                    attribute: "X-VENDOR-ID".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_forced_subtitle() {
+        let master_playlist = MasterPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-MEDIA:TYPE=SUBTITLES,GROUP-ID=\"subs\",NAME=\"English\",",
+            "LANGUAGE=\"en\",URI=\"en.m3u8\"\n",
+            "#EXT-X-MEDIA:TYPE=SUBTITLES,GROUP-ID=\"subs\",NAME=\"Deutsch (forced)\",",
+            "LANGUAGE=\"de\",FORCED=YES,URI=\"de-forced.m3u8\"\n",
+            "#EXT-X-MEDIA:TYPE=SUBTITLES,GROUP-ID=\"subs\",NAME=\"English (forced)\",",
+            "LANGUAGE=\"en\",FORCED=YES,URI=\"en-forced.m3u8\"\n",
+            "#EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"audio\",NAME=\"English\",",
+            "LANGUAGE=\"en\",URI=\"audio-en.m3u8\"\n",
+        ))
+        .unwrap();
+
+        assert_eq!(
+            master_playlist
+                .forced_subtitle("en")
+                .map(|m| m.name().as_ref()),
+            Some("English (forced)")
+        );
+        assert_eq!(
+            master_playlist
+                .forced_subtitle("DE")
+                .map(|m| m.name().as_ref()),
+            Some("Deutsch (forced)")
+        );
+        assert_eq!(master_playlist.forced_subtitle("fr"), None);
+
+        // the `FORCED` attribute only applies to subtitles
+        assert!(MasterPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"audio\",NAME=\"English\",",
+            "LANGUAGE=\"en\",FORCED=YES,URI=\"audio-en.m3u8\"\n",
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn test_video_renditions() {
+        let master_playlist = MasterPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-MEDIA:TYPE=VIDEO,GROUP-ID=\"angles\",NAME=\"Main\",",
+            "DEFAULT=YES,URI=\"main.m3u8\"\n",
+            "#EXT-X-MEDIA:TYPE=VIDEO,GROUP-ID=\"angles\",NAME=\"Onboard\",",
+            "CHARACTERISTICS=\"com.example.onboard,com.example.driver\",URI=\"onboard.m3u8\"\n",
+            "#EXT-X-MEDIA:TYPE=VIDEO,GROUP-ID=\"other\",NAME=\"Onboard\",",
+            "CHARACTERISTICS=\"com.example.onboard\",URI=\"other-onboard.m3u8\"\n",
+            "#EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"audio\",NAME=\"English\",",
+            "URI=\"audio-en.m3u8\"\n",
+            "#EXT-X-STREAM-INF:BANDWIDTH=1280000,VIDEO=\"angles\",AUDIO=\"audio\"\n",
+            "main.m3u8\n",
+        ))
+        .unwrap();
+
+        assert_eq!(
+            master_playlist
+                .video_renditions()
+                .map(|m| m.uri().map(|v| v.as_ref()))
+                .collect::<Vec<_>>(),
+            vec![
+                Some("main.m3u8"),
+                Some("onboard.m3u8"),
+                Some("other-onboard.m3u8")
+            ]
+        );
+
+        assert_eq!(
+            master_playlist
+                .video_renditions_in_group("angles")
+                .map(|m| m.name().as_ref())
+                .collect::<Vec<_>>(),
+            vec!["Main", "Onboard"]
+        );
+
+        assert_eq!(
+            master_playlist
+                .video_rendition_with_characteristic("angles", "com.example.driver")
+                .and_then(|m| m.uri())
+                .map(|v| v.as_ref()),
+            Some("onboard.m3u8")
+        );
+        assert_eq!(
+            master_playlist
+                .video_rendition_with_characteristic("other", "com.example.onboard")
+                .and_then(|m| m.uri())
+                .map(|v| v.as_ref()),
+            Some("other-onboard.m3u8")
+        );
+        assert_eq!(
+            master_playlist.video_rendition_with_characteristic("angles", "com.example"),
+            None
+        );
+        assert_eq!(
+            master_playlist.video_rendition_with_characteristic("audio", "com.example.onboard"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_variants_with_video_codec() {
+        let master_playlist = MasterPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-STREAM-INF:BANDWIDTH=1280000,CODECS=\"avc1.640028,mp4a.40.2\"\n",
+            "avc/720p.m3u8\n",
+            "#EXT-X-STREAM-INF:BANDWIDTH=2560000,CODECS=\"avc1.64002a,mp4a.40.2\"\n",
+            "avc/1080p.m3u8\n",
+            "#EXT-X-STREAM-INF:BANDWIDTH=1920000,CODECS=\"hvc1.2.4.L123.B0,mp4a.40.2\"\n",
+            "hevc/1080p.m3u8\n",
+            "#EXT-X-I-FRAME-STREAM-INF:BANDWIDTH=86000,CODECS=\"hvc1.2.4.L123.B0\",",
+            "URI=\"hevc/iframe.m3u8\"\n",
+            "#EXT-X-STREAM-INF:BANDWIDTH=64000\n",
+            "audio.m3u8\n",
+        ))
+        .unwrap();
+
+        let uris = |prefix| {
+            master_playlist
+                .variants_with_video_codec(prefix)
+                .map(|stream| {
+                    match stream {
+                        VariantStream::ExtXStreamInf { uri, .. }
+                        | VariantStream::ExtXIFrame { uri, .. } => uri.to_string(),
+                    }
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            uris("avc1"),
+            vec!["avc/720p.m3u8".to_string(), "avc/1080p.m3u8".to_string()]
+        );
+
+        assert_eq!(
+            uris("hvc1"),
+            vec![
+                "hevc/1080p.m3u8".to_string(),
+                "hevc/iframe.m3u8".to_string()
+            ]
+        );
+
+        assert_eq!(uris("av01"), Vec::<String>::new());
+    }
+
     #[test]
     fn test_parser() {
         assert_eq!(
@@ -730,6 +1653,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parser_missing_trailing_newline() {
+        assert_eq!(
+            MasterPlaylist::try_from(concat!(
+                "#EXTM3U\n",
+                "#EXT-X-STREAM-INF:BANDWIDTH=64000,CODECS=\"mp4a.40.5\"\n",
+                "http://example.com/audio/index.m3u8"
+            ))
+            .unwrap(),
+            MasterPlaylist::builder()
+                .variant_streams(vec![VariantStream::ExtXStreamInf {
+                    uri: "http://example.com/audio/index.m3u8".into(),
+                    frame_rate: None,
+                    audio: None,
+                    subtitles: None,
+                    closed_captions: None,
+                    stream_data: StreamData::builder()
+                        .bandwidth(64000)
+                        .codecs(&["mp4a.40.5"])
+                        .build()
+                        .unwrap()
+                }])
+                .build()
+                .unwrap()
+        );
+
+        assert!(MasterPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-STREAM-INF:BANDWIDTH=64000,CODECS=\"mp4a.40.5\""
+        ))
+        .is_err());
+    }
+
     #[test]
     fn test_display() {
         assert_eq!(
@@ -828,4 +1784,43 @@ mod tests {
             .to_string()
         );
     }
+
+    #[test]
+    fn test_display_alternate() {
+        let playlist = MasterPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"audio\",NAME=\"English\",",
+            "URI=\"audio/en.m3u8\"\n",
+            "#EXT-X-STREAM-INF:BANDWIDTH=150000,AUDIO=\"audio\"\n",
+            "low/index.m3u8\n",
+            "#EXT-X-START:TIME-OFFSET=10\n",
+        ))
+        .unwrap();
+
+        assert_eq!(
+            format!("{}", playlist),
+            concat!(
+                "#EXTM3U\n",
+                "#EXT-X-MEDIA:TYPE=AUDIO,URI=\"audio/en.m3u8\",GROUP-ID=\"audio\",",
+                "NAME=\"English\"\n",
+                "#EXT-X-STREAM-INF:BANDWIDTH=150000,AUDIO=\"audio\"\n",
+                "low/index.m3u8\n",
+                "#EXT-X-START:TIME-OFFSET=10\n",
+            )
+            .to_string()
+        );
+
+        assert_eq!(
+            format!("{:#}", playlist),
+            concat!(
+                "#EXTM3U\n",
+                "#EXT-X-MEDIA:TYPE=AUDIO,URI=\"audio/en.m3u8\",GROUP-ID=\"audio\",",
+                "NAME=\"English\",DEFAULT=NO,AUTOSELECT=NO,FORCED=NO\n",
+                "#EXT-X-STREAM-INF:BANDWIDTH=150000,AUDIO=\"audio\"\n",
+                "low/index.m3u8\n",
+                "#EXT-X-START:TIME-OFFSET=10,PRECISE=NO\n",
+            )
+            .to_string()
+        );
+    }
 }
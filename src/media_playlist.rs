@@ -1,27 +1,46 @@
 use std::borrow::Cow;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::convert::TryFrom;
 use std::fmt;
+use std::io::{BufRead, BufReader, Read};
+use std::iter;
+use std::mem;
+use std::ops::RangeInclusive;
 use std::str::FromStr;
 use std::time::Duration;
 
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, FixedOffset};
 use derive_builder::Builder;
+#[cfg(feature = "flate2")]
+use flate2::read::GzDecoder;
+use stable_vec::core::DefaultCore;
+use stable_vec::iter::Values;
 use stable_vec::StableVec;
+#[cfg(feature = "url")]
+use url::{ParseError, Url};
 
 use crate::line::{Line, Lines, Tag};
 use crate::media_segment::MediaSegment;
 use crate::tags::{
-    ExtM3u, ExtXByteRange, ExtXDiscontinuitySequence, ExtXEndList, ExtXIFramesOnly,
-    ExtXIndependentSegments, ExtXKey, ExtXMediaSequence, ExtXStart, ExtXTargetDuration,
-    ExtXVersion,
+    ExtM3u, ExtXAllowCache, ExtXByteRange, ExtXDateRange, ExtXDefine, ExtXDiscontinuitySequence,
+    ExtXEndList, ExtXIFramesOnly, ExtXIndependentSegments, ExtXKey, ExtXMediaSequence, ExtXPart,
+    ExtXPartInf, ExtXServerControl, ExtXStart, ExtXTargetDuration, ExtXVersion,
 };
 use crate::types::{
-    DecryptionKey, EncryptionMethod, InitializationVector, KeyFormat, PlaylistType, ProtocolVersion,
+    DecryptionKey, DurationStats, EncryptionMethod, InitializationVector, KeyFormat, PlaylistType,
+    ProtocolVersion,
 };
-use crate::utils::{tag, BoolExt};
-use crate::{Error, RequiredVersion};
+#[cfg(not(feature = "chrono"))]
+use crate::utils::date_time_sort_key;
+use crate::utils::{substitute_variables, tag, BoolExt};
+use crate::{Error, ParseOptions, RequiredVersion, SegmentTag, Warning};
 
 /// Media playlist.
+///
+/// The alternate format (`{:#}`) is passed to the [`ExtXStart`] tag, so that
+/// its `PRECISE` attribute is written explicitly, even if it is equal to its
+/// default value.
 #[derive(Builder, Debug, Clone, PartialEq, Eq)]
 #[builder(build_fn(skip), setter(strip_option))]
 #[non_exhaustive]
@@ -39,6 +58,10 @@ pub struct MediaPlaylist<'a> {
     /// ### Note
     ///
     /// This field is optional and by default a value of 0 is assumed.
+    ///
+    /// A [`MediaSegment::number`], that is derived from this field, must not
+    /// exceed [`usize::MAX`], otherwise an error will be returned instead of
+    /// overflowing.
     #[builder(default)]
     pub media_sequence: usize,
     /// Allows synchronization between different renditions of the same
@@ -63,6 +86,15 @@ pub struct MediaPlaylist<'a> {
     /// This field is optional.
     #[builder(default, setter(into))]
     pub playlist_type: Option<PlaylistType>,
+    /// Indicates whether the client may cache downloaded [`MediaSegment`]s.
+    ///
+    /// ### Note
+    ///
+    /// This field is optional and deprecated, because the `EXT-X-ALLOW-CACHE`
+    /// tag has been removed in [`ProtocolVersion::V7`]. It only exists, so
+    /// that legacy playlists can be round-tripped.
+    #[builder(default, setter(into))]
+    pub allow_cache: Option<bool>,
     /// Indicates that each [`MediaSegment`] in the playlist describes a single
     /// I-frame. I-frames are encoded video frames, whose decoding does not
     /// depend on any other frame. I-frame Playlists can be used for trick
@@ -91,6 +123,32 @@ pub struct MediaPlaylist<'a> {
     /// This field is optional.
     #[builder(default, setter(into))]
     pub start: Option<ExtXStart>,
+    /// Indicates, which delivery directives for low latency streaming are
+    /// supported by the server.
+    ///
+    /// ### Note
+    ///
+    /// This field is optional, but it must have an
+    /// [`ExtXServerControl::part_hold_back`], if any [`MediaSegment`] has
+    /// [`MediaSegment::parts`].
+    #[builder(default, setter(into))]
+    pub server_control: Option<ExtXServerControl>,
+    /// The target duration of the partial segments ([`MediaSegment::parts`]).
+    ///
+    /// ### Note
+    ///
+    /// This field is optional, but required if any [`MediaSegment`] has
+    /// [`MediaSegment::parts`].
+    #[builder(default, setter(into))]
+    pub part_target: Option<Duration>,
+    /// A list of all [`ExtXDefine`] tags, which provide the variables, that
+    /// can be referenced in the `URI`s of this [`MediaPlaylist`].
+    ///
+    /// ### Note
+    ///
+    /// This field is optional.
+    #[builder(default, setter(into))]
+    pub defines: Vec<ExtXDefine<'a>>,
     /// Indicates that no more [`MediaSegment`]s will be added to the
     /// [`MediaPlaylist`] file.
     ///
@@ -109,6 +167,15 @@ pub struct MediaPlaylist<'a> {
     /// This field is required.
     #[builder(setter(custom))]
     pub segments: StableVec<MediaSegment<'a>>,
+    /// The partial segments ([`ExtXPart`]) of the [`MediaSegment`], that is
+    /// still being produced by the server and therefore has no `#EXTINF` tag
+    /// and no `URI` yet.
+    ///
+    /// ### Note
+    ///
+    /// This field is optional and only used by low latency playlists.
+    #[builder(default, setter(into))]
+    pub incomplete_parts: Vec<ExtXPart<'a>>,
     /// The allowable excess duration of each media segment in the
     /// associated playlist.
     ///
@@ -132,8 +199,26 @@ pub struct MediaPlaylist<'a> {
     /// This field is optional.
     #[builder(default, setter(into))]
     pub unknown: Vec<Cow<'a, str>>,
+    /// The lines from which the segments have been parsed, if the playlist
+    /// has been parsed with [`ParseOptions::retain_source_lines`].
+    #[builder(setter(skip))]
+    pub(crate) source_lines: SourceLines,
+}
+
+/// The (1-based) lines of the input, from which each [`MediaSegment`] has
+/// been parsed, in the same order as the segments.
+///
+/// The lines do not change the meaning of a [`MediaPlaylist`], so they are
+/// ignored when comparing playlists.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SourceLines(Vec<RangeInclusive<usize>>);
+
+impl PartialEq for SourceLines {
+    fn eq(&self, _: &Self) -> bool { true }
 }
 
+impl Eq for SourceLines {}
+
 impl<'a> MediaPlaylistBuilder<'a> {
     fn validate(&self) -> Result<(), String> {
         if let Some(target_duration) = &self.target_duration {
@@ -242,7 +327,7 @@ impl<'a> MediaPlaylistBuilder<'a> {
 
     /// Parse the rest of the [`MediaPlaylist`] from an m3u8 file.
     pub fn parse(&mut self, input: &'a str) -> crate::Result<MediaPlaylist<'a>> {
-        parse_media_playlist(input, self)
+        parse_media_playlist(input, self, ParseOptions::strict()).map(|(playlist, _)| playlist)
     }
 
     /// Adds segments to the resulting playlist and assigns a
@@ -279,7 +364,14 @@ impl<'a> MediaPlaylistBuilder<'a> {
     ///
     /// # Errors
     ///
-    /// If a required field has not been initialized.
+    /// If a required field has not been initialized or if the number of a
+    /// [`MediaSegment`] would exceed [`usize::MAX`].
+    ///
+    /// # Note
+    ///
+    /// A byte range of an [`ExtXPart`] without a start continues the previous
+    /// [`ExtXPart`] with the same `URI`. If there is no such part, the byte
+    /// range is assumed to start at zero.
     pub fn build(&self) -> Result<MediaPlaylist<'a>, String> {
         // validate builder
         self.validate()?;
@@ -302,11 +394,36 @@ impl<'a> MediaPlaylistBuilder<'a> {
         }
 
         let mut previous_range: Option<ExtXByteRange> = None;
+        // the end of the last byte range of a partial segment for each resource
+        let mut part_ranges: HashMap<String, usize> = HashMap::new();
+
+        // partial segments continue the previous partial segment of the same
+        // resource, if their byte range has no lower bound. Without a previous
+        // partial segment, the byte range is assumed to start at zero (like the
+        // byte range of a `MediaSegment`).
+        let mut resolve_part_range = |part: &mut ExtXPart<'a>| {
+            if let Some(mut range) = part.byte_range() {
+                if range.start().is_none() {
+                    let start = part_ranges.get(&**part.uri()).copied().unwrap_or(0);
+
+                    range = range.saturating_add(start);
+                    range.set_start(Some(start));
+                    part.set_byte_range(Some(range));
+                }
+
+                part_ranges.insert(part.uri().to_string(), range.end());
+            }
+        };
 
         for (i, segment) in segments.iter_mut() {
             // assign the correct number to all implcitly numbered segments:
             if !segment.explicit_number {
-                segment.number = i + sequence_number;
+                segment.number = sequence_number.checked_add(i).ok_or_else(|| {
+                    format!(
+                        "the number of the segment ({}) overflows the media sequence ({})",
+                        i, sequence_number
+                    )
+                })?;
             }
 
             // add the segment number as iv, if the iv is missing:
@@ -340,6 +457,16 @@ impl<'a> MediaPlaylistBuilder<'a> {
 
                 previous_range = segment.byte_range;
             }
+
+            for part in &mut segment.parts {
+                resolve_part_range(part);
+            }
+        }
+
+        let mut incomplete_parts = self.incomplete_parts.clone().unwrap_or_else(Vec::new);
+
+        for part in &mut incomplete_parts {
+            resolve_part_range(part);
         }
 
         // TODO: can segments be missing?
@@ -360,15 +487,21 @@ impl<'a> MediaPlaylistBuilder<'a> {
             media_sequence: self.media_sequence.unwrap_or(0),
             discontinuity_sequence: self.discontinuity_sequence.unwrap_or(0),
             playlist_type: self.playlist_type.unwrap_or(None),
+            allow_cache: self.allow_cache.unwrap_or(None),
             has_i_frames_only: self.has_i_frames_only.unwrap_or(false),
             has_independent_segments: self.has_independent_segments.unwrap_or(false),
             start: self.start.unwrap_or(None),
+            server_control: self.server_control.unwrap_or(None),
+            part_target: self.part_target.unwrap_or(None),
+            defines: self.defines.clone().unwrap_or_else(Vec::new),
             has_end_list: self.has_end_list.unwrap_or(false),
             segments,
+            incomplete_parts,
             allowable_excess_duration: self
                 .allowable_excess_duration
                 .unwrap_or_else(|| Duration::from_secs(0)),
             unknown: self.unknown.clone().unwrap_or_else(Vec::new),
+            source_lines: SourceLines::default(),
         })
     }
 }
@@ -382,6 +515,7 @@ impl<'a> RequiredVersion for MediaPlaylistBuilder<'a> {
             (self.discontinuity_sequence.unwrap_or(0) != 0)
                 .athen(|| ExtXDiscontinuitySequence(self.discontinuity_sequence.unwrap_or(0))),
             self.playlist_type,
+            self.allow_cache.flatten().map(ExtXAllowCache::new),
             self.has_i_frames_only
                 .unwrap_or(false)
                 .athen_some(ExtXIFramesOnly),
@@ -389,8 +523,12 @@ impl<'a> RequiredVersion for MediaPlaylistBuilder<'a> {
                 .unwrap_or(false)
                 .athen_some(ExtXIndependentSegments),
             self.start,
+            self.server_control,
+            self.part_target.flatten().map(ExtXPartInf),
+            self.defines,
             self.has_end_list.unwrap_or(false).athen_some(ExtXEndList),
-            self.segments
+            self.segments,
+            self.incomplete_parts
         ]
     }
 }
@@ -401,6 +539,64 @@ impl<'a> MediaPlaylist<'a> {
     #[inline]
     pub fn builder() -> MediaPlaylistBuilder<'a> { MediaPlaylistBuilder::default() }
 
+    /// Makes a new [`MediaPlaylist`] from the given [`MediaSegment`]s.
+    ///
+    /// The [`MediaPlaylist::target_duration`] is the duration of the longest
+    /// [`MediaSegment`] rounded up to the next full second, because the
+    /// target duration of a playlist must be an integer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::{MediaPlaylist, MediaSegment};
+    /// use std::time::Duration;
+    ///
+    /// let playlist = MediaPlaylist::from_segments(vec![
+    ///     MediaSegment::builder()
+    ///         .duration(Duration::from_millis(9600))
+    ///         .uri("segment0.ts")
+    ///         .build()
+    ///         .unwrap(),
+    ///     MediaSegment::builder()
+    ///         .duration(Duration::from_secs(8))
+    ///         .uri("segment1.ts")
+    ///         .build()
+    ///         .unwrap(),
+    /// ])?;
+    ///
+    /// assert_eq!(playlist.target_duration, Duration::from_secs(10));
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function fails, if the [`MediaSegment`]s are not valid (see
+    /// [`MediaPlaylistBuilder::build`]).
+    ///
+    /// [`MediaPlaylistBuilder::build`]: crate::builder::MediaPlaylistBuilder::build
+    pub fn from_segments<I>(segments: I) -> crate::Result<Self>
+    where
+        I: IntoIterator<Item = MediaSegment<'a>>,
+    {
+        let mut builder = Self::builder();
+        builder.segments(vec![]);
+
+        let mut target_duration = Duration::from_secs(0);
+
+        for segment in segments {
+            let duration = segment.duration.duration();
+            let seconds = duration.as_secs() + u64::from(duration.subsec_nanos() > 0);
+
+            target_duration = target_duration.max(Duration::from_secs(seconds));
+            builder.push_segment(segment);
+        }
+
+        builder
+            .target_duration(target_duration)
+            .build()
+            .map_err(Error::builder)
+    }
+
     /// Computes the `Duration` of the [`MediaPlaylist`], by adding each segment
     /// duration together.
     #[must_use]
@@ -408,464 +604,3527 @@ impl<'a> MediaPlaylist<'a> {
         self.segments.values().map(|s| s.duration.duration()).sum()
     }
 
-    /// Makes the struct independent of its lifetime, by taking ownership of all
-    /// internal [`Cow`]s.
+    /// Returns the [`MediaPlaylist::part_target`] in seconds, which is for
+    /// example needed by low latency clients to compute how often the
+    /// playlist should be reloaded.
     ///
-    /// # Note
+    /// # Example
     ///
-    /// This is a relatively expensive operation.
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// use std::convert::TryFrom;
+    /// use std::time::Duration;
+    ///
+    /// let playlist = MediaPlaylist::try_from(concat!(
+    ///     "#EXTM3U\n",
+    ///     "#EXT-X-TARGETDURATION:4\n",
+    ///     "#EXT-X-PART-INF:PART-TARGET=0.33334\n",
+    ///     "#EXT-X-SERVER-CONTROL:PART-HOLD-BACK=1.0\n",
+    ///     "#EXTINF:4,\n",
+    ///     "segment0.ts\n",
+    /// ))?;
+    ///
+    /// assert_eq!(
+    ///     playlist.part_target,
+    ///     Some(Duration::from_nanos(333_340_000))
+    /// );
+    /// assert_eq!(playlist.part_target_secs(), Some(0.33334));
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
     #[must_use]
-    pub fn into_owned(self) -> MediaPlaylist<'static> {
-        MediaPlaylist {
-            target_duration: self.target_duration,
-            media_sequence: self.media_sequence,
-            discontinuity_sequence: self.discontinuity_sequence,
-            playlist_type: self.playlist_type,
-            has_i_frames_only: self.has_i_frames_only,
-            has_independent_segments: self.has_independent_segments,
-            start: self.start,
-            has_end_list: self.has_end_list,
-            segments: {
-                self.segments
-                    .into_iter()
-                    .map(|(_, s)| s.into_owned())
-                    .collect()
-            },
-            allowable_excess_duration: self.allowable_excess_duration,
-            unknown: {
-                self.unknown
-                    .into_iter()
-                    .map(|v| Cow::Owned(v.into_owned()))
-                    .collect()
-            },
-        }
+    pub fn part_target_secs(&self) -> Option<f64> {
+        self.part_target.map(|value| value.as_secs_f64())
     }
-}
 
-impl<'a> RequiredVersion for MediaPlaylist<'a> {
-    fn required_version(&self) -> ProtocolVersion {
-        required_version![
-            ExtXTargetDuration(self.target_duration),
-            (self.media_sequence != 0).athen(|| ExtXMediaSequence(self.media_sequence)),
-            (self.discontinuity_sequence != 0)
-                .athen(|| ExtXDiscontinuitySequence(self.discontinuity_sequence)),
-            self.playlist_type,
-            self.has_i_frames_only.athen_some(ExtXIFramesOnly),
-            self.has_independent_segments
-                .athen_some(ExtXIndependentSegments),
-            self.start,
-            self.has_end_list.athen_some(ExtXEndList),
-            self.segments
-        ]
-    }
-}
+    /// Returns the minimum, maximum and mean [`ExtInf::duration`] of the
+    /// [`MediaSegment`]s or `None` if the [`MediaPlaylist`] has no segments.
+    ///
+    /// Segment durations, that vary a lot, can cause issues in some players,
+    /// so this can be used to check the output of a packager.
+    ///
+    /// The durations are summed up in nanoseconds, so the mean does not
+    /// suffer from floating point errors.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// use std::convert::TryFrom;
+    /// use std::time::Duration;
+    ///
+    /// let playlist = MediaPlaylist::try_from(concat!(
+    ///     "#EXTM3U\n",
+    ///     "#EXT-X-TARGETDURATION:10\n",
+    ///     "#EXTINF:10,\n",
+    ///     "segment0.ts\n",
+    ///     "#EXTINF:6,\n",
+    ///     "segment1.ts\n",
+    /// ))?;
+    ///
+    /// let stats = playlist.duration_stats().unwrap();
+    ///
+    /// assert_eq!(stats.min, Duration::from_secs(6));
+    /// assert_eq!(stats.max, Duration::from_secs(10));
+    /// assert_eq!(stats.mean, Duration::from_secs(8));
+    /// assert_eq!(stats.count, 2);
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    ///
+    /// [`ExtInf::duration`]: crate::tags::ExtInf::duration
+    #[must_use]
+    pub fn duration_stats(&self) -> Option<DurationStats> {
+        let mut durations = self.segments.values().map(|s| s.duration.duration());
 
-impl<'a> fmt::Display for MediaPlaylist<'a> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        writeln!(f, "{}", ExtM3u)?;
+        let first = durations.next()?;
 
-        if self.required_version() != ProtocolVersion::V1 {
-            writeln!(f, "{}", ExtXVersion::new(self.required_version()))?;
+        let mut min = first;
+        let mut max = first;
+        let mut total = first.as_nanos();
+        let mut count = 1;
+
+        for duration in durations {
+            min = min.min(duration);
+            max = max.max(duration);
+            total += duration.as_nanos();
+            count += 1;
         }
 
-        writeln!(f, "{}", ExtXTargetDuration(self.target_duration))?;
+        // the mean is never larger than the maximum, so it fits into a `Duration`
+        let mean = total / count as u128;
+        let mean = Duration::new((mean / 1_000_000_000) as u64, (mean % 1_000_000_000) as u32);
 
-        if self.media_sequence != 0 {
-            writeln!(f, "{}", ExtXMediaSequence(self.media_sequence))?;
-        }
+        Some(DurationStats {
+            min,
+            max,
+            mean,
+            count,
+        })
+    }
 
-        if self.discontinuity_sequence != 0 {
-            writeln!(
-                f,
-                "{}",
-                ExtXDiscontinuitySequence(self.discontinuity_sequence)
-            )?;
-        }
+    /// Returns all [`MediaSegment`]s, whose playback interval intersects with
+    /// the time range `[start, end)`.
+    ///
+    /// The playback interval of a segment is computed by adding the durations
+    /// of all previous segments together. Segments, that straddle the start or
+    /// the end of the range, are included.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// use std::convert::TryFrom;
+    /// use std::time::Duration;
+    ///
+    /// let playlist = MediaPlaylist::try_from(concat!(
+    ///     "#EXTM3U\n",
+    ///     "#EXT-X-TARGETDURATION:10\n",
+    ///     "#EXTINF:10,\n",
+    ///     "segment0.ts\n",
+    ///     "#EXTINF:10,\n",
+    ///     "segment1.ts\n",
+    ///     "#EXTINF:10,\n",
+    ///     "segment2.ts\n",
+    /// ))?;
+    ///
+    /// assert_eq!(
+    ///     playlist
+    ///         .segments_in_range(Duration::from_secs(5), Duration::from_secs(15))
+    ///         .map(|s| s.uri().as_ref())
+    ///         .collect::<Vec<_>>(),
+    ///     vec!["segment0.ts", "segment1.ts"]
+    /// );
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    pub fn segments_in_range(
+        &self,
+        start: Duration,
+        end: Duration,
+    ) -> impl Iterator<Item = &MediaSegment<'a>> {
+        self.segments
+            .values()
+            .scan(Duration::from_secs(0), |offset, segment| {
+                let segment_start = *offset;
+                *offset += segment.duration.duration();
 
-        if let Some(value) = &self.playlist_type {
-            writeln!(f, "{}", value)?;
-        }
+                Some((segment_start, *offset, segment))
+            })
+            // an empty range does not contain any segment
+            .take_while(move |(segment_start, ..)| start < end && *segment_start < end)
+            .filter(move |(segment_start, segment_end, _)| {
+                // segments without a duration are included, if they start in the range
+                *segment_end > start || *segment_start >= start
+            })
+            .map(|(_, _, segment)| segment)
+    }
 
-        if self.has_i_frames_only {
-            writeln!(f, "{}", ExtXIFramesOnly)?;
-        }
+    /// Resolves the [`MediaPlaylist::start`] to the [`MediaSegment::number`] of
+    /// the [`MediaSegment`] at which the playback should start and the offset
+    /// relative to the beginning of that segment.
+    ///
+    /// A positive [`ExtXStart::time_offset`] is relative to the beginning of
+    /// the playlist, while a negative one is relative to the end of the last
+    /// [`MediaSegment`] (the live edge). If the absolute value of the offset
+    /// exceeds the [`MediaPlaylist::duration`], the beginning (or the end) of
+    /// the playlist is used instead.
+    ///
+    /// This returns `None`, if the [`MediaPlaylist`] has no [`ExtXStart`] or
+    /// no [`MediaSegment`]s.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// use std::convert::TryFrom;
+    /// use std::time::Duration;
+    ///
+    /// let playlist = MediaPlaylist::try_from(concat!(
+    ///     "#EXTM3U\n",
+    ///     "#EXT-X-TARGETDURATION:10\n",
+    ///     "#EXT-X-START:TIME-OFFSET=-12\n",
+    ///     "#EXTINF:10,\n",
+    ///     "segment0.ts\n",
+    ///     "#EXTINF:10,\n",
+    ///     "segment1.ts\n",
+    ///     "#EXTINF:10,\n",
+    ///     "segment2.ts\n",
+    /// ))?;
+    ///
+    /// // 30s - 12s = 18s, which is 8s into the second segment
+    /// assert_eq!(playlist.start_position(), Some((1, Duration::from_secs(8))));
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    #[must_use]
+    pub fn start_position(&self) -> Option<(usize, Duration)> {
+        let time_offset = self.start?.time_offset().as_f32();
+        let duration = self.duration();
 
-        if self.has_independent_segments {
-            writeln!(f, "{}", ExtXIndependentSegments)?;
-        }
+        let offset = {
+            if f64::from(time_offset.abs()) >= duration.as_secs_f64() {
+                duration
+            } else {
+                Duration::from_secs_f32(time_offset.abs())
+            }
+        };
 
-        if let Some(value) = &self.start {
-            writeln!(f, "{}", value)?;
-        }
+        let position = {
+            if time_offset.is_sign_negative() {
+                duration - offset
+            } else {
+                offset
+            }
+        };
 
-        let mut available_keys = HashSet::<ExtXKey<'_>>::new();
+        let mut segment_start = Duration::from_secs(0);
+        let mut result = None;
 
         for segment in self.segments.values() {
-            for key in &segment.keys {
-                if let ExtXKey(Some(decryption_key)) = key {
-                    // next segment will be encrypted, so the segment can not have an empty key
-                    available_keys.remove(&ExtXKey::empty());
+            let segment_end = segment_start + segment.duration.duration();
+            result = Some((segment.number(), position - segment_start));
 
-                    let mut decryption_key = decryption_key.clone();
-                    let key = {
-                        if let InitializationVector::Number(_) = decryption_key.iv {
-                            // set the iv from a segment number to missing
-                            // this does reduce the output size and the correct iv
-                            // is automatically set, when parsing.
-                            decryption_key.iv = InitializationVector::Missing;
-                        }
+            if position < segment_end {
+                break;
+            }
 
-                        ExtXKey(Some(decryption_key.clone()))
-                    };
+            segment_start = segment_end;
+        }
 
-                    // only do something if a key has been overwritten
-                    if available_keys.insert(key.clone()) {
-                        let mut remove_key = None;
+        result
+    }
 
-                        // an old key might be removed:
-                        for k in &available_keys {
-                            if let ExtXKey(Some(dk)) = k {
-                                if dk.format == decryption_key.format && key != *k {
-                                    remove_key = Some(k.clone());
-                                    break;
-                                }
-                            } else {
-                                unreachable!("empty keys should not exist in `available_keys`");
-                            }
-                        }
+    /// Returns the [`MediaSegment::number`] of the [`MediaSegment`] in which
+    /// the [`ExtXDateRange::start_date`] falls and the offset of the start
+    /// date relative to the beginning of that segment.
+    ///
+    /// The start time of each segment is derived from the most recent
+    /// [`MediaSegment::program_date_time`] and the durations of the segments,
+    /// that follow it.
+    ///
+    /// This returns `None`, if the [`ExtXDateRange`] has no start date or if
+    /// it can not be placed on the timeline of the [`MediaPlaylist`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// use hls_m3u8::tags::ExtXDateRange;
+    /// use std::convert::TryFrom;
+    /// use std::time::Duration;
+    ///
+    /// let playlist = MediaPlaylist::try_from(concat!(
+    ///     "#EXTM3U\n",
+    ///     "#EXT-X-TARGETDURATION:10\n",
+    ///     "#EXT-X-PROGRAM-DATE-TIME:2020-01-01T00:00:00.000Z\n",
+    ///     "#EXTINF:10,\n",
+    ///     "segment0.ts\n",
+    ///     "#EXTINF:10,\n",
+    ///     "segment1.ts\n",
+    /// ))?;
+    ///
+    /// let date_range = ExtXDateRange::try_from(
+    ///     "#EXT-X-DATERANGE:ID=\"ad\",START-DATE=\"2020-01-01T00:00:12.500Z\"",
+    /// )?;
+    ///
+    /// assert_eq!(
+    ///     playlist.date_range_position(&date_range),
+    ///     Some((1, Duration::from_millis(2500)))
+    /// );
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    #[cfg(feature = "chrono")]
+    #[must_use]
+    pub fn date_range_position(&self, date_range: &ExtXDateRange<'_>) -> Option<(usize, Duration)> {
+        let start_date = date_range.start_date()?;
+        let mut segment_start = None;
 
-                        if let Some(k) = remove_key {
-                            // this should always be true:
-                            let res = available_keys.remove(&k);
-                            debug_assert!(res);
-                        }
+        for segment in self.segments.values() {
+            if let Some(program_date_time) = &segment.program_date_time {
+                segment_start = Some(program_date_time.date_time);
+            }
 
-                        writeln!(f, "{}", key)?;
-                    }
-                } else {
-                    // the next segment is not encrypted, so remove all available keys
-                    available_keys.clear();
-                    available_keys.insert(ExtXKey::empty());
-                    writeln!(f, "{}", key)?;
+            if let Some(current) = segment_start {
+                let segment_end =
+                    current + chrono::Duration::from_std(segment.duration.duration()).ok()?;
+
+                if current <= start_date && start_date < segment_end {
+                    return Some((segment.number(), (start_date - current).to_std().ok()?));
                 }
-            }
 
-            write!(f, "{}", segment)?;
+                segment_start = Some(segment_end);
+            }
         }
 
-        for value in &self.unknown {
-            writeln!(f, "{}", value)?;
-        }
+        None
+    }
 
-        if self.has_end_list {
-            writeln!(f, "{}", ExtXEndList)?;
-        }
+    /// Returns all [`ExtXDateRange`]s with the given [`ExtXDateRange::class`],
+    /// ordered by their [`ExtXDateRange::start_date`].
+    ///
+    /// Ranges without a start date come first and ranges with the same start
+    /// date keep the order in which they appear in the playlist. Without the
+    /// `chrono` feature, a start date, that is not a valid [RFC3339] date-time,
+    /// is treated like a missing one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// use std::convert::TryFrom;
+    ///
+    /// let playlist = MediaPlaylist::try_from(concat!(
+    ///     "#EXTM3U\n",
+    ///     "#EXT-X-TARGETDURATION:10\n",
+    ///     "#EXT-X-DATERANGE:ID=\"b\",CLASS=\"ad\",START-DATE=\"2020-01-01T00:00:20Z\"\n",
+    ///     "#EXTINF:10,\n",
+    ///     "segment0.ts\n",
+    ///     "#EXT-X-DATERANGE:ID=\"a\",CLASS=\"ad\",START-DATE=\"2020-01-01T00:00:10Z\"\n",
+    ///     "#EXTINF:10,\n",
+    ///     "segment1.ts\n",
+    /// ))?;
+    ///
+    /// let ids = playlist
+    ///     .date_ranges_by_class("ad")
+    ///     .into_iter()
+    ///     .map(|date_range| date_range.id().as_ref())
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(ids, vec!["a", "b"]);
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    ///
+    /// [RFC3339]: https://tools.ietf.org/html/rfc3339#section-5.6
+    #[must_use]
+    pub fn date_ranges_by_class(&self, class: &str) -> Vec<&ExtXDateRange<'a>> {
+        let mut result = self
+            .segments
+            .values()
+            .filter_map(|segment| segment.date_range.as_ref())
+            .filter(|date_range| date_range.class().map_or(false, |value| value == class))
+            .collect::<Vec<_>>();
 
-        Ok(())
+        #[cfg(feature = "chrono")]
+        result.sort_by_key(|date_range| date_range.start_date());
+        #[cfg(not(feature = "chrono"))]
+        result.sort_by_key(|date_range| {
+            date_range
+                .start_date()
+                .and_then(|value| date_time_sort_key(value))
+        });
+        result
     }
-}
 
-fn parse_media_playlist<'a>(
-    input: &'a str,
-    builder: &mut MediaPlaylistBuilder<'a>,
-) -> crate::Result<MediaPlaylist<'a>> {
-    let input = tag(input, "#EXTM3U")?;
+    /// Checks the [`MediaPlaylist`] for violations of the specification, which
+    /// do not prevent the playlist from being used, and returns a [`Warning`]
+    /// for each of them.
+    ///
+    /// The following rules are checked:
+    ///
+    /// - A playlist with [`ExtXPart`] tags must have a
+    ///   [`MediaPlaylist::part_target`] ([`Warning::MissingPartInf`]) and a
+    ///   [`ExtXServerControl::part_hold_back`]
+    ///   ([`Warning::MissingPartHoldBack`]).
+    /// - A playlist with [`PlaylistType::Vod`] must not change, so it must have
+    ///   an `EXT-X-ENDLIST` tag ([`Warning::MissingEndList`]).
+    /// - All [`ExtXDateRange`] tags with the same [`ExtXDateRange::id`] must
+    ///   have the same value for the attributes they have in common
+    ///   ([`Warning::ConflictingDateRange`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// use hls_m3u8::Warning;
+    /// use std::convert::TryFrom;
+    ///
+    /// let playlist = MediaPlaylist::try_from(concat!(
+    ///     "#EXTM3U\n",
+    ///     "#EXT-X-TARGETDURATION:4\n",
+    ///     "#EXT-X-SERVER-CONTROL:PART-HOLD-BACK=1.5\n",
+    ///     "#EXT-X-PART:DURATION=0.5,URI=\"part0.0.mp4\"\n",
+    ///     "#EXTINF:0.5,\n",
+    ///     "segment0.mp4\n",
+    /// ))?;
+    ///
+    /// assert_eq!(playlist.validate(), vec![Warning::MissingPartInf]);
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    ///
+    /// [`ExtXServerControl::part_hold_back`]:
+    /// crate::tags::ExtXServerControl::part_hold_back
+    #[must_use]
+    pub fn validate(&self) -> Vec<Warning> {
+        let mut warnings = vec![];
 
-    let mut segment = MediaSegment::builder();
-    let mut segments = vec![];
+        // CHECK: `#EXT-X-PART`
+        // > If a Playlist contains EXT-X-PART tags, it MUST contain an
+        // > EXT-X-PART-INF tag and the EXT-X-SERVER-CONTROL tag MUST contain
+        // > a PART-HOLD-BACK attribute.
+        let has_parts = !self.incomplete_parts.is_empty()
+            || self.segments.values().any(|s| !s.parts.is_empty());
 
-    let mut has_partial_segment = false;
-    let mut has_discontinuity_tag = false;
-    let mut unknown = vec![];
-    let mut available_keys = HashSet::new();
+        if has_parts {
+            if self.part_target.is_none() {
+                warnings.push(Warning::MissingPartInf);
+            }
 
-    for line in Lines::from(input) {
-        match line? {
-            Line::Tag(tag) => {
-                match tag {
-                    Tag::ExtInf(t) => {
-                        has_partial_segment = true;
-                        segment.duration(t);
-                    }
-                    Tag::ExtXByteRange(t) => {
-                        has_partial_segment = true;
-                        segment.byte_range(t);
-                    }
-                    Tag::ExtXDiscontinuity(_) => {
-                        has_discontinuity_tag = true;
-                        has_partial_segment = true;
-                        segment.has_discontinuity(true);
-                    }
-                    Tag::ExtXKey(key) => {
-                        has_partial_segment = true;
+            if self
+                .server_control
+                .as_ref()
+                .and_then(|v| v.part_hold_back)
+                .is_none()
+            {
+                warnings.push(Warning::MissingPartHoldBack);
+            }
+        }
 
-                        // An ExtXKey applies to every MediaSegment and to every Media
-                        // Initialization Section declared by an ExtXMap tag, that appears
-                        // between it and the next ExtXKey tag in the Playlist file with the
-                        // same KEYFORMAT attribute (or the end of the Playlist file).
+        // A playlist with the playlist type `VOD` must not change, so it has to
+        // be terminated by an `EXT-X-ENDLIST` tag.
+        if self.playlist_type == Some(PlaylistType::Vod) && !self.has_end_list {
+            warnings.push(Warning::MissingEndList);
+        }
 
-                        let mut is_new_key = true;
-                        let mut remove = None;
+        // CHECK: `#EXT-X-DATERANGE`
+        // > If a Playlist contains two EXT-X-DATERANGE tags with the same ID
+        // > attribute value, then any AttributeName that appears in both
+        // > tags MUST have the same AttributeValue.
+        //
+        // from the rfc: https://tools.ietf.org/html/rfc8216#section-4.3.2.7
+        let mut date_ranges = HashMap::<_, Vec<&ExtXDateRange<'_>>>::new();
 
-                        if let ExtXKey(Some(decryption_key)) = &key {
-                            for old_key in &available_keys {
-                                if let ExtXKey(Some(old_decryption_key)) = &old_key {
-                                    if old_decryption_key.format == decryption_key.format {
-                                        // remove the old key
-                                        remove = Some(old_key.clone());
-
-                                        // there are no keys with the same format in
-                                        // available_keys so the loop can stop here:
-                                        break;
-                                    }
-                                } else {
-                                    // remove an empty key
-                                    remove = Some(ExtXKey::empty());
-                                    break;
-                                }
-                            }
-                        } else {
-                            available_keys.clear();
-                            available_keys.insert(ExtXKey::empty());
-                            is_new_key = false;
-                        }
+        for date_range in self.segments.values().filter_map(|s| s.date_range.as_ref()) {
+            let previous = date_ranges.entry(date_range.id()).or_default();
 
-                        if let Some(key) = &remove {
-                            available_keys.remove(key);
-                        }
+            if previous.iter().any(|v| v.conflicts_with(date_range)) {
+                warnings.push(Warning::ConflictingDateRange {
+                    id: date_range.id().to_string(),
+                });
+            }
 
-                        if is_new_key {
-                            available_keys.insert(key);
-                        }
-                    }
-                    Tag::ExtXMap(mut t) => {
-                        has_partial_segment = true;
+            previous.push(date_range);
+        }
 
-                        t.keys = available_keys.iter().cloned().collect();
-                        segment.map(t);
-                    }
-                    Tag::ExtXProgramDateTime(t) => {
-                        has_partial_segment = true;
-                        segment.program_date_time(t);
-                    }
-                    Tag::ExtXDateRange(t) => {
-                        has_partial_segment = true;
-                        segment.date_range(t);
-                    }
-                    Tag::ExtXTargetDuration(t) => {
-                        builder.target_duration(t.0);
-                    }
-                    Tag::ExtXMediaSequence(t) => {
-                        builder.media_sequence(t.0);
-                    }
-                    Tag::ExtXDiscontinuitySequence(t) => {
-                        // this tag must appear before the first MediaSegment in the playlist
-                        // https://tools.ietf.org/html/rfc8216#section-4.3.3.3
-                        if !segments.is_empty() {
-                            return Err(Error::custom("discontinuity sequence tag must appear before the first media segment in the playlist"));
-                        }
+        warnings
+    }
 
-                        // this tag must appear before any ExtXDiscontinuity tag
-                        // https://tools.ietf.org/html/rfc8216#section-4.3.3.3
-                        if has_discontinuity_tag {
-                            return Err(Error::custom("discontinuity sequence tag must appear before any `ExtXDiscontinuity` tag"));
-                        }
+    /// Checks, that the [`MediaSegment::program_date_time`]s of the
+    /// [`MediaSegment`]s do not go backwards.
+    ///
+    /// A [`Warning::ProgramDateTimeOutOfOrder`] is returned for every segment,
+    /// whose program date time is before the one of the previous segment with
+    /// a program date time. A [`MediaSegment::has_discontinuity`] allows the
+    /// timeline to be reset, so program date times are not compared across
+    /// discontinuities.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// use hls_m3u8::Warning;
+    /// use std::convert::TryFrom;
+    ///
+    /// let playlist = MediaPlaylist::try_from(concat!(
+    ///     "#EXTM3U\n",
+    ///     "#EXT-X-TARGETDURATION:10\n",
+    ///     "#EXT-X-PROGRAM-DATE-TIME:2020-01-01T00:00:10.000Z\n",
+    ///     "#EXTINF:10,\n",
+    ///     "segment0.ts\n",
+    ///     "#EXT-X-PROGRAM-DATE-TIME:2020-01-01T00:00:05.000Z\n",
+    ///     "#EXTINF:10,\n",
+    ///     "segment1.ts\n",
+    /// ))?;
+    ///
+    /// assert_eq!(
+    ///     playlist.validate_program_date_times(),
+    ///     vec![Warning::ProgramDateTimeOutOfOrder {
+    ///         number: 1,
+    ///         previous: "2020-01-01T00:00:10.000Z".to_string(),
+    ///         current: "2020-01-01T00:00:05.000Z".to_string(),
+    ///     }]
+    /// );
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    #[cfg(feature = "chrono")]
+    #[must_use]
+    pub fn validate_program_date_times(&self) -> Vec<Warning> {
+        let format = |value: DateTime<FixedOffset>| {
+            value.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+        };
 
-                        builder.discontinuity_sequence(t.0);
-                    }
-                    Tag::ExtXEndList(_) => {
-                        builder.has_end_list(true);
-                    }
-                    Tag::PlaylistType(t) => {
-                        builder.playlist_type(t);
-                    }
-                    Tag::ExtXIFramesOnly(_) => {
-                        builder.has_i_frames_only(true);
-                    }
-                    Tag::ExtXMedia(_)
-                    | Tag::VariantStream(_)
-                    | Tag::ExtXSessionData(_)
-                    | Tag::ExtXSessionKey(_) => {
-                        return Err(Error::unexpected_tag(tag));
-                    }
-                    Tag::ExtXIndependentSegments(_) => {
-                        builder.has_independent_segments(true);
-                    }
-                    Tag::ExtXStart(t) => {
-                        builder.start(t);
+        let mut result = vec![];
+        let mut previous = None;
+
+        for segment in self.segments.values() {
+            if segment.has_discontinuity {
+                previous = None;
+            }
+
+            if let Some(program_date_time) = &segment.program_date_time {
+                let current = program_date_time.date_time;
+
+                if let Some(previous) = previous {
+                    if current < previous {
+                        result.push(Warning::ProgramDateTimeOutOfOrder {
+                            number: segment.number(),
+                            previous: format(previous),
+                            current: format(current),
+                        });
                     }
-                    Tag::ExtXVersion(_) => {}
-                    Tag::Unknown(s) => {
-                        // [6.3.1. General Client Responsibilities]
-                        // > ignore any unrecognized tags.
-                        unknown.push(Cow::Borrowed(s));
+                }
+
+                previous = Some(current);
+            }
+        }
+
+        result
+    }
+
+    /// Returns an iterator over all [`MediaSegment`]s together with the
+    /// absolute date and time at which they start.
+    ///
+    /// The start of a segment is its [`MediaSegment::program_date_time`] or,
+    /// if it has none, the start of the previous segment plus the duration of
+    /// the previous segment. A [`MediaSegment::has_discontinuity`] breaks the
+    /// timeline, so segments after a discontinuity have no start time until
+    /// the next [`MediaSegment::program_date_time`].
+    ///
+    /// `None` is returned for segments, whose start time is unknown.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// use chrono::{FixedOffset, TimeZone};
+    /// use std::convert::TryFrom;
+    ///
+    /// let playlist = MediaPlaylist::try_from(concat!(
+    ///     "#EXTM3U\n",
+    ///     "#EXT-X-TARGETDURATION:10\n",
+    ///     "#EXTINF:10,\n",
+    ///     "segment0.ts\n",
+    ///     "#EXT-X-PROGRAM-DATE-TIME:2020-01-01T00:00:00.000Z\n",
+    ///     "#EXTINF:10,\n",
+    ///     "segment1.ts\n",
+    ///     "#EXTINF:10,\n",
+    ///     "segment2.ts\n",
+    /// ))?;
+    ///
+    /// let start = FixedOffset::east(0).ymd(2020, 1, 1).and_hms(0, 0, 0);
+    ///
+    /// assert_eq!(
+    ///     playlist
+    ///         .segment_date_times()
+    ///         .map(|(_, date_time)| date_time)
+    ///         .collect::<Vec<_>>(),
+    ///     vec![
+    ///         None,
+    ///         Some(start),
+    ///         Some(start + chrono::Duration::seconds(10))
+    ///     ]
+    /// );
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn segment_date_times(
+        &self,
+    ) -> impl Iterator<Item = (&MediaSegment<'a>, Option<DateTime<FixedOffset>>)> + '_ {
+        self.segments.values().scan(None, |next_start, segment| {
+            if segment.has_discontinuity {
+                *next_start = None;
+            }
+
+            if let Some(program_date_time) = &segment.program_date_time {
+                *next_start = Some(program_date_time.date_time);
+            }
+
+            let start = *next_start;
+
+            *next_start = start.and_then(|start| {
+                let duration = chrono::Duration::from_std(segment.duration.duration()).ok()?;
+                start.checked_add_signed(duration)
+            });
+
+            Some((segment, start))
+        })
+    }
+
+    /// Returns all tags of the kind `T` in the order in which they appear in
+    /// the playlist.
+    ///
+    /// This includes the [`ExtXPart`]s of the incomplete segment (see
+    /// [`MediaPlaylist::incomplete_parts`]), which appear at the end of the
+    /// playlist.
+    ///
+    /// See [`SegmentTag`] for all supported tags.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// use hls_m3u8::tags::ExtXDateRange;
+    /// use std::convert::TryFrom;
+    ///
+    /// let playlist = MediaPlaylist::try_from(concat!(
+    ///     "#EXTM3U\n",
+    ///     "#EXT-X-TARGETDURATION:10\n",
+    ///     "#EXT-X-DATERANGE:ID=\"ad\",START-DATE=\"2020-01-01T00:00:00Z\"\n",
+    ///     "#EXTINF:10,\n",
+    ///     "segment0.ts\n",
+    ///     "#EXTINF:10,\n",
+    ///     "segment1.ts\n",
+    /// ))?;
+    ///
+    /// let ids = playlist
+    ///     .tags::<ExtXDateRange<'_>>()
+    ///     .into_iter()
+    ///     .map(|date_range| date_range.id().to_string())
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(ids, vec!["ad".to_string()]);
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    #[must_use]
+    pub fn tags<T: SegmentTag<'a>>(&self) -> Vec<&T> {
+        self.segments
+            .values()
+            .flat_map(T::from_segment)
+            .chain(T::from_incomplete_parts(&self.incomplete_parts))
+            .collect()
+    }
+
+    /// Returns the (1-based) lines of the input, from which the
+    /// [`MediaSegment`] at the given index has been parsed. The range starts at
+    /// the first tag of the segment and ends with its `URI`.
+    ///
+    /// The lines are only recorded, if the playlist has been parsed with
+    /// [`ParseOptions::retain_source_lines`], otherwise `None` is returned.
+    /// `None` is also returned for segments, which have been added after
+    /// parsing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// use hls_m3u8::ParseOptions;
+    ///
+    /// let input = concat!(
+    ///     "#EXTM3U\n",
+    ///     "#EXT-X-TARGETDURATION:10\n",
+    ///     "#EXTINF:10,\n",
+    ///     "segment0.ts\n",
+    ///     "\n",
+    ///     "#EXT-X-DISCONTINUITY\n",
+    ///     "#EXTINF:10,\n",
+    ///     "segment1.ts\n",
+    /// );
+    ///
+    /// let options = ParseOptions::strict().with_source_lines();
+    /// let (playlist, _) = MediaPlaylist::parse_with(input, options)?;
+    ///
+    /// assert_eq!(playlist.source_line(0), Some(3..=4));
+    /// assert_eq!(playlist.source_line(1), Some(6..=8));
+    /// assert_eq!(playlist.source_line(2), None);
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    #[must_use]
+    pub fn source_line(&self, index: usize) -> Option<RangeInclusive<usize>> {
+        self.source_lines.0.get(index).cloned()
+    }
+
+    /// Parses a [`MediaPlaylist`] line by line from the given reader, without
+    /// reading the entire playlist into memory first.
+    ///
+    /// Errors, which occur while parsing a line, contain the number of that
+    /// line.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// let input: &[u8] = concat!(
+    ///     "#EXTM3U\n",
+    ///     "#EXT-X-TARGETDURATION:10\n",
+    ///     "#EXTINF:10,\n",
+    ///     "segment0.ts\n",
+    /// )
+    /// .as_bytes();
+    ///
+    /// let playlist = MediaPlaylist::from_reader(input)?;
+    /// assert_eq!(playlist.segments.num_elements(), 1);
+    ///
+    /// let input: &[u8] = concat!(
+    ///     "#EXTM3U\n",
+    ///     "#EXT-X-TARGETDURATION:10\n",
+    ///     "#EXTINF:ten,\n",
+    ///     "segment0.ts\n",
+    /// )
+    /// .as_bytes();
+    ///
+    /// let error = MediaPlaylist::from_reader(input).unwrap_err();
+    /// assert!(error.to_string().starts_with("line 3: "));
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function fails, if the input can not be read or if it is not a
+    /// valid [`MediaPlaylist`].
+    pub fn from_reader<R: Read>(reader: R) -> crate::Result<MediaPlaylist<'static>> {
+        let options = ParseOptions::strict();
+
+        let mut reader = BufReader::new(reader);
+        let mut buffer = String::new();
+
+        // the number of the last line, that has been read
+        let mut line_number = 0;
+        let mut is_finished = false;
+
+        let lines = iter::from_fn(|| {
+            loop {
+                buffer.clear();
+                line_number += 1;
+
+                match reader.read_line(&mut buffer) {
+                    Ok(0) if line_number > 1 => {
+                        is_finished = true;
+                        return None;
                     }
+                    Ok(_) => {}
+                    Err(error) => return Some((line_number, Err(Error::io(error)))),
+                }
+
+                let mut line = buffer.as_str();
+
+                if line_number == 1 {
+                    line = match tag(line, "#EXTM3U") {
+                        Ok(rest) => rest,
+                        Err(error) => return Some((line_number, Err(error))),
+                    };
+                }
+
+                // empty lines are skipped
+                if let Some(line) = Lines::with_options(line, options).next() {
+                    return Some((line_number, line.map(Line::into_owned)));
                 }
             }
-            Line::Uri(uri) => {
-                segment.uri(uri);
-                segment.keys(available_keys.iter().cloned().collect::<Vec<_>>());
-                segments.push(segment.build().map_err(Error::builder)?);
+        });
 
-                segment = MediaSegment::builder();
-                has_partial_segment = false;
+        let result = parse_media_playlist_lines(lines, &mut MediaPlaylist::builder(), options);
+
+        result.map(|(playlist, _)| playlist).map_err(|error| {
+            // errors after the last line are about the entire playlist
+            if is_finished {
+                error
+            } else {
+                error.at_line(line_number)
+            }
+        })
+    }
+
+    /// Reads a gzip-compressed [`MediaPlaylist`] from the given reader.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// use std::fs::File;
+    ///
+    /// let file = File::open("tests/assets/media_playlist.m3u8.gz")?;
+    /// let playlist = MediaPlaylist::from_gzip_reader(file)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function fails, if the input can not be decompressed or if it is
+    /// not a valid [`MediaPlaylist`].
+    #[cfg(feature = "flate2")]
+    pub fn from_gzip_reader<R: Read>(reader: R) -> crate::Result<MediaPlaylist<'static>> {
+        Self::from_reader(GzDecoder::new(reader))
+    }
+
+    /// Removes the first `n` [`MediaSegment`]s from the [`MediaPlaylist`] and
+    /// increments the [`MediaPlaylist::media_sequence`] accordingly.
+    ///
+    /// If a removed [`MediaSegment`] has a discontinuity, the
+    /// [`MediaPlaylist::discontinuity_sequence`] will be incremented as well.
+    ///
+    /// This can be used together with [`MediaPlaylist::push_segment`] to
+    /// implement the sliding window of a live playlist.
+    ///
+    /// # Errors
+    ///
+    /// This function fails, if the [`MediaPlaylist::media_sequence`] or the
+    /// [`MediaPlaylist::discontinuity_sequence`] would exceed [`usize::MAX`].
+    /// The [`MediaSegment`]s, that have been removed until then, stay removed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::{MediaPlaylist, MediaSegment};
+    /// use std::time::Duration;
+    ///
+    /// let mut playlist = MediaPlaylist::builder()
+    ///     .target_duration(Duration::from_secs(10))
+    ///     .segments(vec![
+    ///         MediaSegment::builder()
+    ///             .duration(Duration::from_secs(10))
+    ///             .uri("segment0.ts")
+    ///             .build()?,
+    ///         MediaSegment::builder()
+    ///             .duration(Duration::from_secs(10))
+    ///             .uri("segment1.ts")
+    ///             .build()?,
+    ///     ])
+    ///     .build()?;
+    ///
+    /// playlist.remove_oldest(1)?;
+    /// playlist.push_segment(
+    ///     MediaSegment::builder()
+    ///         .duration(Duration::from_secs(10))
+    ///         .uri("segment2.ts")
+    ///         .build()?,
+    /// )?;
+    ///
+    /// assert_eq!(playlist.media_sequence, 1);
+    /// assert_eq!(
+    ///     playlist
+    ///         .segments
+    ///         .values()
+    ///         .map(MediaSegment::number)
+    ///         .collect::<Vec<_>>(),
+    ///     vec![1, 2]
+    /// );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn remove_oldest(&mut self, n: usize) -> crate::Result<()> {
+        let mut bitrate = None;
+        let mut removed = 0;
+        let mut result = Ok(());
+
+        for _ in 0..n {
+            let has_discontinuity = {
+                if let Some(segment) = self.segments.values().next() {
+                    segment.has_discontinuity
+                } else {
+                    break;
+                }
+            };
+
+            let media_sequence = self.media_sequence.checked_add(1);
+            let discontinuity_sequence = {
+                if has_discontinuity {
+                    self.discontinuity_sequence.checked_add(1)
+                } else {
+                    Some(self.discontinuity_sequence)
+                }
+            };
+
+            if let (Some(media_sequence), Some(discontinuity_sequence)) =
+                (media_sequence, discontinuity_sequence)
+            {
+                self.media_sequence = media_sequence;
+                self.discontinuity_sequence = discontinuity_sequence;
+            } else {
+                result = Err(Error::custom("the media sequence overflows"));
+                break;
+            }
+
+            if let Some(segment) = self.segments.remove_first() {
+                removed += 1;
+                bitrate = segment.bitrate.or(bitrate);
+            }
+        }
+
+        self.segments.make_compact();
+
+        // the remaining segments have been moved to the front:
+        let removed = removed.min(self.source_lines.0.len());
+        self.source_lines.0.drain(..removed);
+
+        // the bitrate of a removed segment still applies to the following segments:
+        if let Some(segment) = self.segments.values_mut().next() {
+            if segment.bitrate.is_none() {
+                segment.bitrate = bitrate;
             }
-            Line::Comment(_) => {}
         }
+
+        result
+    }
+
+    /// Returns `true`, if both [`MediaPlaylist`]s have the same content.
+    ///
+    /// Unlike `==`, this ignores the fields, which change with every update of
+    /// a live playlist, even if the content stays the same:
+    ///
+    /// - [`MediaPlaylist::media_sequence`]
+    /// - [`MediaPlaylist::discontinuity_sequence`]
+    /// - [`MediaSegment::number`]
+    ///
+    /// The [`MediaPlaylist::allowable_excess_duration`] is ignored as well,
+    /// because it is not part of the playlist.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// use std::convert::TryFrom;
+    ///
+    /// let first = MediaPlaylist::try_from(concat!(
+    ///     "#EXTM3U\n",
+    ///     "#EXT-X-TARGETDURATION:10\n",
+    ///     "#EXT-X-MEDIA-SEQUENCE:1\n",
+    ///     "#EXTINF:10,\n",
+    ///     "segment.ts\n",
+    /// ))?;
+    ///
+    /// let second = MediaPlaylist::try_from(concat!(
+    ///     "#EXTM3U\n",
+    ///     "#EXT-X-TARGETDURATION:10\n",
+    ///     "#EXT-X-MEDIA-SEQUENCE:2\n",
+    ///     "#EXTINF:10,\n",
+    ///     "segment.ts\n",
+    /// ))?;
+    ///
+    /// assert_ne!(first, second);
+    /// assert!(first.content_eq(&second));
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    #[must_use]
+    pub fn content_eq(&self, other: &Self) -> bool {
+        self.target_duration == other.target_duration
+            && self.playlist_type == other.playlist_type
+            && self.allow_cache == other.allow_cache
+            && self.has_i_frames_only == other.has_i_frames_only
+            && self.has_independent_segments == other.has_independent_segments
+            && self.start == other.start
+            && self.server_control == other.server_control
+            && self.part_target == other.part_target
+            && self.defines == other.defines
+            && self.has_end_list == other.has_end_list
+            && self.segments.num_elements() == other.segments.num_elements()
+            && self
+                .segments
+                .values()
+                .zip(other.segments.values())
+                .all(|(left, right)| left.eq_ignoring_number(right))
+            && self.incomplete_parts == other.incomplete_parts
+            && self.unknown == other.unknown
+    }
+
+    /// Returns the bitrate in kilobits per second, that applies to each
+    /// [`MediaSegment`] of the [`MediaPlaylist`].
+    ///
+    /// An [`ExtXBitrate`] tag applies to every following [`MediaSegment`] until
+    /// the next [`ExtXBitrate`] tag, except for those segments, that have an
+    /// [`ExtXByteRange`]. For those segments (and segments without any
+    /// declared bitrate) `None` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// use std::convert::TryFrom;
+    ///
+    /// let playlist = MediaPlaylist::try_from(concat!(
+    ///     "#EXTM3U\n",
+    ///     "#EXT-X-TARGETDURATION:10\n",
+    ///     "#EXTINF:10,\n",
+    ///     "segment0.ts\n",
+    ///     "#EXT-X-BITRATE:1500\n",
+    ///     "#EXTINF:10,\n",
+    ///     "segment1.ts\n",
+    ///     "#EXTINF:10,\n",
+    ///     "segment2.ts\n",
+    /// ))?;
+    ///
+    /// assert_eq!(
+    ///     playlist.bitrates().collect::<Vec<_>>(),
+    ///     vec![None, Some(1500), Some(1500)]
+    /// );
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    ///
+    /// [`ExtXBitrate`]: crate::tags::ExtXBitrate
+    pub fn bitrates(&self) -> impl Iterator<Item = Option<u64>> + '_ {
+        self.segments.values().scan(None, |current, segment| {
+            if let Some(bitrate) = segment.bitrate {
+                *current = Some(bitrate.bitrate());
+            }
+
+            if segment.byte_range.is_some() {
+                Some(None)
+            } else {
+                Some(*current)
+            }
+        })
+    }
+
+    /// Returns an estimate of the size in bytes of each [`MediaSegment`].
+    ///
+    /// The size is computed from the effective bitrate (see
+    /// [`MediaPlaylist::bitrates`]) and the duration of the segment. The size
+    /// of a segment with an [`ExtXByteRange`] is known exactly and is therefore
+    /// returned as is. For segments without a known bitrate `None` is returned.
+    ///
+    /// This can be used to pre-allocate the buffers for downloading the
+    /// segments.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// use std::convert::TryFrom;
+    ///
+    /// let playlist = MediaPlaylist::try_from(concat!(
+    ///     "#EXTM3U\n",
+    ///     "#EXT-X-TARGETDURATION:10\n",
+    ///     "#EXTINF:10,\n",
+    ///     "segment0.ts\n",
+    ///     "#EXT-X-BITRATE:1500\n",
+    ///     "#EXTINF:10,\n",
+    ///     "segment1.ts\n",
+    /// ))?;
+    ///
+    /// // 1500 kbit/s * 10 s = 1875000 bytes
+    /// assert_eq!(
+    ///     playlist.estimated_segment_sizes().collect::<Vec<_>>(),
+    ///     vec![None, Some(1_875_000)]
+    /// );
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    pub fn estimated_segment_sizes(&self) -> impl Iterator<Item = Option<u64>> + '_ {
+        self.segments
+            .values()
+            .zip(self.bitrates())
+            .map(|(segment, bitrate)| {
+                if let Some(range) = segment.byte_range {
+                    return Some(range.len() as u64);
+                }
+
+                // kilobits per second * milliseconds / 8 = bytes
+                bitrate.map(|kbps| {
+                    (u128::from(kbps) * segment.duration.duration().as_millis() / 8) as u64
+                })
+            })
+    }
+
+    /// Appends a [`MediaSegment`] to the [`MediaPlaylist`] and assigns the
+    /// next [`MediaSegment::number`] to it.
+    ///
+    /// # Note
+    ///
+    /// Unlike [`MediaPlaylistBuilder::build`], this function does not validate
+    /// the [`MediaSegment`] (for example against the
+    /// [`MediaPlaylist::target_duration`]).
+    ///
+    /// # Errors
+    ///
+    /// This function fails, if the next [`MediaSegment::number`] would exceed
+    /// [`usize::MAX`].
+    ///
+    /// [`MediaPlaylistBuilder::build`]: crate::builder::MediaPlaylistBuilder::build
+    pub fn push_segment(&mut self, mut segment: MediaSegment<'a>) -> crate::Result<()> {
+        segment.number = {
+            if let Some(last) = self.segments.values().last() {
+                last.number
+                    .checked_add(1)
+                    .ok_or_else(|| Error::custom("the media sequence overflows"))?
+            } else {
+                self.media_sequence
+            }
+        };
+        segment.explicit_number = false;
+
+        self.segments.push(segment);
+        Ok(())
+    }
+
+    /// Replaces all variable references (`{$name}`) in the `URI`s of the
+    /// [`MediaSegment`]s, [`ExtXKey`]s and [`ExtXMap`]s with the values of the
+    /// [`MediaPlaylist::defines`].
+    ///
+    /// The values of imported variables ([`ExtXDefine::Import`]) are taken
+    /// from `imported`, which are usually the [`MasterPlaylist::defines`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// use std::convert::TryFrom;
+    ///
+    /// let mut playlist = MediaPlaylist::try_from(concat!(
+    ///     "#EXTM3U\n",
+    ///     "#EXT-X-TARGETDURATION:10\n",
+    ///     "#EXT-X-DEFINE:NAME=\"host\",VALUE=\"https://www.example.com\"\n",
+    ///     "#EXTINF:10.0,\n",
+    ///     "{$host}/segment.ts\n",
+    /// ))?;
+    ///
+    /// playlist.normalize_uris(&[])?;
+    ///
+    /// assert_eq!(
+    ///     playlist.segments[0].uri(),
+    ///     "https://www.example.com/segment.ts"
+    /// );
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function fails, if a `URI` references an undefined variable or if
+    /// an imported variable is missing from `imported`. The playlist is not
+    /// changed in that case.
+    ///
+    /// [`ExtXMap`]: crate::tags::ExtXMap
+    /// [`MasterPlaylist::defines`]: crate::MasterPlaylist::defines
+    pub fn normalize_uris(&mut self, imported: &[ExtXDefine<'_>]) -> crate::Result<()> {
+        let mut variables = HashMap::new();
+
+        for define in &self.defines {
+            let value = match define {
+                ExtXDefine::Value { value, .. } => value.to_string(),
+                ExtXDefine::Import { name } => {
+                    imported
+                        .iter()
+                        .find_map(|v| {
+                            match v {
+                                ExtXDefine::Value { name: n, value } if n == name => Some(value),
+                                _ => None,
+                            }
+                        })
+                        .ok_or_else(|| {
+                            Error::custom(format!(
+                                "the imported variable {:?} is not defined",
+                                name
+                            ))
+                        })?
+                        .to_string()
+                }
+            };
+
+            variables.insert(define.name().to_string(), value);
+        }
+
+        let lookup = |name: &str| variables.get(name).map(String::as_str);
+        let substitute = |uri: &str| -> crate::Result<Option<String>> {
+            match substitute_variables(uri, lookup)? {
+                Cow::Owned(value) => Ok(Some(value)),
+                Cow::Borrowed(_) => Ok(None),
+            }
+        };
+
+        // all `URI`s are substituted before any of them is replaced, so that
+        // the playlist is left unchanged, if one of them is invalid:
+        let mut uris = vec![];
+
+        for segment in self.segments.values() {
+            uris.push(substitute(segment.uri())?);
+
+            let map_keys = segment.map.iter().flat_map(|m| m.keys.iter());
+
+            for key in segment.keys.iter().chain(map_keys) {
+                if let ExtXKey(Some(key)) = key {
+                    uris.push(substitute(key.uri())?);
+                }
+            }
+
+            if let Some(map) = &segment.map {
+                uris.push(substitute(map.uri())?);
+            }
+        }
+
+        let mut uris = uris.into_iter();
+
+        for segment in self.segments.values_mut() {
+            if let Some(uri) = uris.next().flatten() {
+                segment.set_uri(uri);
+            }
+
+            let map_keys = segment.map.iter_mut().flat_map(|m| m.keys.iter_mut());
+
+            for key in segment.keys.iter_mut().chain(map_keys) {
+                if let ExtXKey(Some(key)) = key {
+                    if let Some(uri) = uris.next().flatten() {
+                        key.set_uri(uri);
+                    }
+                }
+            }
+
+            if let Some(map) = &mut segment.map {
+                if let Some(uri) = uris.next().flatten() {
+                    map.set_uri(uri);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Replaces the `URI` of every [`ExtXKey`] (including the keys of the
+    /// [`ExtXMap`] tags) with the result of the provided function.
+    ///
+    /// This can be used to point the key requests of a client to a proxy (for
+    /// example a license server).
+    ///
+    /// Every [`MediaSegment`] has its own copy of the keys, that apply to it,
+    /// so the function is called only once for each distinct `URI` and the
+    /// result is reused for all other occurrences.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// use std::convert::TryFrom;
+    ///
+    /// let mut playlist = MediaPlaylist::try_from(concat!(
+    ///     "#EXTM3U\n",
+    ///     "#EXT-X-TARGETDURATION:10\n",
+    ///     "#EXT-X-KEY:METHOD=AES-128,URI=\"https://example.com/key\"\n",
+    ///     "#EXTINF:10,\n",
+    ///     "segment0.ts\n",
+    /// ))?;
+    ///
+    /// playlist.rewrite_key_uris(|uri| format!("https://proxy.example.com/?key={}", uri));
+    ///
+    /// assert_eq!(
+    ///     playlist.segments[0].keys[0].as_ref().unwrap().uri(),
+    ///     "https://proxy.example.com/?key=https://example.com/key"
+    /// );
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    ///
+    /// [`ExtXMap`]: crate::tags::ExtXMap
+    pub fn rewrite_key_uris<F: FnMut(&str) -> String>(&mut self, mut f: F) {
+        let mut rewritten = HashMap::<String, String>::new();
+
+        for segment in self.segments.values_mut() {
+            let map_keys = segment.map.iter_mut().flat_map(|m| m.keys.iter_mut());
+
+            for key in segment.keys.iter_mut().chain(map_keys) {
+                if let ExtXKey(Some(key)) = key {
+                    let uri = {
+                        if let Some(uri) = rewritten.get(key.uri().as_ref()) {
+                            uri.clone()
+                        } else {
+                            let uri = f(key.uri());
+                            rewritten.insert(key.uri().to_string(), uri.clone());
+                            uri
+                        }
+                    };
+
+                    key.set_uri(uri);
+                }
+            }
+        }
+    }
+
+    /// Resolves every `URI` of the [`MediaPlaylist`] against the `base` url
+    /// (the url of the [`MediaPlaylist`] itself) and returns all `URI`s,
+    /// that could not be parsed, together with the reason.
+    ///
+    /// This includes the `URI`s of the [`MediaSegment`]s, of the [`ExtXKey`],
+    /// [`ExtXMap`] and [`ExtXPart`] tags. Every `URI` is reported only once.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// use std::convert::TryFrom;
+    /// use url::Url;
+    ///
+    /// let playlist = MediaPlaylist::try_from(concat!(
+    ///     "#EXTM3U\n",
+    ///     "#EXT-X-TARGETDURATION:10\n",
+    ///     "#EXTINF:10,\n",
+    ///     "segment0.ts\n",
+    ///     "#EXTINF:10,\n",
+    ///     "http://[::1/segment1.ts\n",
+    /// ))?;
+    ///
+    /// let base = Url::parse("https://www.example.com/video/index.m3u8").unwrap();
+    /// let invalid = playlist.validate_uris(&base);
+    ///
+    /// assert_eq!(invalid.len(), 1);
+    /// assert_eq!(invalid[0].0, "http://[::1/segment1.ts");
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    ///
+    /// [`ExtXMap`]: crate::tags::ExtXMap
+    /// [`ExtXPart`]: crate::tags::ExtXPart
+    #[cfg(feature = "url")]
+    #[must_use]
+    pub fn validate_uris(&self, base: &Url) -> Vec<(&str, ParseError)> {
+        let mut checked = HashSet::new();
+        let mut result = vec![];
+
+        for segment in self.segments.values() {
+            let map_keys = segment.map.iter().flat_map(|m| m.keys.iter());
+
+            let uris = std::iter::once(segment.uri())
+                .chain(segment.map.as_ref().map(crate::tags::ExtXMap::uri))
+                .chain(segment.parts.iter().map(crate::tags::ExtXPart::uri))
+                .chain(
+                    segment
+                        .keys
+                        .iter()
+                        .chain(map_keys)
+                        .filter_map(ExtXKey::as_ref)
+                        .map(DecryptionKey::uri),
+                );
+
+            for uri in uris {
+                if !checked.insert(uri.as_ref()) {
+                    continue;
+                }
+
+                if let Err(error) = base.join(uri) {
+                    result.push((uri.as_ref(), error));
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Serializes the [`MediaPlaylist`] into UTF-8 encoded bytes, which can for
+    /// example be used as the body of a http response.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// use std::convert::TryFrom;
+    ///
+    /// let playlist = MediaPlaylist::try_from(concat!(
+    ///     "#EXTM3U\n",
+    ///     "#EXT-X-TARGETDURATION:10\n",
+    ///     "#EXTINF:10,\n",
+    ///     "segment0.ts\n",
+    /// ))?;
+    ///
+    /// assert_eq!(
+    ///     playlist.to_bytes(),
+    ///     b"#EXTM3U\n#EXT-X-TARGETDURATION:10\n#EXTINF:10,\nsegment0.ts\n".to_vec()
+    /// );
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> { self.to_string().into_bytes() }
+
+    /// Makes the struct independent of its lifetime, by taking ownership of all
+    /// internal [`Cow`]s.
+    ///
+    /// # Note
+    ///
+    /// This is a relatively expensive operation.
+    #[must_use]
+    pub fn into_owned(self) -> MediaPlaylist<'static> {
+        MediaPlaylist {
+            target_duration: self.target_duration,
+            media_sequence: self.media_sequence,
+            discontinuity_sequence: self.discontinuity_sequence,
+            playlist_type: self.playlist_type,
+            allow_cache: self.allow_cache,
+            has_i_frames_only: self.has_i_frames_only,
+            has_independent_segments: self.has_independent_segments,
+            start: self.start,
+            server_control: self.server_control,
+            part_target: self.part_target,
+            defines: self
+                .defines
+                .into_iter()
+                .map(ExtXDefine::into_owned)
+                .collect(),
+            has_end_list: self.has_end_list,
+            segments: {
+                self.segments
+                    .into_iter()
+                    .map(|(_, s)| s.into_owned())
+                    .collect()
+            },
+            incomplete_parts: self
+                .incomplete_parts
+                .into_iter()
+                .map(ExtXPart::into_owned)
+                .collect(),
+            allowable_excess_duration: self.allowable_excess_duration,
+            unknown: {
+                self.unknown
+                    .into_iter()
+                    .map(|v| Cow::Owned(v.into_owned()))
+                    .collect()
+            },
+            source_lines: self.source_lines,
+        }
+    }
+}
+
+impl<'a> RequiredVersion for MediaPlaylist<'a> {
+    fn required_version(&self) -> ProtocolVersion {
+        required_version![
+            ExtXTargetDuration(self.target_duration),
+            (self.media_sequence != 0).athen(|| ExtXMediaSequence(self.media_sequence)),
+            (self.discontinuity_sequence != 0)
+                .athen(|| ExtXDiscontinuitySequence(self.discontinuity_sequence)),
+            self.playlist_type,
+            self.allow_cache.map(ExtXAllowCache::new),
+            self.has_i_frames_only.athen_some(ExtXIFramesOnly),
+            self.has_independent_segments
+                .athen_some(ExtXIndependentSegments),
+            self.start,
+            self.server_control,
+            self.part_target.map(ExtXPartInf),
+            self.defines,
+            self.has_end_list.athen_some(ExtXEndList),
+            self.segments,
+            self.incomplete_parts
+        ]
+    }
+}
+
+impl<'a, 'b> IntoIterator for &'b MediaPlaylist<'a> {
+    type IntoIter = Values<'b, MediaSegment<'a>, DefaultCore<MediaSegment<'a>>>;
+    type Item = &'b MediaSegment<'a>;
+
+    fn into_iter(self) -> Self::IntoIter { self.segments.values() }
+}
+
+impl<'a> fmt::Display for MediaPlaylist<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", ExtM3u)?;
+
+        if self.required_version() != ProtocolVersion::V1 {
+            writeln!(f, "{}", ExtXVersion::new(self.required_version()))?;
+        }
+
+        writeln!(f, "{}", ExtXTargetDuration(self.target_duration))?;
+
+        if self.media_sequence != 0 {
+            writeln!(f, "{}", ExtXMediaSequence(self.media_sequence))?;
+        }
+
+        if self.discontinuity_sequence != 0 {
+            writeln!(
+                f,
+                "{}",
+                ExtXDiscontinuitySequence(self.discontinuity_sequence)
+            )?;
+        }
+
+        if let Some(value) = &self.playlist_type {
+            writeln!(f, "{}", value)?;
+        }
+
+        if let Some(value) = self.allow_cache {
+            writeln!(f, "{}", ExtXAllowCache::new(value))?;
+        }
+
+        if self.has_i_frames_only {
+            writeln!(f, "{}", ExtXIFramesOnly)?;
+        }
+
+        if self.has_independent_segments {
+            writeln!(f, "{}", ExtXIndependentSegments)?;
+        }
+
+        if let Some(value) = &self.start {
+            // the formatter is passed on, so that the alternate format applies
+            // to the tag as well
+            fmt::Display::fmt(value, f)?;
+            writeln!(f)?;
+        }
+
+        if let Some(value) = &self.server_control {
+            writeln!(f, "{}", value)?;
+        }
+
+        if let Some(value) = self.part_target {
+            writeln!(f, "{}", ExtXPartInf(value))?;
+        }
+
+        for value in &self.defines {
+            writeln!(f, "{}", value)?;
+        }
+
+        let mut available_keys = HashSet::<ExtXKey<'_>>::new();
+
+        for segment in self.segments.values() {
+            for key in &segment.keys {
+                if let ExtXKey(Some(decryption_key)) = key {
+                    // next segment will be encrypted, so the segment can not have an empty key
+                    available_keys.remove(&ExtXKey::empty());
+
+                    let mut decryption_key = decryption_key.clone();
+                    let key = {
+                        if let InitializationVector::Number(_) = decryption_key.iv {
+                            // set the iv from a segment number to missing
+                            // this does reduce the output size and the correct iv
+                            // is automatically set, when parsing.
+                            decryption_key.iv = InitializationVector::Missing;
+                        }
+
+                        ExtXKey(Some(decryption_key.clone()))
+                    };
+
+                    // only do something if a key has been overwritten
+                    if available_keys.insert(key.clone()) {
+                        let mut remove_key = None;
+
+                        // an old key might be removed:
+                        for k in &available_keys {
+                            if let ExtXKey(Some(dk)) = k {
+                                // a missing `KEYFORMAT` is the same as `identity`
+                                if dk.effective_format() == decryption_key.effective_format()
+                                    && key != *k
+                                {
+                                    remove_key = Some(k.clone());
+                                    break;
+                                }
+                            } else {
+                                unreachable!("empty keys should not exist in `available_keys`");
+                            }
+                        }
+
+                        if let Some(k) = remove_key {
+                            // this should always be true:
+                            let res = available_keys.remove(&k);
+                            debug_assert!(res);
+                        }
+
+                        writeln!(f, "{}", key)?;
+                    }
+                } else {
+                    // the next segment is not encrypted, so remove all available keys
+                    available_keys.clear();
+                    available_keys.insert(ExtXKey::empty());
+                    writeln!(f, "{}", key)?;
+                }
+            }
+
+            write!(f, "{}", segment)?;
+        }
+
+        for part in &self.incomplete_parts {
+            writeln!(f, "{}", part)?;
+        }
+
+        for value in &self.unknown {
+            writeln!(f, "{}", value)?;
+        }
+
+        if self.has_end_list {
+            writeln!(f, "{}", ExtXEndList)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_media_playlist<'a>(
+    input: &'a str,
+    builder: &mut MediaPlaylistBuilder<'a>,
+    options: ParseOptions,
+) -> crate::Result<(MediaPlaylist<'a>, Vec<Warning>)> {
+    let input = tag(input, "#EXTM3U")?;
+
+    let mut lines = Lines::with_options(input, options);
+    let (playlist, mut warnings) =
+        parse_media_playlist_lines(iter::from_fn(|| lines.next_numbered()), builder, options)?;
+
+    lines.warnings.append(&mut warnings);
+
+    Ok((playlist, lines.warnings))
+}
+
+/// Parses a [`MediaPlaylist`] from the lines (without the `#EXTM3U` tag)
+/// together with their line number.
+fn parse_media_playlist_lines<'a, I>(
+    lines: I,
+    builder: &mut MediaPlaylistBuilder<'a>,
+    options: ParseOptions,
+) -> crate::Result<(MediaPlaylist<'a>, Vec<Warning>)>
+where
+    I: IntoIterator<Item = (usize, crate::Result<Line<'a>>)>,
+{
+    let mut warnings = vec![];
+
+    let mut segment = MediaSegment::builder();
+    let mut segments = vec![];
+
+    let mut has_partial_segment = false;
+    let mut has_discontinuity_tag = false;
+    let mut unknown = vec![];
+    let mut defines = vec![];
+    let mut available_keys = vec![];
+
+    // the partial segments of the current segment, which belong to the
+    // playlist itself, if the segment is still being produced
+    let mut parts = vec![];
+
+    let mut has_ext_inf = false;
+    // a trailing uri without an `#EXTINF` tag, that has been dropped
+    let mut dangling_uri = None;
+
+    // the first line of the segment, that is currently being parsed
+    let mut segment_start = None;
+    let mut source_lines = vec![];
+
+    for (line_number, line) in lines {
+        let line = line?;
+
+        if let Some(uri) = &dangling_uri {
+            if !matches!(line, Line::Comment(_)) {
+                return Err(Error::custom(format!(
+                    "missing `#EXTINF` tag for the `MediaSegment` {:?}",
+                    uri
+                )));
+            }
+        }
+
+        match line {
+            Line::Tag(tag) => {
+                match tag {
+                    Tag::ExtInf(t) => {
+                        has_partial_segment = true;
+                        has_ext_inf = true;
+                        segment.duration(t);
+                    }
+                    Tag::ExtXByteRange(t) => {
+                        has_partial_segment = true;
+                        segment.byte_range(t);
+                    }
+                    Tag::ExtXBitrate(t) => {
+                        has_partial_segment = true;
+                        segment.bitrate(t);
+                    }
+                    Tag::ExtXDiscontinuity(_) => {
+                        has_discontinuity_tag = true;
+                        has_partial_segment = true;
+                        segment.has_discontinuity(true);
+                    }
+                    Tag::ExtXGap(_) => {
+                        has_partial_segment = true;
+                        segment.is_gap(true);
+                    }
+                    Tag::ExtXKey(key) => {
+                        has_partial_segment = true;
+
+                        // An ExtXKey applies to every MediaSegment and to every Media
+                        // Initialization Section declared by an ExtXMap tag, that appears
+                        // between it and the next ExtXKey tag in the Playlist file with the
+                        // same KEYFORMAT attribute (or the end of the Playlist file).
+
+                        if let ExtXKey(Some(decryption_key)) = &key {
+                            // the key replaces the previous key with the same format (or an
+                            // empty key), all other keys still apply. A missing `KEYFORMAT`
+                            // is the same as `identity`:
+                            let format = decryption_key.effective_format();
+
+                            available_keys.retain(|old_key: &ExtXKey<'_>| {
+                                if let ExtXKey(Some(old_decryption_key)) = old_key {
+                                    old_decryption_key.effective_format() != format
+                                } else {
+                                    false
+                                }
+                            });
+
+                            available_keys.push(key);
+                        } else {
+                            available_keys.clear();
+                            available_keys.push(ExtXKey::empty());
+                        }
+                    }
+                    Tag::ExtXMap(mut t) => {
+                        has_partial_segment = true;
+
+                        t.keys = available_keys.clone();
+                        segment.map(t);
+                    }
+                    Tag::ExtXProgramDateTime(t) => {
+                        has_partial_segment = true;
+                        segment.program_date_time(t);
+                    }
+                    Tag::ExtXPart(t) => {
+                        parts.push(t);
+                    }
+                    Tag::ExtXDateRange(t) => {
+                        has_partial_segment = true;
+                        segment.date_range(t);
+                    }
+                    Tag::ExtXTargetDuration(t) => {
+                        builder.target_duration(t.0);
+                    }
+                    Tag::ExtXMediaSequence(t) => {
+                        builder.media_sequence(t.0);
+                    }
+                    Tag::ExtXDiscontinuitySequence(t) => {
+                        // this tag must appear before the first MediaSegment in the playlist
+                        // https://tools.ietf.org/html/rfc8216#section-4.3.3.3
+                        if !segments.is_empty() {
+                            return Err(Error::custom("discontinuity sequence tag must appear before the first media segment in the playlist"));
+                        }
+
+                        // this tag must appear before any ExtXDiscontinuity tag
+                        // https://tools.ietf.org/html/rfc8216#section-4.3.3.3
+                        if has_discontinuity_tag {
+                            return Err(Error::custom("discontinuity sequence tag must appear before any `ExtXDiscontinuity` tag"));
+                        }
+
+                        builder.discontinuity_sequence(t.0);
+                    }
+                    Tag::ExtXEndList(_) => {
+                        builder.has_end_list(true);
+                    }
+                    Tag::PlaylistType(t) => {
+                        builder.playlist_type(t);
+                    }
+                    Tag::ExtXAllowCache(t) => {
+                        builder.allow_cache(t.is_allowed());
+                    }
+                    Tag::ExtXPartInf(t) => {
+                        builder.part_target(t.0);
+                    }
+                    Tag::ExtXServerControl(t) => {
+                        builder.server_control(t);
+                    }
+                    Tag::ExtXIFramesOnly(_) => {
+                        builder.has_i_frames_only(true);
+                    }
+                    Tag::ExtXMedia(_)
+                    | Tag::ExtXContentSteering(_)
+                    | Tag::VariantStream(_)
+                    | Tag::ExtXSessionData(_)
+                    | Tag::ExtXSessionKey(_) => {
+                        return Err(Error::unexpected_tag(tag));
+                    }
+                    Tag::ExtXIndependentSegments(_) => {
+                        builder.has_independent_segments(true);
+                    }
+                    Tag::ExtXStart(t) => {
+                        builder.start(t);
+                    }
+                    Tag::ExtXDefine(t) => {
+                        defines.push(t);
+                    }
+                    Tag::ExtXVersion(_) => {}
+                    Tag::Unknown(s) => {
+                        // [6.3.1. General Client Responsibilities]
+                        // > ignore any unrecognized tags.
+                        unknown.push(s);
+                    }
+                }
+            }
+            Line::Uri(uri) => {
+                // Some packagers already list the last segment of a live playlist,
+                // before its `#EXTINF` tag is known. Such a segment is dropped in
+                // lenient mode, if it is the last line of the playlist.
+                if !has_ext_inf && options.is_lenient {
+                    dangling_uri = Some(uri);
+                    segment = MediaSegment::builder();
+                    parts.clear();
+                    has_partial_segment = false;
+                    segment_start = None;
+                    continue;
+                }
+
+                segment.uri(uri);
+                segment.parts(mem::take(&mut parts));
+                segment.keys(available_keys.clone());
+                segments.push(segment.build().map_err(Error::builder)?);
+
+                if options.retain_source_lines {
+                    source_lines.push(segment_start.unwrap_or(line_number)..=line_number);
+                }
+
+                segment_start = None;
+
+                segment = MediaSegment::builder();
+                has_partial_segment = false;
+                has_ext_inf = false;
+            }
+            Line::Comment(_) => {}
+        }
+
+        if (has_partial_segment || !parts.is_empty()) && segment_start.is_none() {
+            segment_start = Some(line_number);
+        }
+    }
+
+    if let Some(uri) = dangling_uri {
+        warnings.push(Warning::IncompleteSegment {
+            uri: uri.to_string(),
+        });
+    }
+
+    if has_partial_segment {
+        return Err(Error::custom("Missing URI for the last `MediaSegment`"));
+    }
+
+    // the partial segments of a segment, that is still being produced
+    builder.incomplete_parts(parts);
+    builder.unknown(unknown);
+    builder.defines(defines);
+    builder.segments(segments);
+
+    let mut playlist = builder.build().map_err(Error::builder)?;
+    playlist.source_lines = SourceLines(source_lines);
+
+    Ok((playlist, warnings))
+}
+
+impl<'a> MediaPlaylist<'a> {
+    /// Parses a [`MediaPlaylist`] with the given [`ParseOptions`] and returns
+    /// it together with all [`Warning`]s, that have been encountered.
+    ///
+    /// In lenient mode, a trailing `URI` without an `#EXTINF` tag (which some
+    /// packagers write for the incomplete last segment of a live playlist) is
+    /// dropped and reported as a [`Warning::IncompleteSegment`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::MediaPlaylist;
+    /// use hls_m3u8::{ParseOptions, Warning};
+    ///
+    /// let input = concat!(
+    ///     "#EXTM3U\n",
+    ///     "#EXT-X-TARGETDURATION:10\n",
+    ///     "#EXTINF:10,\n",
+    ///     "segment0.ts\n",
+    ///     "segment1.ts\n",
+    /// );
+    ///
+    /// assert!(MediaPlaylist::parse_with(input, ParseOptions::strict()).is_err());
+    ///
+    /// let (playlist, warnings) = MediaPlaylist::parse_with(input, ParseOptions::lenient())?;
+    ///
+    /// assert_eq!(playlist.segments.num_elements(), 1);
+    /// assert_eq!(
+    ///     warnings,
+    ///     vec![Warning::IncompleteSegment {
+    ///         uri: "segment1.ts".to_string()
+    ///     }]
+    /// );
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function fails, if the input is not a valid [`MediaPlaylist`].
+    pub fn parse_with(
+        input: &'a str,
+        options: ParseOptions,
+    ) -> crate::Result<(Self, Vec<Warning>)> {
+        parse_media_playlist(input, &mut Self::builder(), options)
+    }
+}
+
+impl FromStr for MediaPlaylist<'static> {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Ok(MediaPlaylist::try_from(input)?.into_owned())
+    }
+}
+
+impl<'a> TryFrom<&'a str> for MediaPlaylist<'a> {
+    type Error = Error;
+
+    fn try_from(input: &'a str) -> Result<Self, Self::Error> {
+        Self::parse_with(input, ParseOptions::strict()).map(|(playlist, _)| playlist)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tags::{ExtInf, ExtXMap};
+    use crate::types::ByteRange;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn too_large_segment_duration_test() {
+        let playlist = concat!(
+            "#EXTM3U\n",
+            "#EXT-X-TARGETDURATION:8\n",
+            "#EXT-X-VERSION:3\n",
+            "#EXTINF:9.009,\n",
+            "http://media.example.com/first.ts\n",
+            "#EXTINF:9.509,\n",
+            "http://media.example.com/second.ts\n",
+            "#EXTINF:3.003,\n",
+            "http://media.example.com/third.ts\n",
+            "#EXT-X-ENDLIST\n"
+        );
+
+        // Error (allowable segment duration = target duration = 8)
+        assert!(MediaPlaylist::try_from(playlist).is_err());
+
+        // Error (allowable segment duration = 9)
+        assert!(MediaPlaylist::builder()
+            .allowable_excess_duration(Duration::from_secs(1))
+            .parse(playlist)
+            .is_err());
+
+        // Ok (allowable segment duration = 10)
+        assert_eq!(
+            MediaPlaylist::builder()
+                .allowable_excess_duration(Duration::from_secs(2))
+                .parse(playlist)
+                .unwrap(),
+            MediaPlaylist::builder()
+                .allowable_excess_duration(Duration::from_secs(2))
+                .target_duration(Duration::from_secs(8))
+                .segments(vec![
+                    MediaSegment::builder()
+                        .duration(Duration::from_secs_f64(9.009))
+                        .uri("http://media.example.com/first.ts")
+                        .build()
+                        .unwrap(),
+                    MediaSegment::builder()
+                        .duration(Duration::from_secs_f64(9.509))
+                        .uri("http://media.example.com/second.ts")
+                        .build()
+                        .unwrap(),
+                    MediaSegment::builder()
+                        .duration(Duration::from_secs_f64(3.003))
+                        .uri("http://media.example.com/third.ts")
+                        .build()
+                        .unwrap(),
+                ])
+                .has_end_list(true)
+                .build()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_segment_number_simple() {
+        let playlist = MediaPlaylist::builder()
+            .allowable_excess_duration(Duration::from_secs(2))
+            .target_duration(Duration::from_secs(8))
+            .segments(vec![
+                MediaSegment::builder()
+                    .duration(Duration::from_secs_f64(9.009))
+                    .uri("http://media.example.com/first.ts")
+                    .build()
+                    .unwrap(),
+                MediaSegment::builder()
+                    .duration(Duration::from_secs_f64(9.509))
+                    .uri("http://media.example.com/second.ts")
+                    .build()
+                    .unwrap(),
+                MediaSegment::builder()
+                    .duration(Duration::from_secs_f64(3.003))
+                    .uri("http://media.example.com/third.ts")
+                    .build()
+                    .unwrap(),
+            ])
+            .build()
+            .unwrap();
+
+        let mut segments = playlist.segments.into_iter().map(|(k, v)| (k, v.number));
+        assert_eq!(segments.next(), Some((0, 0)));
+        assert_eq!(segments.next(), Some((1, 1)));
+        assert_eq!(segments.next(), Some((2, 2)));
+        assert_eq!(segments.next(), None);
+    }
+
+    #[test]
+    fn test_segment_number_sequence() {
+        let playlist = MediaPlaylist::builder()
+            .target_duration(Duration::from_secs(8))
+            .media_sequence(2680)
+            .segments(vec![
+                MediaSegment::builder()
+                    .duration(Duration::from_secs_f64(7.975))
+                    .uri("https://priv.example.com/fileSequence2680.ts")
+                    .build()
+                    .unwrap(),
+                MediaSegment::builder()
+                    .duration(Duration::from_secs_f64(7.941))
+                    .uri("https://priv.example.com/fileSequence2681.ts")
+                    .build()
+                    .unwrap(),
+                MediaSegment::builder()
+                    .duration(Duration::from_secs_f64(7.975))
+                    .uri("https://priv.example.com/fileSequence2682.ts")
+                    .build()
+                    .unwrap(),
+            ])
+            .build()
+            .unwrap();
+        let mut segments = playlist.segments.into_iter().map(|(k, v)| (k, v.number));
+        assert_eq!(segments.next(), Some((0, 2680)));
+        assert_eq!(segments.next(), Some((1, 2681)));
+        assert_eq!(segments.next(), Some((2, 2682)));
+        assert_eq!(segments.next(), None);
+    }
+
+    #[test]
+    fn test_missing_trailing_newline() {
+        let playlist = concat!(
+            "#EXTM3U\n",
+            "#EXT-X-TARGETDURATION:10\n",
+            "#EXTINF:9.009,\n",
+            "http://media.example.com/first.ts\n",
+            "#EXTINF:9.009,\n",
+            "http://media.example.com/second.ts"
+        );
+
+        let playlist = MediaPlaylist::try_from(playlist).unwrap();
+        assert_eq!(playlist.segments.num_elements(), 2);
+        assert_eq!(
+            playlist.segments[1].uri(),
+            "http://media.example.com/second.ts"
+        );
+        assert!(!playlist.has_end_list);
+
+        let playlist = concat!(
+            "#EXTM3U\n",
+            "#EXT-X-TARGETDURATION:10\n",
+            "#EXTINF:9.009,\n",
+            "http://media.example.com/first.ts\n",
+            "#EXT-X-ENDLIST"
+        );
+
+        let playlist = MediaPlaylist::try_from(playlist).unwrap();
+        assert_eq!(playlist.segments.num_elements(), 1);
+        assert!(playlist.has_end_list);
+    }
+
+    #[test]
+    fn test_date_range_id() {
+        let playlist = |second: &'static str| {
+            MediaPlaylist::try_from(
+                [
+                    "#EXTM3U\n",
+                    "#EXT-X-TARGETDURATION:10\n",
+                    "#EXT-X-DATERANGE:ID=\"ad1\",START-DATE=\"2020-01-02T21:55:44Z\",",
+                    "PLANNED-DURATION=20\n",
+                    "#EXTINF:10,\n",
+                    "http://media.example.com/first.ts\n",
+                    second,
+                    "#EXTINF:10,\n",
+                    "http://media.example.com/second.ts\n",
+                ]
+                .concat()
+                .as_str(),
+            )
+            .unwrap()
+            .validate()
+        };
+
+        // the same tag can be repeated:
+        assert_eq!(
+            playlist(concat!(
+                "#EXT-X-DATERANGE:ID=\"ad1\",START-DATE=\"2020-01-02T21:55:44Z\",",
+                "PLANNED-DURATION=20\n"
+            )),
+            vec![]
+        );
+
+        // a later tag can add attributes:
+        assert_eq!(
+            playlist(concat!(
+                "#EXT-X-DATERANGE:ID=\"ad1\",START-DATE=\"2020-01-02T21:55:44Z\",",
+                "DURATION=19.5\n"
+            )),
+            vec![]
+        );
+
+        // a different id is fine:
+        assert_eq!(
+            playlist(concat!(
+                "#EXT-X-DATERANGE:ID=\"ad2\",START-DATE=\"2020-01-02T21:56:04Z\",",
+                "PLANNED-DURATION=30\n"
+            )),
+            vec![]
+        );
+
+        // but the same id with a different value is not:
+        assert_eq!(
+            playlist(concat!(
+                "#EXT-X-DATERANGE:ID=\"ad1\",START-DATE=\"2020-01-02T21:56:04Z\",",
+                "PLANNED-DURATION=20\n"
+            )),
+            vec![Warning::ConflictingDateRange {
+                id: "ad1".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_vod_requires_end_list() {
+        let playlist = MediaPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-TARGETDURATION:10\n",
+            "#EXT-X-PLAYLIST-TYPE:VOD\n",
+            "#EXTINF:10,\n",
+            "http://media.example.com/first.ts\n",
+        ))
+        .unwrap();
+
+        assert_eq!(playlist.validate(), vec![Warning::MissingEndList]);
+
+        let playlist = MediaPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-TARGETDURATION:10\n",
+            "#EXT-X-PLAYLIST-TYPE:VOD\n",
+            "#EXTINF:10,\n",
+            "http://media.example.com/first.ts\n",
+            "#EXT-X-ENDLIST\n"
+        ))
+        .unwrap();
+
+        assert_eq!(playlist.validate(), vec![]);
+
+        // an event playlist can be extended:
+        let playlist = MediaPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-TARGETDURATION:10\n",
+            "#EXT-X-PLAYLIST-TYPE:EVENT\n",
+            "#EXTINF:10,\n",
+            "http://media.example.com/first.ts\n",
+        ))
+        .unwrap();
+
+        assert_eq!(playlist.validate(), vec![]);
+
+        let playlist = MediaPlaylist::builder()
+            .target_duration(Duration::from_secs(10))
+            .playlist_type(PlaylistType::Vod)
+            .segments(vec![])
+            .build()
+            .unwrap();
+
+        assert_eq!(playlist.validate(), vec![Warning::MissingEndList]);
+    }
+
+    #[test]
+    fn test_parts_require_part_inf() {
+        // missing `#EXT-X-PART-INF`
+        let playlist = MediaPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-TARGETDURATION:4\n",
+            "#EXT-X-SERVER-CONTROL:PART-HOLD-BACK=1.5\n",
+            "#EXT-X-PART:DURATION=0.5,URI=\"part0.0.mp4\"\n",
+            "#EXT-X-PART:DURATION=0.5,URI=\"part0.1.mp4\"\n",
+            "#EXTINF:1,\n",
+            "segment0.mp4\n",
+        ))
+        .unwrap();
+
+        assert_eq!(playlist.validate(), vec![Warning::MissingPartInf]);
+
+        // missing `PART-HOLD-BACK`
+        let playlist = MediaPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-TARGETDURATION:4\n",
+            "#EXT-X-SERVER-CONTROL:CAN-BLOCK-RELOAD=YES\n",
+            "#EXT-X-PART-INF:PART-TARGET=0.5\n",
+            "#EXT-X-PART:DURATION=0.5,URI=\"part0.0.mp4\"\n",
+            "#EXTINF:1,\n",
+            "segment0.mp4\n",
+        ))
+        .unwrap();
+
+        assert_eq!(playlist.validate(), vec![Warning::MissingPartHoldBack]);
+
+        let input = concat!(
+            "#EXTM3U\n",
+            "#EXT-X-TARGETDURATION:4\n",
+            "#EXT-X-SERVER-CONTROL:PART-HOLD-BACK=1.5,CAN-BLOCK-RELOAD=YES\n",
+            "#EXT-X-PART-INF:PART-TARGET=0.5\n",
+            "#EXT-X-PART:DURATION=0.5,URI=\"part0.0.mp4\",INDEPENDENT=YES\n",
+            "#EXT-X-PART:DURATION=0.5,URI=\"part0.1.mp4\"\n",
+            "#EXTINF:1,\n",
+            "segment0.mp4\n",
+        );
+
+        let playlist = MediaPlaylist::try_from(input).unwrap();
+
+        assert_eq!(playlist.part_target, Some(Duration::from_millis(500)));
+        assert_eq!(
+            playlist.server_control.and_then(|v| v.part_hold_back),
+            Some(Duration::from_millis(1500))
+        );
+        assert_eq!(playlist.segments[0].parts.len(), 2);
+        assert_eq!(playlist.to_string(), input.to_string());
+        assert_eq!(playlist.validate(), vec![]);
+    }
+
+    #[test]
+    fn test_incomplete_parts() {
+        // a live playlist ends with the parts of the segment, that is still
+        // being produced
+        let input = concat!(
+            "#EXTM3U\n",
+            "#EXT-X-TARGETDURATION:4\n",
+            "#EXT-X-SERVER-CONTROL:PART-HOLD-BACK=1.5,CAN-BLOCK-RELOAD=YES\n",
+            "#EXT-X-PART-INF:PART-TARGET=0.5\n",
+            "#EXT-X-PART:DURATION=0.5,URI=\"part0.0.mp4\",INDEPENDENT=YES\n",
+            "#EXT-X-PART:DURATION=0.5,URI=\"part0.1.mp4\"\n",
+            "#EXTINF:1,\n",
+            "segment0.mp4\n",
+            "#EXT-X-PART:DURATION=0.5,URI=\"part1.0.mp4\",INDEPENDENT=YES\n",
+            "#EXT-X-PART:DURATION=0.5,URI=\"part1.1.mp4\"\n",
+        );
+
+        let playlist = MediaPlaylist::try_from(input).unwrap();
+
+        assert_eq!(playlist.segments.num_elements(), 1);
+        assert_eq!(playlist.segments[0].parts.len(), 2);
+        assert_eq!(
+            playlist
+                .incomplete_parts
+                .iter()
+                .map(|part| part.uri().to_string())
+                .collect::<Vec<_>>(),
+            vec!["part1.0.mp4".to_string(), "part1.1.mp4".to_string()]
+        );
+        assert_eq!(
+            playlist
+                .tags::<ExtXPart<'_>>()
+                .into_iter()
+                .map(|part| part.uri().to_string())
+                .collect::<Vec<_>>(),
+            vec![
+                "part0.0.mp4".to_string(),
+                "part0.1.mp4".to_string(),
+                "part1.0.mp4".to_string(),
+                "part1.1.mp4".to_string(),
+            ]
+        );
+        assert_eq!(playlist.to_string(), input.to_string());
+        assert_eq!(playlist.validate(), vec![]);
+        assert_eq!(playlist.clone().into_owned(), playlist);
+
+        // other tags of an incomplete segment still require a `URI`
+        assert!(MediaPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-TARGETDURATION:4\n",
+            "#EXT-X-PART-INF:PART-TARGET=0.5\n",
+            "#EXTINF:1,\n",
+            "segment0.mp4\n",
+            "#EXT-X-PART:DURATION=0.5,URI=\"part1.0.mp4\"\n",
+            "#EXTINF:1,\n",
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn test_display_alternate() {
+        let playlist = MediaPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-TARGETDURATION:6\n",
+            "#EXT-X-START:TIME-OFFSET=-8\n",
+            "#EXTINF:6,\n",
+            "segment.ts\n",
+        ))
+        .unwrap();
+
+        assert_eq!(
+            format!("{}", playlist),
+            concat!(
+                "#EXTM3U\n",
+                "#EXT-X-TARGETDURATION:6\n",
+                "#EXT-X-START:TIME-OFFSET=-8\n",
+                "#EXTINF:6,\n",
+                "segment.ts\n",
+            )
+            .to_string()
+        );
+
+        assert_eq!(
+            format!("{:#}", playlist),
+            concat!(
+                "#EXTM3U\n",
+                "#EXT-X-TARGETDURATION:6\n",
+                "#EXT-X-START:TIME-OFFSET=-8,PRECISE=NO\n",
+                "#EXTINF:6,\n",
+                "segment.ts\n",
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_start_position() {
+        let input = |time_offset: &str| {
+            format!(
+                concat!(
+                    "#EXTM3U\n",
+                    "#EXT-X-TARGETDURATION:6\n",
+                    "#EXT-X-MEDIA-SEQUENCE:100\n",
+                    "#EXT-X-START:TIME-OFFSET={}\n",
+                    "#EXTINF:6,\n",
+                    "segment100.ts\n",
+                    "#EXTINF:6,\n",
+                    "segment101.ts\n",
+                    "#EXTINF:6,\n",
+                    "segment102.ts\n",
+                    "#EXTINF:6,\n",
+                    "segment103.ts\n",
+                ),
+                time_offset
+            )
+        };
+
+        let start_position = |time_offset: &str| {
+            MediaPlaylist::try_from(input(time_offset).as_str())
+                .unwrap()
+                .start_position()
+        };
+
+        // 24s - 8s = 16s, which is 4s into the third segment
+        assert_eq!(start_position("-8.0"), Some((102, Duration::from_secs(4))));
+        assert_eq!(start_position("-6"), Some((103, Duration::from_secs(0))));
+        assert_eq!(start_position("8"), Some((101, Duration::from_secs(2))));
+        assert_eq!(start_position("0"), Some((100, Duration::from_secs(0))));
+
+        // offsets, that exceed the duration of the playlist are clamped
+        assert_eq!(start_position("-100"), Some((100, Duration::from_secs(0))));
+        assert_eq!(start_position("100"), Some((103, Duration::from_secs(6))));
+
+        // no start tag
+        assert_eq!(
+            MediaPlaylist::try_from(concat!(
+                "#EXTM3U\n",
+                "#EXT-X-TARGETDURATION:6\n",
+                "#EXTINF:6,\n",
+                "segment.ts\n",
+            ))
+            .unwrap()
+            .start_position(),
+            None
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "chrono")]
+    fn test_date_range_position() {
+        let playlist = MediaPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-TARGETDURATION:10\n",
+            "#EXT-X-MEDIA-SEQUENCE:100\n",
+            "#EXTINF:10,\n",
+            "segment100.ts\n",
+            "#EXT-X-PROGRAM-DATE-TIME:2020-01-01T00:00:10.000Z\n",
+            "#EXTINF:10,\n",
+            "segment101.ts\n",
+            "#EXTINF:6,\n",
+            "segment102.ts\n",
+            "#EXT-X-DISCONTINUITY\n",
+            "#EXT-X-PROGRAM-DATE-TIME:2020-01-01T01:00:00.000Z\n",
+            "#EXTINF:10,\n",
+            "segment103.ts\n",
+        ))
+        .unwrap();
+
+        let date_range = |start_date: &str| {
+            ExtXDateRange::try_from(
+                format!("#EXT-X-DATERANGE:ID=\"ad\",START-DATE=\"{}\"", start_date).as_str(),
+            )
+            .unwrap()
+            .into_owned()
+        };
+
+        assert_eq!(
+            playlist.date_range_position(&date_range("2020-01-01T00:00:10.000Z")),
+            Some((101, Duration::from_secs(0)))
+        );
+
+        assert_eq!(
+            playlist.date_range_position(&date_range("2020-01-01T00:00:23.000Z")),
+            Some((102, Duration::from_secs(3)))
+        );
+
+        assert_eq!(
+            playlist.date_range_position(&date_range("2020-01-01T01:00:05.000Z")),
+            Some((103, Duration::from_secs(5)))
+        );
+
+        // the first segment has no program date time:
+        assert_eq!(
+            playlist.date_range_position(&date_range("2020-01-01T00:00:05.000Z")),
+            None
+        );
+
+        // in the gap between the discontinuity:
+        assert_eq!(
+            playlist.date_range_position(&date_range("2020-01-01T00:30:00.000Z")),
+            None
+        );
+
+        assert_eq!(
+            playlist.date_range_position(&ExtXDateRange::builder().id("ad").build().unwrap()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_date_ranges_by_class() {
+        let playlist = MediaPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-TARGETDURATION:10\n",
+            "#EXT-X-DATERANGE:ID=\"ad2\",CLASS=\"com.apple.hls.interstitial\",",
+            "START-DATE=\"2020-01-01T00:00:30Z\"\n",
+            "#EXTINF:10,\n",
+            "segment0.ts\n",
+            "#EXT-X-DATERANGE:ID=\"chapter\",CLASS=\"chapter\",",
+            "START-DATE=\"2020-01-01T00:00:00Z\"\n",
+            "#EXTINF:10,\n",
+            "segment1.ts\n",
+            "#EXT-X-DATERANGE:ID=\"ad1\",CLASS=\"com.apple.hls.interstitial\",",
+            "START-DATE=\"2020-01-01T00:00:10Z\"\n",
+            "#EXTINF:10,\n",
+            "segment2.ts\n",
+            "#EXT-X-DATERANGE:ID=\"other\",START-DATE=\"2020-01-01T00:00:05Z\"\n",
+            "#EXTINF:10,\n",
+            "segment3.ts\n",
+        ))
+        .unwrap();
+
+        let ids = |class| {
+            playlist
+                .date_ranges_by_class(class)
+                .into_iter()
+                .map(|date_range| date_range.id().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(ids("com.apple.hls.interstitial"), vec!["ad1", "ad2"]);
+        assert_eq!(ids("chapter"), vec!["chapter"]);
+        assert_eq!(ids("unknown"), Vec::<String>::new());
+
+        // the dates are ordered by the instant they represent and not by their
+        // text (this does not rely on the `chrono` feature):
+        let playlist = MediaPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-TARGETDURATION:10\n",
+            "#EXT-X-DATERANGE:ID=\"c\",CLASS=\"ad\",START-DATE=\"2020-01-01T00:00:10.5Z\"\n",
+            "#EXTINF:10,\n",
+            "segment0.ts\n",
+            "#EXT-X-DATERANGE:ID=\"b\",CLASS=\"ad\",START-DATE=\"2020-01-01T00:00:10Z\"\n",
+            "#EXTINF:10,\n",
+            "segment1.ts\n",
+            "#EXT-X-DATERANGE:ID=\"a\",CLASS=\"ad\",START-DATE=\"2020-01-01T00:00:05+01:00\"\n",
+            "#EXTINF:10,\n",
+            "segment2.ts\n",
+        ))
+        .unwrap();
+
+        assert_eq!(
+            playlist
+                .date_ranges_by_class("ad")
+                .into_iter()
+                .map(|date_range| date_range.id().to_string())
+                .collect::<Vec<_>>(),
+            vec!["a", "b", "c"]
+        );
+    }
+
+    #[test]
+    fn test_tags() {
+        let playlist = MediaPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-TARGETDURATION:10\n",
+            "#EXT-X-DATERANGE:ID=\"first\",START-DATE=\"2020-01-01T00:00:00Z\"\n",
+            "#EXTINF:10,\n",
+            "segment0.ts\n",
+            "#EXT-X-BYTERANGE:100@0\n",
+            "#EXTINF:5,\n",
+            "segment1.ts\n",
+            "#EXT-X-DATERANGE:ID=\"second\",START-DATE=\"2020-01-01T00:00:15Z\"\n",
+            "#EXTINF:10,\n",
+            "segment2.ts\n",
+        ))
+        .unwrap();
+
+        assert_eq!(
+            playlist
+                .tags::<ExtXDateRange<'_>>()
+                .into_iter()
+                .map(|date_range| date_range.id().to_string())
+                .collect::<Vec<_>>(),
+            vec!["first".to_string(), "second".to_string()]
+        );
+
+        assert_eq!(
+            playlist.tags::<ExtInf<'_>>(),
+            vec![
+                &ExtInf::new(Duration::from_secs(10)),
+                &ExtInf::new(Duration::from_secs(5)),
+                &ExtInf::new(Duration::from_secs(10)),
+            ]
+        );
+
+        assert_eq!(
+            playlist.tags::<ExtXByteRange>(),
+            vec![&ExtXByteRange::from(0..100)]
+        );
+        assert!(playlist.tags::<ExtXMap<'_>>().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "chrono")]
+    fn test_validate_program_date_times() {
+        let playlist = MediaPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-TARGETDURATION:10\n",
+            "#EXT-X-PROGRAM-DATE-TIME:2020-01-01T00:00:00.000Z\n",
+            "#EXTINF:10,\n",
+            "segment0.ts\n",
+            "#EXT-X-PROGRAM-DATE-TIME:2020-01-01T00:00:10.000Z\n",
+            "#EXTINF:10,\n",
+            "segment1.ts\n",
+            // goes backwards:
+            "#EXT-X-PROGRAM-DATE-TIME:2020-01-01T00:00:05.000Z\n",
+            "#EXTINF:10,\n",
+            "segment2.ts\n",
+            "#EXTINF:10,\n",
+            "segment3.ts\n",
+            // a discontinuity may reset the timeline:
+            "#EXT-X-DISCONTINUITY\n",
+            "#EXT-X-PROGRAM-DATE-TIME:2019-01-01T00:00:00.000Z\n",
+            "#EXTINF:10,\n",
+            "segment4.ts\n",
+        ))
+        .unwrap();
+
+        assert_eq!(
+            playlist.validate_program_date_times(),
+            vec![Warning::ProgramDateTimeOutOfOrder {
+                number: 2,
+                previous: "2020-01-01T00:00:10.000Z".to_string(),
+                current: "2020-01-01T00:00:05.000Z".to_string(),
+            }]
+        );
+
+        let playlist = MediaPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-TARGETDURATION:10\n",
+            "#EXT-X-PROGRAM-DATE-TIME:2020-01-01T00:00:00.000Z\n",
+            "#EXTINF:10,\n",
+            "segment0.ts\n",
+            "#EXT-X-PROGRAM-DATE-TIME:2020-01-01T00:00:00.000Z\n",
+            "#EXTINF:10,\n",
+            "segment1.ts\n",
+        ))
+        .unwrap();
+
+        assert_eq!(playlist.validate_program_date_times(), vec![]);
+    }
+
+    #[test]
+    #[cfg(feature = "chrono")]
+    fn test_segment_date_times() {
+        use chrono::TimeZone;
+
+        let playlist = MediaPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-TARGETDURATION:10\n",
+            "#EXT-X-PROGRAM-DATE-TIME:2020-01-01T00:00:00.000Z\n",
+            "#EXTINF:10,\n",
+            "segment0.ts\n",
+            "#EXTINF:2.5,\n",
+            "segment1.ts\n",
+            "#EXTINF:10,\n",
+            "segment2.ts\n",
+            "#EXT-X-DISCONTINUITY\n",
+            "#EXTINF:10,\n",
+            "segment3.ts\n",
+            "#EXT-X-PROGRAM-DATE-TIME:2020-01-01T01:00:00.000+01:00\n",
+            "#EXTINF:10,\n",
+            "segment4.ts\n",
+            "#EXTINF:10,\n",
+            "segment5.ts\n",
+        ))
+        .unwrap();
+
+        let utc = FixedOffset::east(0);
+        let cet = FixedOffset::east(3600);
+
+        assert_eq!(
+            playlist
+                .segment_date_times()
+                .map(|(segment, date_time)| (segment.uri().to_string(), date_time))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "segment0.ts".to_string(),
+                    Some(utc.ymd(2020, 1, 1).and_hms(0, 0, 0))
+                ),
+                (
+                    "segment1.ts".to_string(),
+                    Some(utc.ymd(2020, 1, 1).and_hms(0, 0, 10))
+                ),
+                (
+                    "segment2.ts".to_string(),
+                    Some(utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 12, 500))
+                ),
+                // the discontinuity resets the timeline
+                ("segment3.ts".to_string(), None),
+                (
+                    "segment4.ts".to_string(),
+                    Some(cet.ymd(2020, 1, 1).and_hms(1, 0, 0))
+                ),
+                (
+                    "segment5.ts".to_string(),
+                    Some(cet.ymd(2020, 1, 1).and_hms(1, 0, 10))
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_source_line() {
+        let input = concat!(
+            "#EXTM3U\n",
+            "#EXT-X-TARGETDURATION:10\n",
+            "#EXTINF:10,\n",
+            "segment0.ts\n",
+            "# a comment\n",
+            "\n",
+            "#EXT-X-BYTERANGE:100@0\n",
+            "#EXTINF:10,\n",
+            "segment1.ts\n",
+            "#EXTINF:10,\n",
+            "segment2.ts\n",
+        );
+
+        let options = ParseOptions::strict().with_source_lines();
+        let (mut playlist, _) = MediaPlaylist::parse_with(input, options).unwrap();
+
+        let lines = input.lines().collect::<Vec<_>>();
+        let uri_line = |playlist: &MediaPlaylist<'_>, index| {
+            playlist
+                .source_line(index)
+                .map(|range| lines[*range.end() - 1])
+        };
+
+        for (i, segment) in playlist.segments.values().enumerate() {
+            assert_eq!(uri_line(&playlist, i), Some(&**segment.uri()));
+        }
+
+        assert_eq!(playlist.source_line(1), Some(7..=9));
+        assert_eq!(playlist.source_line(3), None);
+
+        // the source lines are kept in sync with the segments:
+        playlist.remove_oldest(1).unwrap();
+        assert_eq!(uri_line(&playlist, 0), Some("segment1.ts"));
+        assert_eq!(playlist.source_line(2), None);
+
+        // the source lines are not recorded by default:
+        let (playlist, _) = MediaPlaylist::parse_with(input, ParseOptions::strict()).unwrap();
+        assert_eq!(playlist.source_line(0), None);
+        assert_eq!(playlist, MediaPlaylist::try_from(input).unwrap());
+    }
+
+    #[test]
+    fn test_from_reader() {
+        let mut input = String::from("#EXTM3U\n#EXT-X-TARGETDURATION:10\n");
+        input.push_str("#EXT-X-KEY:METHOD=AES-128,URI=\"https://www.example.com/key\"\n\n");
+
+        for i in 0..500 {
+            input.push_str(&format!("#EXTINF:10,segment {}\nsegment{}.ts\n", i, i));
+        }
+
+        input.push_str("#EXT-X-ENDLIST\n");
+        assert!(input.len() > 10_000);
+
+        let reader = std::io::BufReader::new(input.as_bytes());
+        let playlist = MediaPlaylist::from_reader(reader).unwrap();
+
+        assert_eq!(playlist.segments.num_elements(), 500);
+        assert_eq!(playlist.segments[499].uri(), "segment499.ts");
+        assert_eq!(playlist, MediaPlaylist::try_from(input.as_str()).unwrap());
+
+        // the error contains the number of the line:
+        let input = concat!(
+            "#EXTM3U\n",
+            "#EXT-X-TARGETDURATION:10\n",
+            "\n",
+            "#EXTINF:10,\n",
+            "segment0.ts\n",
+            "#EXT-X-BYTERANGE:invalid\n",
+            "#EXTINF:10,\n",
+            "segment1.ts\n",
+        );
+
+        let error = MediaPlaylist::from_reader(input.as_bytes()).unwrap_err();
+        assert!(error.to_string().starts_with("line 6: "));
+
+        // errors about the entire playlist do not have a line number:
+        let error =
+            MediaPlaylist::from_reader(&b"#EXTM3U\n#EXTINF:10,\nsegment0.ts\n"[..]).unwrap_err();
+        assert!(!error.to_string().starts_with("line "));
+
+        assert!(MediaPlaylist::from_reader(&b""[..]).is_err());
+        assert!(MediaPlaylist::from_reader(&b"#EXT-X-TARGETDURATION:10\n"[..]).is_err());
+    }
+
+    #[test]
+    fn test_try_from_borrows() {
+        let input = concat!(
+            "#EXTM3U\n",
+            "#EXT-X-TARGETDURATION:10\n",
+            "#EXT-X-MAP:URI=\"init.mp4\"\n",
+            "#EXT-X-PART-INF:PART-TARGET=1.0\n",
+            "#EXT-X-SERVER-CONTROL:PART-HOLD-BACK=3.0\n",
+            "#EXT-X-PART:DURATION=1.0,URI=\"part0.mp4\"\n",
+            "#EXTINF:10,title\n",
+            "segment0.ts\n",
+        );
+
+        let playlist = MediaPlaylist::try_from(input).unwrap();
+        let segment = &playlist.segments[0];
+
+        // parsing from a `&str` does not copy the strings of the playlist:
+        assert!(matches!(segment.uri(), Cow::Borrowed("segment0.ts")));
+        assert!(matches!(
+            segment.duration.title(),
+            Some(Cow::Borrowed("title"))
+        ));
+        assert!(matches!(
+            segment.map.as_ref().unwrap().uri(),
+            Cow::Borrowed("init.mp4")
+        ));
+        assert!(matches!(segment.parts[0].uri(), Cow::Borrowed("part0.mp4")));
+
+        let playlist = playlist.into_owned();
+        assert!(matches!(playlist.segments[0].uri(), Cow::Owned(_)));
+    }
+
+    #[test]
+    fn test_into_iter() {
+        let playlist = MediaPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-TARGETDURATION:10\n",
+            "#EXTINF:10,\n",
+            "segment0.ts\n",
+            "#EXTINF:10,\n",
+            "segment1.ts\n",
+            "#EXTINF:5,\n",
+            "segment2.ts\n",
+        ))
+        .unwrap();
+
+        let mut count = 0;
+
+        for segment in &playlist {
+            assert_eq!(segment.uri(), &format!("segment{}.ts", count));
+            count += 1;
+        }
+
+        assert_eq!(count, 3);
+        assert_eq!((&playlist).into_iter().count(), 3);
+    }
+
+    #[test]
+    fn test_bitrates() {
+        let mut playlist = MediaPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-VERSION:4\n",
+            "#EXT-X-TARGETDURATION:10\n",
+            "#EXT-X-BITRATE:1500\n",
+            "#EXTINF:10,\n",
+            "segment0.ts\n",
+            "#EXTINF:10,\n",
+            "segment1.ts\n",
+            "#EXT-X-BITRATE:800\n",
+            "#EXTINF:10,\n",
+            "segment2.ts\n",
+            "#EXT-X-BYTERANGE:1000@0\n",
+            "#EXTINF:10,\n",
+            "segment3.ts\n",
+            "#EXTINF:10,\n",
+            "segment4.ts\n",
+        ))
+        .unwrap();
+
+        assert_eq!(
+            playlist.bitrates().collect::<Vec<_>>(),
+            vec![Some(1500), Some(1500), Some(800), None, Some(800)]
+        );
+
+        assert_eq!(
+            playlist.to_string(),
+            concat!(
+                "#EXTM3U\n",
+                "#EXT-X-VERSION:4\n",
+                "#EXT-X-TARGETDURATION:10\n",
+                "#EXT-X-BITRATE:1500\n",
+                "#EXTINF:10,\n",
+                "segment0.ts\n",
+                "#EXTINF:10,\n",
+                "segment1.ts\n",
+                "#EXT-X-BITRATE:800\n",
+                "#EXTINF:10,\n",
+                "segment2.ts\n",
+                "#EXT-X-BYTERANGE:1000@0\n",
+                "#EXTINF:10,\n",
+                "segment3.ts\n",
+                "#EXTINF:10,\n",
+                "segment4.ts\n",
+            )
+        );
+
+        // the bitrate still applies after the segment, that declared it, has been
+        // removed:
+        playlist.remove_oldest(1).unwrap();
+
+        assert_eq!(
+            playlist.bitrates().collect::<Vec<_>>(),
+            vec![Some(1500), Some(800), None, Some(800)]
+        );
+    }
+
+    #[test]
+    fn test_segments_in_range() {
+        let playlist = MediaPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-TARGETDURATION:10\n",
+            "#EXTINF:10,\n",
+            "segment0.ts\n",
+            "#EXTINF:10,\n",
+            "segment1.ts\n",
+            "#EXTINF:5,\n",
+            "segment2.ts\n",
+            "#EXTINF:10,\n",
+            "segment3.ts\n",
+        ))
+        .unwrap();
+
+        let in_range = |start: u64, end: u64| {
+            playlist
+                .segments_in_range(Duration::from_secs(start), Duration::from_secs(end))
+                .map(|s| s.uri().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        // the range matches the segment boundaries exactly:
+        assert_eq!(in_range(10, 25), vec!["segment1.ts", "segment2.ts"]);
+        // the segments straddling the boundaries are included:
+        assert_eq!(
+            in_range(9, 21),
+            vec!["segment0.ts", "segment1.ts", "segment2.ts"]
+        );
+        // the end is exclusive:
+        assert_eq!(in_range(0, 10), vec!["segment0.ts"]);
+        assert_eq!(in_range(24, 26), vec!["segment2.ts", "segment3.ts"]);
+        // the whole playlist:
+        assert_eq!(in_range(0, 100).len(), 4);
+        // ranges outside of the playlist and empty ranges:
+        assert_eq!(in_range(35, 100), Vec::<String>::new());
+        assert_eq!(in_range(5, 5), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_from_segments() {
+        let segment = |duration: Duration, uri: &'static str| {
+            MediaSegment::builder()
+                .duration(duration)
+                .uri(uri)
+                .build()
+                .unwrap()
+        };
+
+        let playlist = MediaPlaylist::from_segments(vec![
+            segment(Duration::from_secs(8), "segment0.ts"),
+            segment(Duration::from_millis(9600), "segment1.ts"),
+            segment(Duration::from_millis(4500), "segment2.ts"),
+        ])
+        .unwrap();
+
+        assert_eq!(playlist.target_duration, Duration::from_secs(10));
+        assert_eq!(
+            playlist
+                .segments
+                .values()
+                .map(|s| (s.number(), s.uri().as_ref()))
+                .collect::<Vec<_>>(),
+            vec![(0, "segment0.ts"), (1, "segment1.ts"), (2, "segment2.ts")]
+        );
+
+        // a whole number of seconds is not rounded up
+        assert_eq!(
+            MediaPlaylist::from_segments(vec![segment(Duration::from_secs(6), "segment0.ts")])
+                .unwrap()
+                .target_duration,
+            Duration::from_secs(6)
+        );
+
+        assert_eq!(
+            MediaPlaylist::from_segments(vec![])
+                .unwrap()
+                .target_duration,
+            Duration::from_secs(0)
+        );
+    }
+
+    #[test]
+    fn test_duration_stats() {
+        let playlist = MediaPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-TARGETDURATION:10\n",
+            "#EXTINF:4.004,\n",
+            "segment0.ts\n",
+            "#EXTINF:6.006,\n",
+            "segment1.ts\n",
+            "#EXTINF:2.002,\n",
+            "segment2.ts\n",
+        ))
+        .unwrap();
+
+        assert_eq!(
+            playlist.duration_stats(),
+            Some(DurationStats {
+                min: Duration::from_millis(2002),
+                max: Duration::from_millis(6006),
+                mean: Duration::from_millis(4004),
+                count: 3,
+            })
+        );
+
+        // the mean is rounded down to the nanosecond:
+        let playlist = MediaPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-TARGETDURATION:10\n",
+            "#EXTINF:1,\n",
+            "segment0.ts\n",
+            "#EXTINF:1,\n",
+            "segment1.ts\n",
+            "#EXTINF:0,\n",
+            "segment2.ts\n",
+        ))
+        .unwrap();
+
+        assert_eq!(
+            playlist.duration_stats().map(|stats| stats.mean),
+            Some(Duration::from_nanos(666_666_666))
+        );
+
+        assert_eq!(
+            MediaPlaylist::builder()
+                .target_duration(Duration::from_secs(10))
+                .segments(vec![])
+                .build()
+                .unwrap()
+                .duration_stats(),
+            None
+        );
     }
 
-    if has_partial_segment {
-        return Err(Error::custom("Missing URI for the last `MediaSegment`"));
+    #[test]
+    fn test_estimated_segment_sizes() {
+        let playlist = MediaPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-VERSION:4\n",
+            "#EXT-X-TARGETDURATION:10\n",
+            "#EXTINF:10,\n",
+            "segment0.ts\n",
+            "#EXT-X-BITRATE:1500\n",
+            "#EXTINF:10,\n",
+            "segment1.ts\n",
+            "#EXTINF:2.5,\n",
+            "segment2.ts\n",
+            "#EXT-X-BYTERANGE:1000@0\n",
+            "#EXTINF:10,\n",
+            "segment3.ts\n",
+        ))
+        .unwrap();
+
+        assert_eq!(
+            playlist.estimated_segment_sizes().collect::<Vec<_>>(),
+            vec![None, Some(1_875_000), Some(468_750), Some(1000)]
+        );
     }
 
-    builder.unknown(unknown);
-    builder.segments(segments);
-    builder.build().map_err(Error::builder)
-}
+    #[test]
+    fn test_content_eq() {
+        let first = MediaPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-TARGETDURATION:10\n",
+            "#EXT-X-MEDIA-SEQUENCE:10\n",
+            "#EXT-X-DISCONTINUITY-SEQUENCE:2\n",
+            "#EXTINF:10,\n",
+            "segment-a.ts\n",
+            "#EXTINF:10,\n",
+            "segment-b.ts\n",
+        ))
+        .unwrap();
 
-impl FromStr for MediaPlaylist<'static> {
-    type Err = Error;
+        // the same window, that has been shifted
+        let second = MediaPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-TARGETDURATION:10\n",
+            "#EXT-X-MEDIA-SEQUENCE:42\n",
+            "#EXT-X-DISCONTINUITY-SEQUENCE:3\n",
+            "#EXTINF:10,\n",
+            "segment-a.ts\n",
+            "#EXTINF:10,\n",
+            "segment-b.ts\n",
+        ))
+        .unwrap();
 
-    fn from_str(input: &str) -> Result<Self, Self::Err> {
-        Ok(parse_media_playlist(input, &mut Self::builder())?.into_owned())
-    }
-}
+        assert_ne!(first, second);
+        assert!(first.content_eq(&second));
+        assert!(second.content_eq(&first));
+        assert!(first.content_eq(&first));
 
-impl<'a> TryFrom<&'a str> for MediaPlaylist<'a> {
-    type Error = Error;
+        // a different segment
+        let third = MediaPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-TARGETDURATION:10\n",
+            "#EXT-X-MEDIA-SEQUENCE:11\n",
+            "#EXTINF:10,\n",
+            "segment-b.ts\n",
+            "#EXTINF:10,\n",
+            "segment-c.ts\n",
+        ))
+        .unwrap();
 
-    fn try_from(input: &'a str) -> Result<Self, Self::Error> {
-        parse_media_playlist(input, &mut Self::builder())
+        assert!(!first.content_eq(&third));
+
+        // an additional segment
+        let mut fourth = first.clone();
+        fourth
+            .push_segment(
+                MediaSegment::builder()
+                    .duration(Duration::from_secs(10))
+                    .uri("segment-c.ts")
+                    .build()
+                    .unwrap(),
+            )
+            .unwrap();
+
+        assert!(!first.content_eq(&fourth));
+
+        // the structural tags are compared
+        let mut fifth = first.clone();
+        fifth.has_end_list = true;
+
+        assert!(!first.content_eq(&fifth));
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use pretty_assertions::assert_eq;
+    #[test]
+    fn test_parse_with_unknown_cue() {
+        let input = concat!(
+            "#EXTM3U\n",
+            "#EXT-X-TARGETDURATION:10\n",
+            "#EXT-X-DATERANGE:ID=\"ad\",START-DATE=\"2020-01-02T21:55:44Z\",CUE=\"PRE,MIDDLE\"\n",
+            "#EXTINF:10,\n",
+            "segment0.ts\n",
+        );
+
+        assert!(MediaPlaylist::parse_with(input, ParseOptions::strict()).is_err());
+
+        let (playlist, warnings) =
+            MediaPlaylist::parse_with(input, ParseOptions::lenient()).unwrap();
+
+        let cue = playlist.segments[0].date_range.as_ref().unwrap().cue();
+        assert!(cue.map_or(false, |cue| cue.is_pre && !cue.is_once));
+        assert_eq!(
+            warnings,
+            vec![Warning::UnknownCue {
+                value: "MIDDLE".to_string()
+            }]
+        );
+    }
 
     #[test]
-    fn too_large_segment_duration_test() {
-        let playlist = concat!(
+    fn test_parse_with_dangling_uri() {
+        let input = concat!(
             "#EXTM3U\n",
-            "#EXT-X-TARGETDURATION:8\n",
-            "#EXT-X-VERSION:3\n",
-            "#EXTINF:9.009,\n",
-            "http://media.example.com/first.ts\n",
-            "#EXTINF:9.509,\n",
-            "http://media.example.com/second.ts\n",
-            "#EXTINF:3.003,\n",
-            "http://media.example.com/third.ts\n",
-            "#EXT-X-ENDLIST\n"
+            "#EXT-X-TARGETDURATION:10\n",
+            "#EXTINF:10,\n",
+            "segment0.ts\n",
+            "#EXT-X-BITRATE:1500\n",
+            "segment1.ts\n",
         );
 
-        // Error (allowable segment duration = target duration = 8)
-        assert!(MediaPlaylist::try_from(playlist).is_err());
+        assert!(MediaPlaylist::try_from(input).is_err());
+        assert!(MediaPlaylist::parse_with(input, ParseOptions::strict()).is_err());
 
-        // Error (allowable segment duration = 9)
-        assert!(MediaPlaylist::builder()
-            .allowable_excess_duration(Duration::from_secs(1))
-            .parse(playlist)
-            .is_err());
+        let (playlist, warnings) =
+            MediaPlaylist::parse_with(input, ParseOptions::lenient()).unwrap();
 
-        // Ok (allowable segment duration = 10)
         assert_eq!(
+            playlist,
             MediaPlaylist::builder()
-                .allowable_excess_duration(Duration::from_secs(2))
-                .parse(playlist)
-                .unwrap(),
-            MediaPlaylist::builder()
-                .allowable_excess_duration(Duration::from_secs(2))
-                .target_duration(Duration::from_secs(8))
-                .segments(vec![
-                    MediaSegment::builder()
-                        .duration(Duration::from_secs_f64(9.009))
-                        .uri("http://media.example.com/first.ts")
-                        .build()
-                        .unwrap(),
-                    MediaSegment::builder()
-                        .duration(Duration::from_secs_f64(9.509))
-                        .uri("http://media.example.com/second.ts")
-                        .build()
-                        .unwrap(),
-                    MediaSegment::builder()
-                        .duration(Duration::from_secs_f64(3.003))
-                        .uri("http://media.example.com/third.ts")
-                        .build()
-                        .unwrap(),
-                ])
-                .has_end_list(true)
+                .target_duration(Duration::from_secs(10))
+                .segments(vec![MediaSegment::builder()
+                    .duration(Duration::from_secs(10))
+                    .uri("segment0.ts")
+                    .build()
+                    .unwrap()])
                 .build()
                 .unwrap()
         );
+        assert_eq!(
+            warnings,
+            vec![Warning::IncompleteSegment {
+                uri: "segment1.ts".to_string()
+            }]
+        );
+
+        // only the last segment may be incomplete
+        assert!(MediaPlaylist::parse_with(
+            concat!(
+                "#EXTM3U\n",
+                "#EXT-X-TARGETDURATION:10\n",
+                "segment0.ts\n",
+                "#EXTINF:10,\n",
+                "segment1.ts\n",
+            ),
+            ParseOptions::lenient()
+        )
+        .is_err());
+
+        // a playlist with an `#EXT-X-ENDLIST` tag is complete
+        assert!(MediaPlaylist::parse_with(
+            concat!(
+                "#EXTM3U\n",
+                "#EXT-X-TARGETDURATION:10\n",
+                "#EXTINF:10,\n",
+                "segment0.ts\n",
+                "segment1.ts\n",
+                "#EXT-X-ENDLIST\n",
+            ),
+            ParseOptions::lenient()
+        )
+        .is_err());
+
+        // a complete playlist has no warnings
+        let (_, warnings) = MediaPlaylist::parse_with(
+            concat!(
+                "#EXTM3U\n",
+                "#EXT-X-TARGETDURATION:10\n",
+                "#EXTINF:10,\n",
+                "segment0.ts\n",
+            ),
+            ParseOptions::lenient(),
+        )
+        .unwrap();
+
+        assert_eq!(warnings, vec![]);
     }
 
     #[test]
-    fn test_segment_number_simple() {
-        let playlist = MediaPlaylist::builder()
-            .allowable_excess_duration(Duration::from_secs(2))
-            .target_duration(Duration::from_secs(8))
-            .segments(vec![
-                MediaSegment::builder()
-                    .duration(Duration::from_secs_f64(9.009))
-                    .uri("http://media.example.com/first.ts")
-                    .build()
-                    .unwrap(),
-                MediaSegment::builder()
-                    .duration(Duration::from_secs_f64(9.509))
-                    .uri("http://media.example.com/second.ts")
-                    .build()
-                    .unwrap(),
-                MediaSegment::builder()
-                    .duration(Duration::from_secs_f64(3.003))
-                    .uri("http://media.example.com/third.ts")
-                    .build()
-                    .unwrap(),
-            ])
+    fn test_sliding_window() {
+        let segment = |uri: &'static str| {
+            MediaSegment::builder()
+                .duration(Duration::from_secs(10))
+                .uri(uri)
+                .build()
+                .unwrap()
+        };
+
+        let mut playlist = MediaPlaylist::builder()
+            .target_duration(Duration::from_secs(10))
+            .media_sequence(5)
+            .segments(vec![segment("5.ts"), segment("6.ts"), segment("7.ts")])
             .build()
             .unwrap();
 
-        let mut segments = playlist.segments.into_iter().map(|(k, v)| (k, v.number));
-        assert_eq!(segments.next(), Some((0, 0)));
-        assert_eq!(segments.next(), Some((1, 1)));
-        assert_eq!(segments.next(), Some((2, 2)));
-        assert_eq!(segments.next(), None);
+        playlist.remove_oldest(2).unwrap();
+        playlist.push_segment(segment("8.ts")).unwrap();
+
+        assert_eq!(playlist.media_sequence, 7);
+        assert_eq!(playlist.discontinuity_sequence, 0);
+        assert_eq!(
+            playlist
+                .segments
+                .values()
+                .map(|s| (s.number(), s.uri().to_string()))
+                .collect::<Vec<_>>(),
+            vec![(7, "7.ts".to_string()), (8, "8.ts".to_string())]
+        );
+
+        assert_eq!(
+            playlist.to_string(),
+            concat!(
+                "#EXTM3U\n",
+                "#EXT-X-TARGETDURATION:10\n",
+                "#EXT-X-MEDIA-SEQUENCE:7\n",
+                "#EXTINF:10,\n",
+                "7.ts\n",
+                "#EXTINF:10,\n",
+                "8.ts\n",
+            )
+        );
+
+        // removing a discontinuity increments the discontinuity sequence
+        let mut discontinuity = segment("9.ts");
+        discontinuity.has_discontinuity = true;
+        playlist.push_segment(discontinuity).unwrap();
+
+        playlist.remove_oldest(3).unwrap();
+        assert_eq!(playlist.media_sequence, 10);
+        assert_eq!(playlist.discontinuity_sequence, 1);
+        assert_eq!(playlist.segments.num_elements(), 0);
+
+        // removing more segments than available only removes all segments
+        playlist.push_segment(segment("10.ts")).unwrap();
+        playlist.remove_oldest(5).unwrap();
+        assert_eq!(playlist.media_sequence, 11);
     }
 
     #[test]
-    fn test_segment_number_sequence() {
-        let playlist = MediaPlaylist::builder()
-            .target_duration(Duration::from_secs(8))
-            .media_sequence(2680)
-            .segments(vec![
-                MediaSegment::builder()
-                    .duration(Duration::from_secs_f64(7.975))
-                    .uri("https://priv.example.com/fileSequence2680.ts")
-                    .build()
-                    .unwrap(),
-                MediaSegment::builder()
-                    .duration(Duration::from_secs_f64(7.941))
-                    .uri("https://priv.example.com/fileSequence2681.ts")
-                    .build()
-                    .unwrap(),
-                MediaSegment::builder()
-                    .duration(Duration::from_secs_f64(7.975))
-                    .uri("https://priv.example.com/fileSequence2682.ts")
-                    .build()
-                    .unwrap(),
-            ])
+    fn test_empty_playlist() {
+        let playlist = "";
+        assert!(MediaPlaylist::try_from(playlist).is_err());
+    }
+
+    #[test]
+    fn test_normalize_uris() {
+        let input = concat!(
+            "#EXTM3U\n",
+            "#EXT-X-TARGETDURATION:10\n",
+            "#EXT-X-DEFINE:NAME=\"host\",VALUE=\"https://www.example.com\"\n",
+            "#EXT-X-DEFINE:IMPORT=\"token\"\n",
+            "#EXT-X-KEY:METHOD=AES-128,URI=\"{$host}/key?token={$token}\"\n",
+            "#EXT-X-MAP:URI=\"{$host}/init.mp4\"\n",
+            "#EXTINF:10.0,\n",
+            "{$host}/segment.ts\n",
+        );
+
+        let mut playlist = MediaPlaylist::try_from(input).unwrap();
+
+        assert_eq!(
+            playlist.defines,
+            vec![
+                ExtXDefine::new("host", "https://www.example.com"),
+                ExtXDefine::import("token"),
+            ]
+        );
+
+        // the imported variable is missing
+        assert!(playlist.clone().normalize_uris(&[]).is_err());
+
+        playlist
+            .normalize_uris(&[ExtXDefine::new("token", "secret")])
+            .unwrap();
+
+        let segment = &playlist.segments[0];
+
+        assert_eq!(segment.uri(), "https://www.example.com/segment.ts");
+        assert_eq!(
+            segment.keys[0].as_ref().unwrap().uri(),
+            "https://www.example.com/key?token=secret"
+        );
+
+        let map = segment.map.as_ref().unwrap();
+
+        assert_eq!(map.uri(), "https://www.example.com/init.mp4");
+        assert_eq!(
+            map.keys[0].as_ref().unwrap().uri(),
+            "https://www.example.com/key?token=secret"
+        );
+
+        // a reference to an undefined variable is an error
+        let mut playlist = MediaPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-TARGETDURATION:10\n",
+            "#EXTINF:10.0,\n",
+            "{$host}/segment.ts\n",
+        ))
+        .unwrap();
+
+        assert!(playlist.normalize_uris(&[]).is_err());
+
+        // nothing is replaced, if a later `URI` is invalid
+        let mut playlist = MediaPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-TARGETDURATION:10\n",
+            "#EXT-X-DEFINE:NAME=\"host\",VALUE=\"https://www.example.com\"\n",
+            "#EXTINF:10.0,\n",
+            "{$host}/segment0.ts\n",
+            "#EXTINF:10.0,\n",
+            "{$undefined}/segment1.ts\n",
+        ))
+        .unwrap();
+        let expected = playlist.clone();
+
+        assert!(playlist.normalize_uris(&[]).is_err());
+        assert_eq!(playlist, expected);
+        assert_eq!(playlist.segments[0].uri(), "{$host}/segment0.ts");
+    }
+
+    #[test]
+    fn test_to_bytes() {
+        let playlist = MediaPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-TARGETDURATION:10\n",
+            "#EXTINF:10,Épisode 1\n",
+            "segment0.ts\n",
+        ))
+        .unwrap();
+
+        assert_eq!(playlist.to_bytes(), playlist.to_string().into_bytes());
+    }
+
+    #[test]
+    fn test_gap() {
+        let input = concat!(
+            "#EXTM3U\n",
+            "#EXT-X-TARGETDURATION:10\n",
+            "#EXTINF:10,\n",
+            "segment0.ts\n",
+            "#EXT-X-GAP\n",
+            "#EXTINF:10,\n",
+            "segment1.ts\n",
+            "#EXTINF:10,\n",
+            "segment2.ts\n",
+        );
+
+        let playlist = MediaPlaylist::try_from(input).unwrap();
+
+        assert_eq!(
+            playlist
+                .segments
+                .values()
+                .map(|segment| segment.is_gap)
+                .collect::<Vec<_>>(),
+            vec![false, true, false]
+        );
+        assert_eq!(playlist.to_string(), input.to_string());
+    }
+
+    #[test]
+    fn test_media_sequence_overflow() {
+        let input = |media_sequence: usize| {
+            format!(
+                concat!(
+                    "#EXTM3U\n",
+                    "#EXT-X-TARGETDURATION:10\n",
+                    "#EXT-X-MEDIA-SEQUENCE:{}\n",
+                    "#EXTINF:10,\n",
+                    "segment0.ts\n",
+                    "#EXTINF:10,\n",
+                    "segment1.ts\n",
+                ),
+                media_sequence
+            )
+        };
+
+        // the number of the second segment would overflow:
+        assert!(MediaPlaylist::try_from(input(usize::MAX).as_str()).is_err());
+
+        let mut playlist = MediaPlaylist::try_from(input(usize::MAX - 1).as_str())
+            .unwrap()
+            .into_owned();
+
+        let numbers = |playlist: &MediaPlaylist<'_>| {
+            playlist
+                .segments
+                .values()
+                .map(MediaSegment::number)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(numbers(&playlist), vec![usize::MAX - 1, usize::MAX]);
+
+        let segment = MediaSegment::builder()
+            .duration(Duration::from_secs(10))
+            .uri("segment2.ts")
             .build()
             .unwrap();
-        let mut segments = playlist.segments.into_iter().map(|(k, v)| (k, v.number));
-        assert_eq!(segments.next(), Some((0, 2680)));
-        assert_eq!(segments.next(), Some((1, 2681)));
-        assert_eq!(segments.next(), Some((2, 2682)));
-        assert_eq!(segments.next(), None);
+
+        // there is no number left for another segment:
+        assert!(playlist.push_segment(segment).is_err());
+        assert_eq!(numbers(&playlist), vec![usize::MAX - 1, usize::MAX]);
+
+        playlist.remove_oldest(1).unwrap();
+        assert_eq!(playlist.media_sequence, usize::MAX);
+
+        // the media sequence can not be incremented beyond the last segment:
+        assert!(playlist.remove_oldest(1).is_err());
+        assert_eq!(playlist.media_sequence, usize::MAX);
+        assert_eq!(numbers(&playlist), vec![usize::MAX]);
     }
 
     #[test]
-    fn test_empty_playlist() {
-        let playlist = "";
-        assert!(MediaPlaylist::try_from(playlist).is_err());
+    fn test_part_byte_range_continuation() {
+        let playlist = MediaPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-TARGETDURATION:4\n",
+            "#EXT-X-SERVER-CONTROL:PART-HOLD-BACK=3\n",
+            "#EXT-X-PART-INF:PART-TARGET=1\n",
+            "#EXT-X-PART:DURATION=1,URI=\"segment.mp4\",BYTERANGE=\"100\"\n",
+            "#EXT-X-PART:DURATION=1,URI=\"segment.mp4\",BYTERANGE=\"150\"\n",
+            "#EXT-X-PART:DURATION=1,URI=\"segment.mp4\",BYTERANGE=\"50\"\n",
+            "#EXTINF:3,\n",
+            "segment.mp4\n",
+            "#EXT-X-PART:DURATION=1,URI=\"segment1.mp4\",BYTERANGE=\"100\"\n",
+            "#EXT-X-PART:DURATION=1,URI=\"segment1.mp4\",BYTERANGE=\"80\"\n",
+        ))
+        .unwrap();
+
+        let ranges = playlist.segments[0]
+            .parts
+            .iter()
+            .map(|part| part.byte_range())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            ranges,
+            vec![
+                Some(ByteRange::from(0..100)),
+                Some(ByteRange::from(100..250)),
+                Some(ByteRange::from(250..300)),
+            ]
+        );
+
+        // the parts of the incomplete segment are resolved as well:
+        let ranges = playlist
+            .incomplete_parts
+            .iter()
+            .map(|part| part.byte_range())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            ranges,
+            vec![
+                Some(ByteRange::from(0..100)),
+                Some(ByteRange::from(100..180)),
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "url")]
+    fn test_validate_uris() {
+        let playlist = MediaPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-TARGETDURATION:10\n",
+            "#EXT-X-KEY:METHOD=AES-128,URI=\"https://example.com/key\"\n",
+            "#EXT-X-MAP:URI=\"init.mp4\"\n",
+            "#EXTINF:10.0,\n",
+            "segment0.ts\n",
+            "#EXTINF:10.0,\n",
+            "http://[invalid/segment1.ts\n",
+            "#EXTINF:10.0,\n",
+            "/video/segment2.ts\n",
+        ))
+        .unwrap();
+
+        let base = Url::parse("https://www.example.com/video/index.m3u8").unwrap();
+
+        assert_eq!(
+            playlist.validate_uris(&base),
+            vec![(
+                "http://[invalid/segment1.ts",
+                ParseError::InvalidIpv6Address
+            )]
+        );
+    }
+
+    #[test]
+    fn test_rewrite_key_uris() {
+        let mut playlist = MediaPlaylist::try_from(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-TARGETDURATION:10\n",
+            "#EXT-X-KEY:METHOD=AES-128,URI=\"https://example.com/a\"\n",
+            "#EXT-X-MAP:URI=\"init.mp4\"\n",
+            "#EXTINF:10.0,\n",
+            "segment0.ts\n",
+            "#EXTINF:10.0,\n",
+            "segment1.ts\n",
+            "#EXT-X-KEY:METHOD=NONE\n",
+            "#EXTINF:10.0,\n",
+            "segment2.ts\n",
+            "#EXT-X-KEY:METHOD=AES-128,URI=\"https://example.com/b\"\n",
+            "#EXTINF:10.0,\n",
+            "segment3.ts\n",
+        ))
+        .unwrap();
+
+        let mut calls = vec![];
+
+        playlist.rewrite_key_uris(|uri| {
+            calls.push(uri.to_string());
+            uri.replace("example.com", "proxy.example.com")
+        });
+
+        // the function is only called once for every distinct uri
+        assert_eq!(
+            calls,
+            vec![
+                "https://example.com/a".to_string(),
+                "https://example.com/b".to_string()
+            ]
+        );
+
+        let key_uris = |segment: &MediaSegment<'_>| {
+            segment
+                .keys
+                .iter()
+                .filter_map(|key| key.as_ref().map(|key| key.uri().to_string()))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            key_uris(&playlist.segments[0]),
+            vec!["https://proxy.example.com/a"]
+        );
+        assert_eq!(
+            key_uris(&playlist.segments[1]),
+            vec!["https://proxy.example.com/a"]
+        );
+        assert_eq!(key_uris(&playlist.segments[2]), Vec::<String>::new());
+        assert_eq!(
+            key_uris(&playlist.segments[3]),
+            vec!["https://proxy.example.com/b"]
+        );
+
+        assert_eq!(
+            playlist.segments[0].map.as_ref().unwrap().keys[0]
+                .as_ref()
+                .unwrap()
+                .uri(),
+            "https://proxy.example.com/a"
+        );
     }
 }
@@ -3,11 +3,14 @@ use std::fmt;
 
 use derive_builder::Builder;
 use shorthand::ShortHand;
+#[cfg(feature = "url")]
+use url::{ParseError, Url};
 
 use crate::tags::{
-    ExtInf, ExtXByteRange, ExtXDateRange, ExtXDiscontinuity, ExtXKey, ExtXMap, ExtXProgramDateTime,
+    ExtInf, ExtXBitrate, ExtXByteRange, ExtXDateRange, ExtXDiscontinuity, ExtXGap, ExtXKey,
+    ExtXMap, ExtXPart, ExtXProgramDateTime,
 };
-use crate::types::{DecryptionKey, ProtocolVersion};
+use crate::types::{ByteRange, DecryptionKey, ProtocolVersion};
 use crate::{Decryptable, RequiredVersion};
 
 /// A video is split into smaller chunks called [`MediaSegment`]s, which are
@@ -104,6 +107,18 @@ pub struct MediaSegment<'a> {
     /// This field is optional.
     #[builder(default, setter(into))]
     pub byte_range: Option<ExtXByteRange>,
+    /// This field declares the approximate bitrate of this and the following
+    /// [`MediaSegment`]s.
+    ///
+    /// ## Note
+    ///
+    /// This field is optional and only contains the bitrate, that has been
+    /// declared right before this segment. The bitrate, which applies to a
+    /// segment can be obtained with [`MediaPlaylist::bitrates`].
+    ///
+    /// [`MediaPlaylist::bitrates`]: crate::MediaPlaylist::bitrates
+    #[builder(default, setter(into))]
+    pub bitrate: Option<ExtXBitrate>,
     /// This field associates a date-range (i.e., a range of time defined by a
     /// starting and ending date) with a set of attribute/value pairs.
     ///
@@ -128,6 +143,14 @@ pub struct MediaSegment<'a> {
     /// - encoding sequence
     #[builder(default)]
     pub has_discontinuity: bool,
+    /// Indicates, that the resource of this [`MediaSegment`] is missing and
+    /// should not be loaded by clients (`#EXT-X-GAP`).
+    ///
+    /// ## Note
+    ///
+    /// This field is optional and defaults to `false`.
+    #[builder(default)]
+    pub is_gap: bool,
     /// This field associates the first sample of a media segment with an
     /// absolute date and/or time.
     ///
@@ -136,6 +159,21 @@ pub struct MediaSegment<'a> {
     /// This field is optional.
     #[builder(default)]
     pub program_date_time: Option<ExtXProgramDateTime<'a>>,
+    /// The partial segments ([`ExtXPart`]), which make up this
+    /// [`MediaSegment`].
+    ///
+    /// ## Note
+    ///
+    /// This field is optional. If it is not empty, the [`MediaPlaylist`] must
+    /// have a [`MediaPlaylist::part_target`] and a
+    /// [`ExtXServerControl::part_hold_back`].
+    ///
+    /// [`MediaPlaylist`]: crate::MediaPlaylist
+    /// [`MediaPlaylist::part_target`]: crate::MediaPlaylist::part_target
+    /// [`ExtXServerControl::part_hold_back`]:
+    /// crate::tags::ExtXServerControl::part_hold_back
+    #[builder(default, setter(into))]
+    pub parts: Vec<ExtXPart<'a>>,
     /// This field indicates the duration of a media segment.
     ///
     /// ## Note
@@ -176,6 +214,142 @@ impl<'a> MediaSegment<'a> {
     #[inline]
     pub fn builder() -> MediaSegmentBuilder<'static> { MediaSegmentBuilder::default() }
 
+    /// Returns the percent-decoded [`MediaSegment::uri`], which can be used
+    /// for displaying the `URI`.
+    ///
+    /// The [`MediaSegment::uri`] itself is not changed, so the segment will
+    /// still be written with the percent-encoded `URI`. Invalid UTF-8
+    /// sequences are replaced with `U+FFFD`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::MediaSegment;
+    /// use std::time::Duration;
+    ///
+    /// let segment = MediaSegment::builder()
+    ///     .duration(Duration::from_secs(4))
+    ///     .uri("my%20segment.ts")
+    ///     .build()?;
+    ///
+    /// assert_eq!(segment.decoded_uri(), "my segment.ts");
+    /// assert_eq!(segment.uri(), "my%20segment.ts");
+    /// # Ok::<(), String>(())
+    /// ```
+    #[cfg(feature = "percent-encoding")]
+    #[must_use]
+    pub fn decoded_uri(&self) -> Cow<'_, str> {
+        percent_encoding::percent_decode_str(&self.uri).decode_utf8_lossy()
+    }
+
+    /// Resolves the [`MediaSegment::uri`] against the `base` url, which
+    /// should be the url of the [`MediaPlaylist`].
+    ///
+    /// `URI`s, which are already absolute, replace the `base` url and are
+    /// normalized by [`Url::join`] (for example `HTTP://Example.com/a/../b`
+    /// becomes `http://example.com/b`).
+    ///
+    /// # Errors
+    ///
+    /// This function fails, if the `URI` can not be resolved into a valid url.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::MediaSegment;
+    /// use std::time::Duration;
+    /// use url::Url;
+    ///
+    /// let segment = MediaSegment::builder()
+    ///     .duration(Duration::from_secs(4))
+    ///     .uri("segment0.ts")
+    ///     .build()?;
+    ///
+    /// let base = Url::parse("https://www.example.com/video/index.m3u8").unwrap();
+    ///
+    /// assert_eq!(
+    ///     segment.resolve_uri(&base).unwrap().as_str(),
+    ///     "https://www.example.com/video/segment0.ts"
+    /// );
+    /// # Ok::<(), String>(())
+    /// ```
+    ///
+    /// [`MediaPlaylist`]: crate::MediaPlaylist
+    #[cfg(feature = "url")]
+    pub fn resolve_uri(&self, base: &Url) -> Result<Url, ParseError> { base.join(&self.uri) }
+
+    /// Returns the [`ByteRange`], that covers both the media initialization
+    /// section ([`MediaSegment::map`]) and this [`MediaSegment`], so that both
+    /// can be fetched with a single request.
+    ///
+    /// This is useful for CMAF fMP4 streams, where the initialization section
+    /// and the media segments are stored in a single file.
+    ///
+    /// `None` is returned, if the [`MediaSegment`] has no [`ExtXMap`], if they
+    /// do not share the same `URI`, if one of them has no range with an
+    /// explicit start or if the initialization section does not precede the
+    /// [`MediaSegment`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::MediaSegment;
+    /// use hls_m3u8::tags::ExtXMap;
+    /// use hls_m3u8::types::ByteRange;
+    /// use std::time::Duration;
+    ///
+    /// let segment = MediaSegment::builder()
+    ///     .map(ExtXMap::with_range("stream.mp4", 0..720))
+    ///     .byte_range(720..50_000)
+    ///     .duration(Duration::from_secs(4))
+    ///     .uri("stream.mp4")
+    ///     .build()?;
+    ///
+    /// assert_eq!(segment.init_range(), Some(ByteRange::from(0..50_000)));
+    /// # Ok::<(), String>(())
+    /// ```
+    ///
+    /// ## Note
+    ///
+    /// If there is a gap between the initialization section and the
+    /// [`MediaSegment`], the returned range includes the bytes of the gap.
+    #[must_use]
+    pub fn init_range(&self) -> Option<ByteRange> {
+        let map = self.map.as_ref()?;
+
+        if *map.uri() != self.uri {
+            return None;
+        }
+
+        let init_range = map.range()?;
+        let segment_range = self.byte_range?;
+
+        let init_start = init_range.start()?;
+        let segment_start = segment_range.start()?;
+
+        if init_range.end() > segment_start {
+            return None;
+        }
+
+        Some(ByteRange::from(init_start..segment_range.end()))
+    }
+
+    /// Returns `true`, if both [`MediaSegment`]s are equal, without comparing
+    /// their [`MediaSegment::number`]s.
+    pub(crate) fn eq_ignoring_number(&self, other: &Self) -> bool {
+        self.keys == other.keys
+            && self.map == other.map
+            && self.byte_range == other.byte_range
+            && self.bitrate == other.bitrate
+            && self.date_range == other.date_range
+            && self.has_discontinuity == other.has_discontinuity
+            && self.is_gap == other.is_gap
+            && self.program_date_time == other.program_date_time
+            && self.parts == other.parts
+            && self.duration == other.duration
+            && self.uri == other.uri
+    }
+
     /// Makes the struct independent of its lifetime, by taking ownership of all
     /// internal [`Cow`]s.
     ///
@@ -191,9 +365,12 @@ impl<'a> MediaSegment<'a> {
             keys: self.keys.into_iter().map(|k| k.into_owned()).collect(),
             map: self.map.map(|v| v.into_owned()),
             byte_range: self.byte_range,
+            bitrate: self.bitrate,
             date_range: self.date_range.map(|v| v.into_owned()),
             has_discontinuity: self.has_discontinuity,
+            is_gap: self.is_gap,
             program_date_time: self.program_date_time.map(|v| v.into_owned()),
+            parts: self.parts.into_iter().map(|v| v.into_owned()).collect(),
             duration: self.duration.into_owned(),
             uri: Cow::Owned(self.uri.into_owned()),
         }
@@ -201,6 +378,17 @@ impl<'a> MediaSegment<'a> {
 }
 
 impl<'a> MediaSegmentBuilder<'a> {
+    /// Pushes an [`ExtXPart`] tag.
+    pub fn push_part<VALUE: Into<ExtXPart<'a>>>(&mut self, value: VALUE) -> &mut Self {
+        if let Some(parts) = &mut self.parts {
+            parts.push(value.into());
+        } else {
+            self.parts = Some(vec![value.into()]);
+        }
+
+        self
+    }
+
     /// Pushes an [`ExtXKey`] tag.
     pub fn push_key<VALUE: Into<ExtXKey<'a>>>(&mut self, value: VALUE) -> &mut Self {
         if let Some(keys) = &mut self.keys {
@@ -237,6 +425,10 @@ impl<'a> fmt::Display for MediaSegment<'a> {
             writeln!(f, "{}", value)?;
         }
 
+        if let Some(value) = &self.bitrate {
+            writeln!(f, "{}", value)?;
+        }
+
         if let Some(value) = &self.date_range {
             writeln!(f, "{}", value)?;
         }
@@ -249,6 +441,14 @@ impl<'a> fmt::Display for MediaSegment<'a> {
             writeln!(f, "{}", value)?;
         }
 
+        if self.is_gap {
+            writeln!(f, "{}", ExtXGap)?;
+        }
+
+        for value in &self.parts {
+            writeln!(f, "{}", value)?;
+        }
+
         writeln!(f, "{}", self.duration)?;
         writeln!(f, "{}", self.uri)?;
         Ok(())
@@ -261,6 +461,7 @@ impl<'a> RequiredVersion for MediaSegment<'a> {
             self.keys,
             self.map,
             self.byte_range,
+            self.bitrate,
             self.date_range,
             {
                 if self.has_discontinuity {
@@ -270,6 +471,14 @@ impl<'a> RequiredVersion for MediaSegment<'a> {
                 }
             },
             self.program_date_time,
+            {
+                if self.is_gap {
+                    Some(ExtXGap)
+                } else {
+                    None
+                }
+            },
+            self.parts,
             self.duration
         ]
     }
@@ -310,4 +519,170 @@ mod tests {
             .to_string()
         );
     }
+
+    #[test]
+    fn test_builder() {
+        let segment = MediaSegment::builder()
+            .duration(Duration::from_secs(4))
+            .uri("segment.ts")
+            .build()
+            .unwrap();
+
+        assert_eq!(segment.duration, ExtInf::new(Duration::from_secs(4)));
+        assert_eq!(segment.uri(), "segment.ts");
+        assert_eq!(segment.keys, vec![]);
+        assert_eq!(segment.map, None);
+        assert_eq!(segment.byte_range, None);
+        assert_eq!(segment.program_date_time, None);
+        assert!(!segment.has_discontinuity);
+
+        // the uri is required
+        assert!(MediaSegment::builder()
+            .duration(Duration::from_secs(4))
+            .build()
+            .is_err());
+
+        // the duration is required
+        assert!(MediaSegment::builder().uri("segment.ts").build().is_err());
+    }
+
+    #[test]
+    fn test_display_gap() {
+        assert_eq!(
+            MediaSegment::builder()
+                .is_gap(true)
+                .duration(ExtInf::new(Duration::from_secs(4)))
+                .uri("missing.ts")
+                .build()
+                .unwrap()
+                .to_string(),
+            "#EXT-X-GAP\n#EXTINF:4,\nmissing.ts\n".to_string()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "percent-encoding")]
+    fn test_decoded_uri() {
+        let segment = MediaSegment::builder()
+            .duration(Duration::from_secs(4))
+            .uri("https://www.example.com/my%20segment%2B1.ts")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            segment.decoded_uri(),
+            "https://www.example.com/my segment+1.ts"
+        );
+
+        // the stored uri is not changed:
+        assert_eq!(segment.uri(), "https://www.example.com/my%20segment%2B1.ts");
+        assert_eq!(
+            segment.to_string(),
+            "#EXTINF:4,\nhttps://www.example.com/my%20segment%2B1.ts\n".to_string()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "url")]
+    fn test_resolve_uri() {
+        let base = Url::parse("https://cdn.example.com/path/stream.m3u8").unwrap();
+        let resolve = |uri: &str| {
+            MediaSegment::builder()
+                .duration(Duration::from_secs(4))
+                .uri(uri.to_string())
+                .build()
+                .unwrap()
+                .resolve_uri(&base)
+                .unwrap()
+                .to_string()
+        };
+
+        // relative path:
+        assert_eq!(
+            resolve("720p/segment0.ts"),
+            "https://cdn.example.com/path/720p/segment0.ts".to_string()
+        );
+        // root-relative path:
+        assert_eq!(
+            resolve("/media/segment0.ts"),
+            "https://cdn.example.com/media/segment0.ts".to_string()
+        );
+        // absolute uri:
+        assert_eq!(
+            resolve("https://other.example.com/segment0.ts?token=1"),
+            "https://other.example.com/segment0.ts?token=1".to_string()
+        );
+        // absolute uris are normalized:
+        assert_eq!(
+            resolve("HTTP://Other.example.com/a/../segment0.ts"),
+            "http://other.example.com/segment0.ts".to_string()
+        );
+
+        let key = DecryptionKey::new(crate::types::EncryptionMethod::Aes128, "../keys/key0");
+        assert_eq!(
+            key.resolve_uri(&base).unwrap().as_str(),
+            "https://cdn.example.com/keys/key0"
+        );
+
+        let map = ExtXMap::new("init.mp4");
+        assert_eq!(
+            map.resolve_uri(&base).unwrap().as_str(),
+            "https://cdn.example.com/path/init.mp4"
+        );
+
+        assert!(MediaSegment::builder()
+            .duration(Duration::from_secs(4))
+            .uri("http://[::1/segment0.ts")
+            .build()
+            .unwrap()
+            .resolve_uri(&base)
+            .is_err());
+    }
+
+    #[test]
+    fn test_init_range() {
+        // a single file cmaf layout, where the init section is directly followed by
+        // the first segment:
+        let segment = MediaSegment::builder()
+            .map(ExtXMap::with_range("stream.mp4", 0..720))
+            .byte_range(720..50_720)
+            .duration(Duration::from_secs(4))
+            .uri("stream.mp4")
+            .build()
+            .unwrap();
+
+        assert_eq!(segment.init_range(), Some(ByteRange::from(0..50_720)));
+
+        // the init section is stored in a different file
+        let segment = MediaSegment::builder()
+            .map(ExtXMap::with_range("init.mp4", 0..720))
+            .byte_range(720..50_720)
+            .duration(Duration::from_secs(4))
+            .uri("stream.mp4")
+            .build()
+            .unwrap();
+
+        assert_eq!(segment.init_range(), None);
+
+        // the init section does not precede the segment
+        let segment = MediaSegment::builder()
+            .map(ExtXMap::with_range("stream.mp4", 50_720..51_440))
+            .byte_range(720..50_720)
+            .duration(Duration::from_secs(4))
+            .uri("stream.mp4")
+            .build()
+            .unwrap();
+
+        assert_eq!(segment.init_range(), None);
+
+        // no byte ranges
+        let segment = MediaSegment::builder()
+            .map(ExtXMap::new("stream.mp4"))
+            .duration(Duration::from_secs(4))
+            .uri("stream.mp4")
+            .build()
+            .unwrap();
+
+        assert_eq!(segment.init_range(), None);
+    }
 }
@@ -0,0 +1,79 @@
+/// Configures how a playlist is parsed.
+///
+/// # Example
+///
+/// ```
+/// use hls_m3u8::ParseOptions;
+///
+/// let options = ParseOptions::lenient();
+///
+/// assert!(options.is_lenient);
+/// assert_eq!(ParseOptions::default(), ParseOptions::strict());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct ParseOptions {
+    /// If `true`, some violations of the specification, which are commonly
+    /// found in the wild, are tolerated and reported as a [`Warning`] instead
+    /// of an error.
+    ///
+    /// ## Note
+    ///
+    /// This field defaults to `false`.
+    ///
+    /// [`Warning`]: crate::Warning
+    pub is_lenient: bool,
+    /// If `true`, the lines from which each [`MediaSegment`] has been parsed
+    /// are recorded and can be looked up with [`MediaPlaylist::source_line`].
+    ///
+    /// ## Note
+    ///
+    /// This field defaults to `false`.
+    ///
+    /// [`MediaSegment`]: crate::MediaSegment
+    /// [`MediaPlaylist::source_line`]: crate::MediaPlaylist::source_line
+    pub retain_source_lines: bool,
+}
+
+impl ParseOptions {
+    /// Returns the [`ParseOptions`], which reject any playlist, that violates
+    /// the specification.
+    #[must_use]
+    pub const fn strict() -> Self {
+        Self {
+            is_lenient: false,
+            retain_source_lines: false,
+        }
+    }
+
+    /// Returns the [`ParseOptions`], which tolerate common violations of the
+    /// specification.
+    #[must_use]
+    pub const fn lenient() -> Self {
+        Self {
+            is_lenient: true,
+            retain_source_lines: false,
+        }
+    }
+
+    /// Returns the same [`ParseOptions`], but with
+    /// [`ParseOptions::retain_source_lines`] enabled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hls_m3u8::ParseOptions;
+    ///
+    /// let options = ParseOptions::lenient().with_source_lines();
+    ///
+    /// assert!(options.is_lenient);
+    /// assert!(options.retain_source_lines);
+    /// ```
+    #[must_use]
+    pub const fn with_source_lines(self) -> Self {
+        Self {
+            retain_source_lines: true,
+            ..self
+        }
+    }
+}
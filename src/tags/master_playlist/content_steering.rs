@@ -0,0 +1,187 @@
+use std::borrow::Cow;
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::attribute::AttributePairs;
+use crate::types::ProtocolVersion;
+use crate::utils::{quote, tag, unquote};
+use crate::{Error, RequiredVersion};
+
+/// Allows a server to provide a steering manifest, which tells the client
+/// in which order the pathways (for example different CDNs) of a
+/// [`MasterPlaylist`] should be used.
+///
+/// The [`VariantStream`]s of a pathway are identified by their
+/// [`StreamData::pathway_id`].
+///
+/// # Example
+///
+/// ```
+/// # use hls_m3u8::tags::ExtXContentSteering;
+/// let mut content_steering = ExtXContentSteering::new("https://example.com/manifest.json");
+/// content_steering.pathway_id = Some("CDN-A".into());
+///
+/// assert_eq!(
+///     content_steering.to_string(),
+///     concat!(
+///         "#EXT-X-CONTENT-STEERING:",
+///         "SERVER-URI=\"https://example.com/manifest.json\",PATHWAY-ID=\"CDN-A\""
+///     )
+///     .to_string()
+/// );
+/// ```
+///
+/// [`MasterPlaylist`]: crate::MasterPlaylist
+/// [`VariantStream`]: crate::tags::VariantStream
+/// [`StreamData::pathway_id`]: crate::types::StreamData::pathway_id
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ExtXContentSteering<'a> {
+    /// The `URI` of the steering manifest.
+    ///
+    /// ## Note
+    ///
+    /// This field is required.
+    pub server_uri: Cow<'a, str>,
+    /// The pathway, which should be used until the steering manifest has
+    /// been obtained.
+    ///
+    /// ## Note
+    ///
+    /// This field is optional.
+    pub pathway_id: Option<Cow<'a, str>>,
+}
+
+impl<'a> ExtXContentSteering<'a> {
+    pub(crate) const PREFIX: &'static str = "#EXT-X-CONTENT-STEERING:";
+
+    /// Makes a new [`ExtXContentSteering`] tag.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::tags::ExtXContentSteering;
+    /// let content_steering = ExtXContentSteering::new("https://example.com/manifest.json");
+    /// ```
+    #[must_use]
+    pub fn new<T: Into<Cow<'a, str>>>(server_uri: T) -> Self {
+        Self {
+            server_uri: server_uri.into(),
+            pathway_id: None,
+        }
+    }
+
+    /// Makes the struct independent of its lifetime, by taking ownership of all
+    /// internal [`Cow`]s.
+    ///
+    /// # Note
+    ///
+    /// This is a relatively expensive operation.
+    #[must_use]
+    pub fn into_owned(self) -> ExtXContentSteering<'static> {
+        ExtXContentSteering {
+            server_uri: Cow::Owned(self.server_uri.into_owned()),
+            pathway_id: self.pathway_id.map(|v| Cow::Owned(v.into_owned())),
+        }
+    }
+}
+
+/// This tag requires [`ProtocolVersion::V1`].
+impl<'a> RequiredVersion for ExtXContentSteering<'a> {
+    fn required_version(&self) -> ProtocolVersion { ProtocolVersion::V1 }
+}
+
+impl<'a> fmt::Display for ExtXContentSteering<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}SERVER-URI={}", Self::PREFIX, quote(&self.server_uri))?;
+
+        if let Some(value) = &self.pathway_id {
+            write!(f, ",PATHWAY-ID={}", quote(value))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a str> for ExtXContentSteering<'a> {
+    type Error = Error;
+
+    fn try_from(input: &'a str) -> Result<Self, Self::Error> {
+        let input = tag(input, Self::PREFIX)?;
+
+        let mut server_uri = None;
+        let mut pathway_id = None;
+
+        for (key, value) in AttributePairs::new(input) {
+            match key {
+                "SERVER-URI" => server_uri = Some(unquote(value)),
+                "PATHWAY-ID" => pathway_id = Some(unquote(value)),
+                _ => {
+                    // [6.3.1. General Client Responsibilities]
+                    // > ignore any attribute/value pair with an unrecognized
+                    // AttributeName.
+                }
+            }
+        }
+
+        let server_uri = server_uri.ok_or_else(|| Error::missing_value("SERVER-URI"))?;
+
+        Ok(Self {
+            server_uri,
+            pathway_id,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    macro_rules! generate_tests {
+        ( $( { $struct:expr, $str:expr } ),+ $(,)* ) => {
+            #[test]
+            fn test_display() {
+                $(
+                    assert_eq!($struct.to_string(), $str.to_string());
+                )+
+            }
+
+            #[test]
+            fn test_parser() {
+                $(
+                    assert_eq!($struct, ExtXContentSteering::try_from($str).unwrap());
+                )+
+            }
+        }
+    }
+
+    generate_tests! {
+        {
+            ExtXContentSteering::new("manifest.json"),
+            "#EXT-X-CONTENT-STEERING:SERVER-URI=\"manifest.json\""
+        },
+        {
+            ExtXContentSteering {
+                server_uri: "manifest.json".into(),
+                pathway_id: Some("CDN-A".into()),
+            },
+            "#EXT-X-CONTENT-STEERING:SERVER-URI=\"manifest.json\",PATHWAY-ID=\"CDN-A\""
+        },
+    }
+
+    #[test]
+    fn test_parser_error() {
+        assert_eq!(
+            ExtXContentSteering::try_from("#EXT-X-CONTENT-STEERING:PATHWAY-ID=\"CDN-A\""),
+            Err(Error::missing_value("SERVER-URI"))
+        );
+    }
+
+    #[test]
+    fn test_required_version() {
+        assert_eq!(
+            ExtXContentSteering::new("manifest.json").required_version(),
+            ProtocolVersion::V1
+        );
+    }
+}
@@ -4,6 +4,8 @@ use std::fmt;
 
 use derive_builder::Builder;
 use shorthand::ShortHand;
+#[cfg(feature = "url")]
+use url::{ParseError, Url};
 
 use crate::attribute::AttributePairs;
 use crate::types::{Channels, InStreamId, MediaType, ProtocolVersion};
@@ -16,6 +18,33 @@ use crate::{Error, RequiredVersion};
 /// languages (e.g. english is the default and there also exists an
 /// [`ExtXMedia`] stream with a german audio).
 ///
+/// Attributes, which are equal to their default value (like `DEFAULT=NO`), are
+/// omitted, when the tag is displayed. The alternate format (`{:#}`) writes
+/// them explicitly.
+///
+/// # Example
+///
+/// ```
+/// # use hls_m3u8::tags::ExtXMedia;
+/// use hls_m3u8::types::MediaType;
+///
+/// let media = ExtXMedia::new(MediaType::Audio, "audio", "english");
+///
+/// assert_eq!(
+///     format!("{}", media),
+///     "#EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"audio\",NAME=\"english\"".to_string()
+/// );
+///
+/// assert_eq!(
+///     format!("{:#}", media),
+///     concat!(
+///         "#EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"audio\",NAME=\"english\",",
+///         "DEFAULT=NO,AUTOSELECT=NO,FORCED=NO"
+///     )
+///     .to_string()
+/// );
+/// ```
+///
 /// [`MediaPlaylist`]: crate::MediaPlaylist
 /// [`VariantStream`]: crate::tags::VariantStream
 #[derive(ShortHand, Builder, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -289,6 +318,112 @@ impl<'a> ExtXMedia<'a> {
     #[inline]
     pub fn builder() -> ExtXMediaBuilder<'a> { ExtXMediaBuilder::default() }
 
+    /// Resolves the [`ExtXMedia::uri`] against the `base` url, which should be
+    /// the url of the [`MasterPlaylist`].
+    ///
+    /// `URI`s, which are already absolute, replace the `base` url and are
+    /// normalized by [`Url::join`] (for example `HTTP://Example.com/a/../b`
+    /// becomes `http://example.com/b`).
+    ///
+    /// `None` is returned, if the [`ExtXMedia`] has no `URI` (for example
+    /// [`MediaType::ClosedCaptions`]).
+    ///
+    /// # Errors
+    ///
+    /// This function fails, if the `URI` can not be resolved into a valid url.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::tags::ExtXMedia;
+    /// use hls_m3u8::types::MediaType;
+    /// use url::Url;
+    ///
+    /// let media = ExtXMedia::builder()
+    ///     .media_type(MediaType::Audio)
+    ///     .uri("audio/en.m3u8")
+    ///     .group_id("audio")
+    ///     .name("English")
+    ///     .build()?;
+    ///
+    /// let base = Url::parse("https://www.example.com/master.m3u8").unwrap();
+    ///
+    /// assert_eq!(
+    ///     media.resolve_uri(&base).unwrap().unwrap().as_str(),
+    ///     "https://www.example.com/audio/en.m3u8"
+    /// );
+    ///
+    /// let media = ExtXMedia::new(MediaType::Audio, "audio", "English");
+    ///
+    /// assert_eq!(media.resolve_uri(&base), None);
+    /// # Ok::<(), String>(())
+    /// ```
+    ///
+    /// [`MasterPlaylist`]: crate::MasterPlaylist
+    #[cfg(feature = "url")]
+    pub fn resolve_uri(&self, base: &Url) -> Option<Result<Url, ParseError>> {
+        self.uri.as_ref().map(|uri| base.join(uri))
+    }
+
+    /// Returns `true`, if this is a [`MediaType::Subtitles`] rendition, which
+    /// is likely in the WebVTT format.
+    ///
+    /// ## Note
+    ///
+    /// This is only a heuristic, because the playlist does not declare the
+    /// format of a subtitle rendition:
+    ///
+    /// - a `URI` with a `.vtt` or `.webvtt` extension is WebVTT,
+    /// - a `URI` or [`ExtXMedia::characteristics`], which mention IMSC or TTML
+    ///   (`imsc`, `ttml`, `stpp` or `dfxp`), are not WebVTT,
+    /// - otherwise WebVTT is assumed, because it is the default subtitle format
+    ///   of HLS.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::tags::ExtXMedia;
+    /// use hls_m3u8::types::MediaType;
+    ///
+    /// let mut media = ExtXMedia::new(MediaType::Subtitles, "subs", "English");
+    ///
+    /// media.set_uri(Some("subtitles/en.m3u8"));
+    /// assert!(media.is_likely_webvtt());
+    ///
+    /// media.set_uri(Some("subtitles/en-imsc.m3u8"));
+    /// assert!(!media.is_likely_webvtt());
+    /// ```
+    #[must_use]
+    pub fn is_likely_webvtt(&self) -> bool {
+        const OTHER_FORMATS: &[&str] = &["imsc", "ttml", "stpp", "dfxp"];
+
+        if self.media_type != MediaType::Subtitles {
+            return false;
+        }
+
+        // the query and the fragment are not part of the path:
+        let path = self
+            .uri
+            .as_ref()
+            .and_then(|uri| uri.split(&['?', '#'][..]).next())
+            .map(str::to_ascii_lowercase)
+            .unwrap_or_default();
+
+        if path.ends_with(".vtt") || path.ends_with(".webvtt") {
+            return true;
+        }
+
+        let characteristics = self
+            .characteristics
+            .as_ref()
+            .map(|value| value.to_ascii_lowercase())
+            .unwrap_or_default();
+
+        !OTHER_FORMATS
+            .iter()
+            .any(|format| path.contains(format) || characteristics.contains(format))
+    }
+
     /// Makes the struct independent of its lifetime, by taking ownership of all
     /// internal [`Cow`]s.
     ///
@@ -346,14 +481,20 @@ impl<'a> fmt::Display for ExtXMedia<'a> {
 
         if self.is_default {
             write!(f, ",DEFAULT=YES")?;
+        } else if f.alternate() {
+            write!(f, ",DEFAULT=NO")?;
         }
 
         if self.is_autoselect {
             write!(f, ",AUTOSELECT=YES")?;
+        } else if f.alternate() {
+            write!(f, ",AUTOSELECT=NO")?;
         }
 
         if self.is_forced {
             write!(f, ",FORCED=YES")?;
+        } else if f.alternate() {
+            write!(f, ",FORCED=NO")?;
         }
 
         if let Some(value) = &self.instream_id {
@@ -425,6 +566,14 @@ impl<'a> TryFrom<&'a str> for ExtXMedia<'a> {
             }
         }
 
+        // The URI is optional for `AUDIO` and `VIDEO` renditions (the rendition is
+        // then included in the variant stream) and required for `SUBTITLES`, but it
+        // must not be present for `CLOSED-CAPTIONS`, because they are always part
+        // of the video stream.
+        if builder.media_type == Some(MediaType::ClosedCaptions) && builder.uri.is_some() {
+            return Err(Error::invalid_input());
+        }
+
         builder.build().map_err(Error::builder)
     }
 }
@@ -743,6 +892,107 @@ mod test {
         },
     }
 
+    #[test]
+    fn test_parser_uri_per_media_type() {
+        // the uri is optional for audio and video renditions:
+        for media_type in &["AUDIO", "VIDEO"] {
+            assert!(ExtXMedia::try_from(
+                format!(
+                    "#EXT-X-MEDIA:TYPE={},GROUP-ID=\"foo\",NAME=\"bar\"",
+                    media_type
+                )
+                .as_str()
+            )
+            .is_ok());
+
+            assert!(ExtXMedia::try_from(
+                format!(
+                    "#EXT-X-MEDIA:TYPE={},GROUP-ID=\"foo\",NAME=\"bar\",URI=\"foo.m3u8\"",
+                    media_type
+                )
+                .as_str()
+            )
+            .is_ok());
+        }
+
+        // the uri is required for subtitles:
+        assert!(ExtXMedia::try_from(
+            "#EXT-X-MEDIA:TYPE=SUBTITLES,GROUP-ID=\"foo\",NAME=\"bar\",URI=\"foo.m3u8\""
+        )
+        .is_ok());
+
+        assert!(
+            ExtXMedia::try_from("#EXT-X-MEDIA:TYPE=SUBTITLES,GROUP-ID=\"foo\",NAME=\"bar\"")
+                .is_err()
+        );
+
+        // the uri must be absent for closed captions:
+        assert!(ExtXMedia::try_from(concat!(
+            "#EXT-X-MEDIA:TYPE=CLOSED-CAPTIONS,GROUP-ID=\"foo\",NAME=\"bar\",",
+            "INSTREAM-ID=\"CC1\""
+        ))
+        .is_ok());
+
+        assert_eq!(
+            ExtXMedia::try_from(concat!(
+                "#EXT-X-MEDIA:TYPE=CLOSED-CAPTIONS,GROUP-ID=\"foo\",NAME=\"bar\",",
+                "INSTREAM-ID=\"CC1\",URI=\"foo.m3u8\""
+            )),
+            Err(Error::invalid_input())
+        );
+    }
+
+    #[test]
+    fn test_is_likely_webvtt() {
+        let subtitles = |uri: &'static str| {
+            let mut media = ExtXMedia::new(MediaType::Subtitles, "subs", "English");
+            media.set_uri(Some(uri));
+            media
+        };
+
+        assert!(subtitles("en.m3u8").is_likely_webvtt());
+        assert!(subtitles("https://example.com/subs/EN.VTT?token=imsc").is_likely_webvtt());
+        assert!(subtitles("en.webvtt").is_likely_webvtt());
+        assert!(!subtitles("en-ttml.m3u8").is_likely_webvtt());
+        assert!(!subtitles("imsc1/en.m3u8").is_likely_webvtt());
+
+        let mut media = subtitles("en.m3u8");
+        media.set_characteristics(Some("com.example.imsc1"));
+        assert!(!media.is_likely_webvtt());
+
+        assert!(!ExtXMedia::new(MediaType::Audio, "audio", "English").is_likely_webvtt());
+    }
+
+    #[test]
+    fn test_display_alternate() {
+        let media = ExtXMedia::builder()
+            .media_type(MediaType::Audio)
+            .group_id("audio")
+            .language("en")
+            .name("English")
+            .is_autoselect(true)
+            .uri("english.m3u8")
+            .build()
+            .unwrap();
+
+        let compact = concat!(
+            "#EXT-X-MEDIA:TYPE=AUDIO,URI=\"english.m3u8\",GROUP-ID=\"audio\",",
+            "LANGUAGE=\"en\",NAME=\"English\",AUTOSELECT=YES"
+        );
+
+        let full = concat!(
+            "#EXT-X-MEDIA:TYPE=AUDIO,URI=\"english.m3u8\",GROUP-ID=\"audio\",",
+            "LANGUAGE=\"en\",NAME=\"English\",DEFAULT=NO,AUTOSELECT=YES,FORCED=NO"
+        );
+
+        assert_eq!(format!("{}", media), compact.to_string());
+        assert_eq!(format!("{:#}", media), full.to_string());
+
+        // both representations describe the same tag
+        assert_eq!(ExtXMedia::try_from(compact).unwrap(), media);
+        assert_eq!(ExtXMedia::try_from(full).unwrap(), media);
+    }
+
     #[test]
     fn test_parser_error() {
         assert_eq!(ExtXMedia::try_from("").is_err(), true);
@@ -1,9 +1,11 @@
+pub(crate) mod content_steering;
 pub(crate) mod media;
 pub(crate) mod session_data;
 pub(crate) mod session_key;
 pub(crate) mod variant_stream;
 
+pub use content_steering::ExtXContentSteering;
 pub use media::ExtXMedia;
 pub use session_data::{ExtXSessionData, SessionData};
 pub use session_key::*;
-pub use variant_stream::*;
+pub use variant_stream::VariantStream;
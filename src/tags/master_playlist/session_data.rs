@@ -153,6 +153,58 @@ impl<'a> ExtXSessionData<'a> {
         }
     }
 
+    /// Returns the inline value of the session data or `None`, if the data is
+    /// provided by an `URI`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::tags::ExtXSessionData;
+    /// use hls_m3u8::tags::SessionData;
+    ///
+    /// let session_data = ExtXSessionData::new(
+    ///     "com.example.movie.title",
+    ///     SessionData::Value("some data".into()),
+    /// );
+    ///
+    /// assert_eq!(session_data.value(), Some("some data"));
+    /// assert_eq!(session_data.uri(), None);
+    /// ```
+    #[must_use]
+    pub fn value(&self) -> Option<&str> {
+        match &self.data {
+            SessionData::Value(value) => Some(value),
+            SessionData::Uri(_) => None,
+        }
+    }
+
+    /// Returns the `URI` of the [`json`] file, that contains the session data,
+    /// or `None`, if the data is provided inline.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::tags::ExtXSessionData;
+    /// use hls_m3u8::tags::SessionData;
+    ///
+    /// let session_data = ExtXSessionData::new(
+    ///     "com.example.movie.title",
+    ///     SessionData::Uri("https://www.example.com/".into()),
+    /// );
+    ///
+    /// assert_eq!(session_data.uri(), Some("https://www.example.com/"));
+    /// assert_eq!(session_data.value(), None);
+    /// ```
+    ///
+    /// [`json`]: https://tools.ietf.org/html/rfc8259
+    #[must_use]
+    pub fn uri(&self) -> Option<&str> {
+        match &self.data {
+            SessionData::Uri(uri) => Some(uri),
+            SessionData::Value(_) => None,
+        }
+    }
+
     /// Makes the struct independent of its lifetime, by taking ownership of all
     /// internal [`Cow`]s.
     ///
@@ -335,4 +387,54 @@ mod test {
             ProtocolVersion::V1
         );
     }
+
+    #[test]
+    fn test_accessors() {
+        let session_data = ExtXSessionData::try_from(concat!(
+            "#EXT-X-SESSION-DATA:",
+            "DATA-ID=\"com.example.title\",",
+            "VALUE=\"This is an example\",",
+            "LANGUAGE=\"en\""
+        ))
+        .unwrap();
+
+        assert_eq!(session_data.value(), Some("This is an example"));
+        assert_eq!(session_data.uri(), None);
+        assert_eq!(session_data.language(), Some(&Cow::Borrowed("en")));
+
+        let session_data = ExtXSessionData::try_from(concat!(
+            "#EXT-X-SESSION-DATA:",
+            "DATA-ID=\"com.example.lyrics\",",
+            "URI=\"lyrics.json\""
+        ))
+        .unwrap();
+
+        assert_eq!(session_data.value(), None);
+        assert_eq!(session_data.uri(), Some("lyrics.json"));
+        assert_eq!(session_data.language(), None);
+    }
+
+    #[test]
+    fn test_parser_value_or_uri() {
+        // exactly one of `VALUE` and `URI` must be present
+        assert!(ExtXSessionData::try_from(concat!(
+            "#EXT-X-SESSION-DATA:",
+            "DATA-ID=\"com.example.lyrics\""
+        ))
+        .is_err());
+
+        assert!(ExtXSessionData::try_from(concat!(
+            "#EXT-X-SESSION-DATA:",
+            "DATA-ID=\"com.example.lyrics\",",
+            "URI=\"lyrics.json\",",
+            "VALUE=\"value\""
+        ))
+        .is_err());
+
+        // the builder can not be built without data
+        assert!(ExtXSessionData::builder()
+            .data_id("com.example.lyrics")
+            .build()
+            .is_err());
+    }
 }
@@ -3,12 +3,18 @@ use core::fmt;
 use core::ops::Deref;
 use std::borrow::Cow;
 
+#[cfg(feature = "url")]
+use url::{ParseError, Url};
+
 use crate::attribute::AttributePairs;
 use crate::tags::ExtXMedia;
 use crate::traits::RequiredVersion;
-use crate::types::{ClosedCaptions, MediaType, ProtocolVersion, StreamData, UFloat};
+use crate::types::{
+    ClosedCaptions, Codecs, HdcpLevel, MediaType, ProtocolVersion, Resolution, StreamData, UFloat,
+    VideoRange,
+};
 use crate::utils::{quote, tag, unquote};
-use crate::Error;
+use crate::{Error, ParseOptions, Warning};
 
 /// A server may offer multiple [`MediaPlaylist`] files to provide different
 /// encodings of the same presentation.
@@ -172,6 +178,46 @@ impl<'a> VariantStream<'a> {
     pub(crate) const PREFIX_EXTXIFRAME: &'static str = "#EXT-X-I-FRAME-STREAM-INF:";
     pub(crate) const PREFIX_EXTXSTREAMINF: &'static str = "#EXT-X-STREAM-INF:";
 
+    /// Returns a builder for a [`VariantStream::ExtXStreamInf`] with the given
+    /// bandwidth.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hls_m3u8::tags::VariantStream;
+    /// use hls_m3u8::types::ClosedCaptions;
+    ///
+    /// let variant_stream = VariantStream::stream_inf_builder(1_110_000)
+    ///     .uri("https://www.example.com/hi.m3u8")
+    ///     .codecs(&["avc1.640028", "mp4a.40.2"])
+    ///     .resolution((1920, 1080))
+    ///     .frame_rate(60_u8)
+    ///     .audio("aac")
+    ///     .closed_captions(ClosedCaptions::None)
+    ///     .build()?;
+    ///
+    /// assert_eq!(
+    ///     variant_stream.to_string(),
+    ///     concat!(
+    ///         "#EXT-X-STREAM-INF:BANDWIDTH=1110000,CODECS=\"avc1.640028,mp4a.40.2\",",
+    ///         "RESOLUTION=1920x1080,FRAME-RATE=60.000,AUDIO=\"aac\",CLOSED-CAPTIONS=NONE\n",
+    ///         "https://www.example.com/hi.m3u8"
+    ///     )
+    /// );
+    /// # Ok::<(), String>(())
+    /// ```
+    #[must_use]
+    pub const fn stream_inf_builder(bandwidth: u64) -> ExtXStreamInfBuilder<'a> {
+        ExtXStreamInfBuilder {
+            uri: None,
+            frame_rate: None,
+            audio: None,
+            subtitles: None,
+            closed_captions: None,
+            stream_data: StreamData::new(bandwidth),
+        }
+    }
+
     /// Checks if a [`VariantStream`] and an [`ExtXMedia`] element are
     /// associated.
     ///
@@ -240,6 +286,44 @@ impl<'a> VariantStream<'a> {
         }
     }
 
+    /// Resolves the `URI` of the [`VariantStream`] against the `base` url,
+    /// which should be the url of the [`MasterPlaylist`].
+    ///
+    /// `URI`s, which are already absolute, replace the `base` url and are
+    /// normalized by [`Url::join`] (for example `HTTP://Example.com/a/../b`
+    /// becomes `http://example.com/b`).
+    ///
+    /// # Errors
+    ///
+    /// This function fails, if the `URI` can not be resolved into a valid url.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::tags::VariantStream;
+    /// use url::Url;
+    ///
+    /// let variant = VariantStream::stream_inf_builder(150_000)
+    ///     .uri("low/index.m3u8")
+    ///     .build()?;
+    ///
+    /// let base = Url::parse("https://www.example.com/master.m3u8").unwrap();
+    ///
+    /// assert_eq!(
+    ///     variant.resolve_uri(&base).unwrap().as_str(),
+    ///     "https://www.example.com/low/index.m3u8"
+    /// );
+    /// # Ok::<(), String>(())
+    /// ```
+    ///
+    /// [`MasterPlaylist`]: crate::MasterPlaylist
+    #[cfg(feature = "url")]
+    pub fn resolve_uri(&self, base: &Url) -> Result<Url, ParseError> {
+        match self {
+            Self::ExtXIFrame { uri, .. } | Self::ExtXStreamInf { uri, .. } => base.join(uri),
+        }
+    }
+
     /// Makes the struct independent of its lifetime, by taking ownership of all
     /// internal [`Cow`]s.
     ///
@@ -276,6 +360,132 @@ impl<'a> VariantStream<'a> {
     }
 }
 
+/// Builder for a [`VariantStream::ExtXStreamInf`], which can be created with
+/// [`VariantStream::stream_inf_builder`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtXStreamInfBuilder<'a> {
+    uri: Option<Cow<'a, str>>,
+    frame_rate: Option<UFloat>,
+    audio: Option<Cow<'a, str>>,
+    subtitles: Option<Cow<'a, str>>,
+    closed_captions: Option<ClosedCaptions<'a>>,
+    stream_data: StreamData<'a>,
+}
+
+impl<'a> ExtXStreamInfBuilder<'a> {
+    /// The URI of the [`MediaPlaylist`], that contains the renditions of the
+    /// variant stream.
+    ///
+    /// [`MediaPlaylist`]: crate::MediaPlaylist
+    pub fn uri<T: Into<Cow<'a, str>>>(&mut self, value: T) -> &mut Self {
+        self.uri = Some(value.into());
+        self
+    }
+
+    /// The average segment bitrate of the variant stream.
+    pub fn average_bandwidth(&mut self, value: u64) -> &mut Self {
+        self.stream_data.set_average_bandwidth(Some(value));
+        self
+    }
+
+    /// The list of formats, that are present in the variant stream.
+    pub fn codecs<T: Into<Codecs<'a>>>(&mut self, value: T) -> &mut Self {
+        self.stream_data.set_codecs(Some(value));
+        self
+    }
+
+    /// The optimal pixel resolution at which to display all the video in the
+    /// variant stream.
+    pub fn resolution<T: Into<Resolution>>(&mut self, value: T) -> &mut Self {
+        self.stream_data.set_resolution(Some(value));
+        self
+    }
+
+    /// The maximum frame rate for all the video in the variant stream.
+    pub fn frame_rate<T: Into<UFloat>>(&mut self, value: T) -> &mut Self {
+        self.frame_rate = Some(value.into());
+        self
+    }
+
+    /// The High-bandwidth Digital Content Protection level of the variant
+    /// stream.
+    pub fn hdcp_level(&mut self, value: HdcpLevel) -> &mut Self {
+        self.stream_data.set_hdcp_level(Some(value));
+        self
+    }
+
+    /// The dynamic range of the video in the variant stream.
+    pub fn video_range(&mut self, value: VideoRange) -> &mut Self {
+        self.stream_data.set_video_range(Some(value));
+        self
+    }
+
+    /// The group id of the audio renditions.
+    pub fn audio<T: Into<Cow<'a, str>>>(&mut self, value: T) -> &mut Self {
+        self.audio = Some(value.into());
+        self
+    }
+
+    /// The group id of the video renditions.
+    pub fn video<T: Into<Cow<'a, str>>>(&mut self, value: T) -> &mut Self {
+        self.stream_data.set_video(Some(value));
+        self
+    }
+
+    /// The pathway, which provides the variant stream.
+    pub fn pathway_id<T: Into<Cow<'a, str>>>(&mut self, value: T) -> &mut Self {
+        self.stream_data.set_pathway_id(Some(value));
+        self
+    }
+
+    /// The group id of the subtitle renditions.
+    pub fn subtitles<T: Into<Cow<'a, str>>>(&mut self, value: T) -> &mut Self {
+        self.subtitles = Some(value.into());
+        self
+    }
+
+    /// The group id of the closed caption renditions or
+    /// [`ClosedCaptions::None`].
+    pub fn closed_captions(&mut self, value: ClosedCaptions<'a>) -> &mut Self {
+        self.closed_captions = Some(value);
+        self
+    }
+
+    /// Builds a new [`VariantStream::ExtXStreamInf`].
+    ///
+    /// # Errors
+    ///
+    /// If the uri is missing, a component of the resolution is zero or the
+    /// group id of the closed captions is empty.
+    pub fn build(&self) -> Result<VariantStream<'a>, String> {
+        let uri = self
+            .uri
+            .clone()
+            .ok_or_else(|| Error::missing_field("ExtXStreamInf", "uri").to_string())?;
+
+        if let Some(resolution) = self.stream_data.resolution() {
+            if resolution.width() == 0 || resolution.height() == 0 {
+                return Err(format!("invalid resolution: {}", resolution));
+            }
+        }
+
+        if let Some(ClosedCaptions::GroupId(group_id)) = &self.closed_captions {
+            if group_id.is_empty() {
+                return Err("the group id of the closed captions must not be empty".to_string());
+            }
+        }
+
+        Ok(VariantStream::ExtXStreamInf {
+            uri,
+            frame_rate: self.frame_rate,
+            audio: self.audio.clone(),
+            subtitles: self.subtitles.clone(),
+            closed_captions: self.closed_captions.clone(),
+            stream_data: self.stream_data.clone(),
+        })
+    }
+}
+
 /// This tag requires [`ProtocolVersion::V1`].
 impl<'a> RequiredVersion for VariantStream<'a> {
     fn required_version(&self) -> ProtocolVersion { ProtocolVersion::V1 }
@@ -316,16 +526,47 @@ impl<'a> fmt::Display for VariantStream<'a> {
                 closed_captions,
                 stream_data,
             } => {
-                write!(f, "{}{}", Self::PREFIX_EXTXSTREAMINF, stream_data)?;
+                // The attributes are written in the order in which they are
+                // listed in the specification, which is also the order used by
+                // apple's tools:
+                // BANDWIDTH, AVERAGE-BANDWIDTH, CODECS, RESOLUTION, FRAME-RATE,
+                // HDCP-LEVEL, VIDEO-RANGE, AUDIO, VIDEO, SUBTITLES, CLOSED-CAPTIONS,
+                // PATHWAY-ID
+                write!(f, "{}", Self::PREFIX_EXTXSTREAMINF)?;
+                write!(f, "BANDWIDTH={}", stream_data.bandwidth())?;
+
+                if let Some(value) = stream_data.average_bandwidth() {
+                    write!(f, ",AVERAGE-BANDWIDTH={}", value)?;
+                }
+
+                if let Some(value) = stream_data.codecs() {
+                    write!(f, ",CODECS={}", quote(value))?;
+                }
+
+                if let Some(value) = stream_data.resolution() {
+                    write!(f, ",RESOLUTION={}", value)?;
+                }
 
                 if let Some(value) = frame_rate {
                     write!(f, ",FRAME-RATE={:.3}", value.as_f32())?;
                 }
 
+                if let Some(value) = stream_data.hdcp_level() {
+                    write!(f, ",HDCP-LEVEL={}", value)?;
+                }
+
+                if let Some(value) = stream_data.video_range() {
+                    write!(f, ",VIDEO-RANGE={}", value)?;
+                }
+
                 if let Some(value) = audio {
                     write!(f, ",AUDIO={}", quote(value))?;
                 }
 
+                if let Some(value) = stream_data.video() {
+                    write!(f, ",VIDEO={}", quote(value))?;
+                }
+
                 if let Some(value) = subtitles {
                     write!(f, ",SUBTITLES={}", quote(value))?;
                 }
@@ -334,6 +575,10 @@ impl<'a> fmt::Display for VariantStream<'a> {
                     write!(f, ",CLOSED-CAPTIONS={}", value)?;
                 }
 
+                if let Some(value) = stream_data.pathway_id() {
+                    write!(f, ",PATHWAY-ID={}", quote(value))?;
+                }
+
                 write!(f, "\n{}", uri)?;
             }
         }
@@ -342,11 +587,21 @@ impl<'a> fmt::Display for VariantStream<'a> {
     }
 }
 
-impl<'a> TryFrom<&'a str> for VariantStream<'a> {
-    type Error = Error;
-
-    fn try_from(input: &'a str) -> Result<Self, Self::Error> {
+impl<'a> VariantStream<'a> {
+    /// Parses a [`VariantStream`] with the given [`ParseOptions`].
+    ///
+    /// With [`ParseOptions::lenient`], a missing `BANDWIDTH` attribute of an
+    /// `#EXT-X-STREAM-INF` tag defaults to `0` and a [`Warning`] is recorded.
+    pub(crate) fn parse(
+        input: &'a str,
+        options: ParseOptions,
+        warnings: &mut Vec<Warning>,
+    ) -> crate::Result<Self> {
         if let Ok(input) = tag(input, Self::PREFIX_EXTXIFRAME) {
+            if options.is_lenient {
+                Self::warn_unknown_attributes(Self::PREFIX_EXTXIFRAME, input, &["URI"], warnings);
+            }
+
             let uri = AttributePairs::new(input)
                 .find_map(|(key, value)| {
                     if key == "URI" {
@@ -355,7 +610,7 @@ impl<'a> TryFrom<&'a str> for VariantStream<'a> {
                         None
                     }
                 })
-                .ok_or_else(|| Error::missing_value("URI"))?;
+                .ok_or_else(Error::invalid_input)?;
 
             Ok(Self::ExtXIFrame {
                 uri,
@@ -368,6 +623,15 @@ impl<'a> TryFrom<&'a str> for VariantStream<'a> {
                 .ok_or_else(|| Error::missing_value("first_line"))?;
             let uri = lines.next().ok_or_else(|| Error::missing_value("URI"))?;
 
+            if options.is_lenient {
+                Self::warn_unknown_attributes(
+                    Self::PREFIX_EXTXSTREAMINF,
+                    first_line,
+                    &["FRAME-RATE", "AUDIO", "SUBTITLES", "CLOSED-CAPTIONS"],
+                    warnings,
+                );
+            }
+
             let mut frame_rate = None;
             let mut audio = None;
             let mut subtitles = None;
@@ -391,7 +655,19 @@ impl<'a> TryFrom<&'a str> for VariantStream<'a> {
                 audio,
                 subtitles,
                 closed_captions,
-                stream_data: StreamData::try_from(first_line)?,
+                stream_data: StreamData::parse(first_line, || {
+                    if options.is_lenient {
+                        warnings.push(Warning::MissingAttribute {
+                            tag: Self::PREFIX_EXTXSTREAMINF.trim_end_matches(':').to_string(),
+                            attribute: "BANDWIDTH".to_string(),
+                            default: "0".to_string(),
+                        });
+
+                        Ok(0)
+                    } else {
+                        Err(Error::missing_value("BANDWIDTH"))
+                    }
+                })?,
             })
         } else {
             // TODO: custom error type? + attach input data
@@ -402,6 +678,32 @@ impl<'a> TryFrom<&'a str> for VariantStream<'a> {
             )))
         }
     }
+
+    /// Adds a [`Warning`] for every attribute, that is neither in `known` nor
+    /// an attribute of the [`StreamData`].
+    fn warn_unknown_attributes(
+        prefix: &str,
+        input: &str,
+        known: &[&str],
+        warnings: &mut Vec<Warning>,
+    ) {
+        for (key, _) in AttributePairs::new(input) {
+            if !known.contains(&key) && !StreamData::ATTRIBUTES.contains(&key) {
+                warnings.push(Warning::UnknownAttribute {
+                    tag: prefix.trim_end_matches(':').to_string(),
+                    attribute: key.to_string(),
+                });
+            }
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a str> for VariantStream<'a> {
+    type Error = Error;
+
+    fn try_from(input: &'a str) -> Result<Self, Self::Error> {
+        Self::parse(input, ParseOptions::strict(), &mut vec![])
+    }
 }
 
 impl<'a> Deref for VariantStream<'a> {
@@ -426,6 +728,142 @@ mod tests {
     use crate::types::InStreamId;
     use pretty_assertions::assert_eq;
 
+    #[test]
+    fn test_stream_inf_builder() {
+        assert_eq!(
+            VariantStream::stream_inf_builder(7_680_000)
+                .uri("https://www.example.com/hi.m3u8")
+                .average_bandwidth(6_000_000)
+                .codecs(&["avc1.640028", "mp4a.40.2"])
+                .resolution((1920, 1080))
+                .frame_rate(UFloat::new(59.94))
+                .hdcp_level(HdcpLevel::Type0)
+                .audio("aac")
+                .video("vid")
+                .subtitles("subs")
+                .closed_captions(ClosedCaptions::group_id("cc"))
+                .build()
+                .unwrap(),
+            VariantStream::ExtXStreamInf {
+                uri: "https://www.example.com/hi.m3u8".into(),
+                frame_rate: Some(UFloat::new(59.94)),
+                audio: Some("aac".into()),
+                subtitles: Some("subs".into()),
+                closed_captions: Some(ClosedCaptions::group_id("cc")),
+                stream_data: StreamData::builder()
+                    .bandwidth(7_680_000)
+                    .average_bandwidth(6_000_000)
+                    .codecs(&["avc1.640028", "mp4a.40.2"])
+                    .resolution(Resolution::new(1920, 1080))
+                    .hdcp_level(HdcpLevel::Type0)
+                    .video("vid")
+                    .build()
+                    .unwrap(),
+            }
+        );
+
+        assert_eq!(
+            VariantStream::stream_inf_builder(64000)
+                .uri("audio.m3u8")
+                .closed_captions(ClosedCaptions::None)
+                .build()
+                .unwrap()
+                .to_string(),
+            "#EXT-X-STREAM-INF:BANDWIDTH=64000,CLOSED-CAPTIONS=NONE\naudio.m3u8".to_string()
+        );
+
+        // the uri is required
+        assert!(VariantStream::stream_inf_builder(64000).build().is_err());
+
+        // a resolution can not be zero
+        assert!(VariantStream::stream_inf_builder(64000)
+            .uri("video.m3u8")
+            .resolution((0, 1080))
+            .build()
+            .is_err());
+        assert!(VariantStream::stream_inf_builder(64000)
+            .uri("video.m3u8")
+            .resolution((1920, 0))
+            .build()
+            .is_err());
+
+        // the group id must not be empty
+        assert!(VariantStream::stream_inf_builder(64000)
+            .uri("video.m3u8")
+            .closed_captions(ClosedCaptions::group_id(""))
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_parser_i_frame_uri() {
+        assert_eq!(
+            VariantStream::try_from(concat!(
+                "#EXT-X-I-FRAME-STREAM-INF:BANDWIDTH=86000,",
+                "URI=\"low/iframe.m3u8\",CODECS=\"avc1.4d001f\""
+            ))
+            .unwrap(),
+            VariantStream::ExtXIFrame {
+                uri: "low/iframe.m3u8".into(),
+                stream_data: StreamData::builder()
+                    .bandwidth(86000)
+                    .codecs(&["avc1.4d001f"])
+                    .build()
+                    .unwrap(),
+            }
+        );
+
+        // unlike the uri of an `#EXT-X-STREAM-INF` tag, the uri is an attribute
+        // and it is required:
+        assert_eq!(
+            VariantStream::try_from("#EXT-X-I-FRAME-STREAM-INF:BANDWIDTH=86000"),
+            Err(Error::invalid_input())
+        );
+        assert_eq!(
+            VariantStream::try_from("#EXT-X-I-FRAME-STREAM-INF:BANDWIDTH=86000\nlow/iframe.m3u8"),
+            Err(Error::invalid_input())
+        );
+    }
+
+    #[test]
+    fn test_display_attribute_order() {
+        assert_eq!(
+            VariantStream::ExtXStreamInf {
+                uri: "https://www.example.com/hi.m3u8".into(),
+                frame_rate: Some(UFloat::new(59.94)),
+                audio: Some("aac".into()),
+                subtitles: Some("subs".into()),
+                closed_captions: Some(ClosedCaptions::group_id("cc")),
+                stream_data: StreamData::builder()
+                    .bandwidth(7_680_000)
+                    .average_bandwidth(6_000_000)
+                    .codecs(&["avc1.640028", "mp4a.40.2"])
+                    .resolution(Resolution::new(1920, 1080))
+                    .hdcp_level(HdcpLevel::Type0)
+                    .video_range(VideoRange::Pq)
+                    .video("vid")
+                    .build()
+                    .unwrap(),
+            }
+            .to_string(),
+            concat!(
+                "#EXT-X-STREAM-INF:",
+                "BANDWIDTH=7680000,",
+                "AVERAGE-BANDWIDTH=6000000,",
+                "CODECS=\"avc1.640028,mp4a.40.2\",",
+                "RESOLUTION=1920x1080,",
+                "FRAME-RATE=59.940,",
+                "HDCP-LEVEL=TYPE-0,",
+                "VIDEO-RANGE=PQ,",
+                "AUDIO=\"aac\",",
+                "VIDEO=\"vid\",",
+                "SUBTITLES=\"subs\",",
+                "CLOSED-CAPTIONS=\"cc\"\n",
+                "https://www.example.com/hi.m3u8"
+            )
+        );
+    }
+
     #[test]
     fn test_required_version() {
         assert_eq!(
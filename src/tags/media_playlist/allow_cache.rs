@@ -0,0 +1,123 @@
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::types::ProtocolVersion;
+use crate::utils::{parse_yes_or_no, tag};
+use crate::{Error, RequiredVersion};
+
+/// Indicates whether the client may cache downloaded [`MediaSegment`]s.
+///
+/// ## Note
+///
+/// This tag has been removed in [`ProtocolVersion::V7`] and is only
+/// supported, so that legacy playlists can be parsed and written without
+/// losing information.
+///
+/// The value is available as [`MediaPlaylist::allow_cache`].
+///
+/// [`MediaSegment`]: crate::MediaSegment
+/// [`MediaPlaylist::allow_cache`]: crate::MediaPlaylist::allow_cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ExtXAllowCache(bool);
+
+impl ExtXAllowCache {
+    pub(crate) const PREFIX: &'static str = "#EXT-X-ALLOW-CACHE:";
+
+    /// Makes a new [`ExtXAllowCache`] tag.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::tags::ExtXAllowCache;
+    /// let allow_cache = ExtXAllowCache::new(false);
+    ///
+    /// assert_eq!(allow_cache.to_string(), "#EXT-X-ALLOW-CACHE:NO".to_string());
+    /// ```
+    #[must_use]
+    pub const fn new(is_allowed: bool) -> Self { Self(is_allowed) }
+
+    /// Returns `true`, if the client may cache downloaded [`MediaSegment`]s.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::tags::ExtXAllowCache;
+    /// use std::convert::TryFrom;
+    ///
+    /// let allow_cache = ExtXAllowCache::try_from("#EXT-X-ALLOW-CACHE:YES")?;
+    ///
+    /// assert_eq!(allow_cache.is_allowed(), true);
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    ///
+    /// [`MediaSegment`]: crate::MediaSegment
+    #[must_use]
+    pub const fn is_allowed(self) -> bool { self.0 }
+}
+
+/// This tag requires [`ProtocolVersion::V1`].
+impl RequiredVersion for ExtXAllowCache {
+    fn required_version(&self) -> ProtocolVersion { ProtocolVersion::V1 }
+}
+
+impl fmt::Display for ExtXAllowCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0 {
+            write!(f, "{}YES", Self::PREFIX)
+        } else {
+            write!(f, "{}NO", Self::PREFIX)
+        }
+    }
+}
+
+impl TryFrom<&str> for ExtXAllowCache {
+    type Error = Error;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        let input = tag(input, Self::PREFIX)?;
+
+        Ok(Self(parse_yes_or_no(input)?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            ExtXAllowCache::new(true).to_string(),
+            "#EXT-X-ALLOW-CACHE:YES".to_string()
+        );
+
+        assert_eq!(
+            ExtXAllowCache::new(false).to_string(),
+            "#EXT-X-ALLOW-CACHE:NO".to_string()
+        );
+    }
+
+    #[test]
+    fn test_parser() {
+        assert_eq!(
+            ExtXAllowCache::new(true),
+            ExtXAllowCache::try_from("#EXT-X-ALLOW-CACHE:YES").unwrap()
+        );
+
+        assert_eq!(
+            ExtXAllowCache::new(false),
+            ExtXAllowCache::try_from("#EXT-X-ALLOW-CACHE:NO").unwrap()
+        );
+
+        assert!(ExtXAllowCache::try_from("#EXT-X-ALLOW-CACHE:MAYBE").is_err());
+    }
+
+    #[test]
+    fn test_required_version() {
+        assert_eq!(
+            ExtXAllowCache::new(true).required_version(),
+            ProtocolVersion::V1
+        );
+    }
+}
@@ -23,7 +23,7 @@ impl RequiredVersion for ExtXEndList {
 }
 
 impl fmt::Display for ExtXEndList {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { Self::PREFIX.fmt(f) }
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str(Self::PREFIX) }
 }
 
 impl TryFrom<&str> for ExtXEndList {
@@ -1,11 +1,17 @@
+pub(crate) mod allow_cache;
 pub(crate) mod discontinuity_sequence;
 pub(crate) mod end_list;
 pub(crate) mod i_frames_only;
 pub(crate) mod media_sequence;
+pub(crate) mod part_inf;
+pub(crate) mod server_control;
 pub(crate) mod target_duration;
 
+pub use allow_cache::ExtXAllowCache;
 pub(crate) use discontinuity_sequence::*;
 pub(crate) use end_list::*;
 pub(crate) use i_frames_only::*;
 pub(crate) use media_sequence::*;
+pub(crate) use part_inf::*;
+pub use server_control::ExtXServerControl;
 pub(crate) use target_duration::*;
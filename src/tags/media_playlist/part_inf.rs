@@ -0,0 +1,104 @@
+use std::convert::TryFrom;
+use std::fmt;
+use std::time::Duration;
+
+use crate::attribute::AttributePairs;
+use crate::types::ProtocolVersion;
+use crate::utils::{format_duration, parse_duration, tag};
+use crate::{Error, RequiredVersion};
+
+/// Provides information about the partial segments ([`ExtXPart`]) in a
+/// playlist, namely the `PART-TARGET` duration.
+///
+/// [`ExtXPart`]: crate::tags::ExtXPart
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, PartialOrd, Ord)]
+pub(crate) struct ExtXPartInf(pub Duration);
+
+impl ExtXPartInf {
+    pub(crate) const PREFIX: &'static str = "#EXT-X-PART-INF:";
+}
+
+/// This tag requires [`ProtocolVersion::V1`].
+impl RequiredVersion for ExtXPartInf {
+    fn required_version(&self) -> ProtocolVersion { ProtocolVersion::V1 }
+}
+
+impl fmt::Display for ExtXPartInf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}PART-TARGET={}", Self::PREFIX, format_duration(self.0))
+    }
+}
+
+impl TryFrom<&str> for ExtXPartInf {
+    type Error = Error;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        let input = tag(input, Self::PREFIX)?;
+
+        let mut part_target = None;
+
+        for (key, value) in AttributePairs::new(input) {
+            match key {
+                "PART-TARGET" => part_target = Some(parse_duration(value)?),
+                _ => {
+                    // [6.3.1. General Client Responsibilities]
+                    // > ignore any attribute/value pair with an unrecognized
+                    // AttributeName.
+                }
+            }
+        }
+
+        let part_target = part_target.ok_or_else(|| Error::missing_value("PART-TARGET"))?;
+
+        Ok(Self(part_target))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            ExtXPartInf(Duration::from_millis(500)).to_string(),
+            "#EXT-X-PART-INF:PART-TARGET=0.5".to_string()
+        );
+
+        // the part target is written without rounding errors:
+        assert_eq!(
+            ExtXPartInf(Duration::new(1, 1)).to_string(),
+            "#EXT-X-PART-INF:PART-TARGET=1.000000001".to_string()
+        );
+    }
+
+    #[test]
+    fn test_parser() {
+        assert_eq!(
+            ExtXPartInf(Duration::from_millis(500)),
+            ExtXPartInf::try_from("#EXT-X-PART-INF:PART-TARGET=0.5").unwrap()
+        );
+
+        // the part target is parsed without rounding errors:
+        let part_inf = ExtXPartInf::try_from("#EXT-X-PART-INF:PART-TARGET=0.33334").unwrap();
+
+        assert_eq!(part_inf, ExtXPartInf(Duration::from_nanos(333_340_000)));
+        assert_eq!(part_inf.0.as_secs_f64(), 0.33334);
+        assert_eq!(
+            part_inf.to_string(),
+            "#EXT-X-PART-INF:PART-TARGET=0.33334".to_string()
+        );
+
+        assert!(ExtXPartInf::try_from("#EXT-X-PART-INF:").is_err());
+        assert!(ExtXPartInf::try_from("#EXT-X-PART-INF:PART-TARGET=-1").is_err());
+    }
+
+    #[test]
+    fn test_required_version() {
+        assert_eq!(
+            ExtXPartInf(Duration::from_secs(1)).required_version(),
+            ProtocolVersion::V1
+        );
+    }
+}
@@ -0,0 +1,204 @@
+use std::convert::TryFrom;
+use std::fmt;
+use std::time::Duration;
+
+use crate::attribute::AttributePairs;
+use crate::types::ProtocolVersion;
+use crate::utils::{parse_duration, parse_yes_or_no, tag};
+use crate::{Error, RequiredVersion};
+
+/// Allows the server to indicate support for the delivery directives, which
+/// have been introduced for low latency streaming.
+///
+/// # Example
+///
+/// ```
+/// # use hls_m3u8::tags::ExtXServerControl;
+/// use std::time::Duration;
+///
+/// let mut server_control = ExtXServerControl::default();
+/// server_control.part_hold_back = Some(Duration::from_secs(1));
+/// server_control.can_block_reload = true;
+///
+/// assert_eq!(
+///     server_control.to_string(),
+///     "#EXT-X-SERVER-CONTROL:PART-HOLD-BACK=1,CAN-BLOCK-RELOAD=YES".to_string()
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ExtXServerControl {
+    /// Indicates, that the server can produce playlist delta updates, which
+    /// skip the [`MediaSegment`]s older than this duration (relative to the
+    /// end of the playlist).
+    ///
+    /// ## Note
+    ///
+    /// This field is optional.
+    ///
+    /// [`MediaSegment`]: crate::MediaSegment
+    pub can_skip_until: Option<Duration>,
+    /// Indicates, that the server can skip [`ExtXDateRange`] tags in playlist
+    /// delta updates.
+    ///
+    /// ## Note
+    ///
+    /// This field is optional and defaults to `false`.
+    ///
+    /// [`ExtXDateRange`]: crate::tags::ExtXDateRange
+    pub can_skip_date_ranges: bool,
+    /// The minimum distance from the end of the playlist, at which clients
+    /// should begin to play.
+    ///
+    /// ## Note
+    ///
+    /// This field is optional.
+    pub hold_back: Option<Duration>,
+    /// The minimum distance from the end of the playlist, at which clients
+    /// should begin to play in low latency mode.
+    ///
+    /// ## Note
+    ///
+    /// This field is optional, but required if the playlist contains
+    /// [`ExtXPart`] tags.
+    ///
+    /// [`ExtXPart`]: crate::tags::ExtXPart
+    pub part_hold_back: Option<Duration>,
+    /// Indicates, that the server supports blocking playlist reloads.
+    ///
+    /// ## Note
+    ///
+    /// This field is optional and defaults to `false`.
+    pub can_block_reload: bool,
+}
+
+impl ExtXServerControl {
+    pub(crate) const PREFIX: &'static str = "#EXT-X-SERVER-CONTROL:";
+}
+
+/// This tag requires [`ProtocolVersion::V1`].
+impl RequiredVersion for ExtXServerControl {
+    fn required_version(&self) -> ProtocolVersion { ProtocolVersion::V1 }
+}
+
+impl fmt::Display for ExtXServerControl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", Self::PREFIX)?;
+
+        let mut attributes = vec![];
+
+        if let Some(value) = self.can_skip_until {
+            attributes.push(format!("CAN-SKIP-UNTIL={}", value.as_secs_f64()));
+        }
+
+        if self.can_skip_date_ranges {
+            attributes.push("CAN-SKIP-DATERANGES=YES".to_string());
+        }
+
+        if let Some(value) = self.hold_back {
+            attributes.push(format!("HOLD-BACK={}", value.as_secs_f64()));
+        }
+
+        if let Some(value) = self.part_hold_back {
+            attributes.push(format!("PART-HOLD-BACK={}", value.as_secs_f64()));
+        }
+
+        if self.can_block_reload {
+            attributes.push("CAN-BLOCK-RELOAD=YES".to_string());
+        }
+
+        write!(f, "{}", attributes.join(","))
+    }
+}
+
+impl TryFrom<&str> for ExtXServerControl {
+    type Error = Error;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        let input = tag(input, Self::PREFIX)?;
+
+        let mut result = Self::default();
+
+        for (key, value) in AttributePairs::new(input) {
+            match key {
+                "CAN-SKIP-UNTIL" => result.can_skip_until = Some(parse_duration(value)?),
+                "CAN-SKIP-DATERANGES" => result.can_skip_date_ranges = parse_yes_or_no(value)?,
+                "HOLD-BACK" => result.hold_back = Some(parse_duration(value)?),
+                "PART-HOLD-BACK" => result.part_hold_back = Some(parse_duration(value)?),
+                "CAN-BLOCK-RELOAD" => result.can_block_reload = parse_yes_or_no(value)?,
+                _ => {
+                    // [6.3.1. General Client Responsibilities]
+                    // > ignore any attribute/value pair with an unrecognized
+                    // AttributeName.
+                }
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    macro_rules! generate_tests {
+        ( $( { $struct:expr, $str:expr } ),+ $(,)* ) => {
+            #[test]
+            fn test_display() {
+                $(
+                    assert_eq!($struct.to_string(), $str.to_string());
+                )+
+            }
+
+            #[test]
+            fn test_parser() {
+                $(
+                    assert_eq!($struct, ExtXServerControl::try_from($str).unwrap());
+                )+
+            }
+        }
+    }
+
+    generate_tests! {
+        {
+            ExtXServerControl::default(),
+            "#EXT-X-SERVER-CONTROL:"
+        },
+        {
+            ExtXServerControl {
+                part_hold_back: Some(Duration::from_millis(1500)),
+                can_block_reload: true,
+                ..ExtXServerControl::default()
+            },
+            "#EXT-X-SERVER-CONTROL:PART-HOLD-BACK=1.5,CAN-BLOCK-RELOAD=YES"
+        },
+        {
+            ExtXServerControl {
+                can_skip_until: Some(Duration::from_secs(36)),
+                can_skip_date_ranges: true,
+                hold_back: Some(Duration::from_secs(12)),
+                part_hold_back: Some(Duration::from_secs(1)),
+                can_block_reload: true,
+            },
+            concat!(
+                "#EXT-X-SERVER-CONTROL:CAN-SKIP-UNTIL=36,CAN-SKIP-DATERANGES=YES,",
+                "HOLD-BACK=12,PART-HOLD-BACK=1,CAN-BLOCK-RELOAD=YES"
+            )
+        },
+    }
+
+    #[test]
+    fn test_parser_error() {
+        assert!(ExtXServerControl::try_from("#EXT-X-SERVER-CONTROL:HOLD-BACK=-1").is_err());
+        assert!(ExtXServerControl::try_from("#EXT-X-SERVER-CONTROL:CAN-BLOCK-RELOAD=1").is_err());
+    }
+
+    #[test]
+    fn test_required_version() {
+        assert_eq!(
+            ExtXServerControl::default().required_version(),
+            ProtocolVersion::V1
+        );
+    }
+}
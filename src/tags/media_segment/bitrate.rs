@@ -0,0 +1,128 @@
+use std::convert::TryFrom;
+use std::fmt;
+
+use derive_more::{Deref, DerefMut};
+
+use crate::types::ProtocolVersion;
+use crate::utils::tag;
+use crate::{Error, RequiredVersion};
+
+/// The approximate bitrate of the [`MediaSegment`]s in kilobits per second.
+///
+/// This tag applies to every [`MediaSegment`] between it and the next
+/// [`ExtXBitrate`] tag (or the end of the playlist), that does not have an
+/// [`ExtXByteRange`] tag applied to it.
+///
+/// The effective bitrate of each segment can be queried with
+/// [`MediaPlaylist::bitrates`].
+///
+/// [`MediaSegment`]: crate::MediaSegment
+/// [`ExtXByteRange`]: crate::tags::ExtXByteRange
+/// [`MediaPlaylist::bitrates`]: crate::MediaPlaylist::bitrates
+#[derive(Deref, DerefMut, Default, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ExtXBitrate(u64);
+
+impl ExtXBitrate {
+    pub(crate) const PREFIX: &'static str = "#EXT-X-BITRATE:";
+
+    /// Makes a new [`ExtXBitrate`] tag from the bitrate in kilobits per
+    /// second.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::tags::ExtXBitrate;
+    /// let bitrate = ExtXBitrate::new(1500);
+    /// ```
+    #[must_use]
+    pub const fn new(bitrate: u64) -> Self { Self(bitrate) }
+
+    /// Returns the bitrate in kilobits per second.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::tags::ExtXBitrate;
+    /// let bitrate = ExtXBitrate::new(1500);
+    ///
+    /// assert_eq!(bitrate.bitrate(), 1500);
+    /// ```
+    #[must_use]
+    pub const fn bitrate(self) -> u64 { self.0 }
+}
+
+/// This tag requires [`ProtocolVersion::V1`].
+impl RequiredVersion for ExtXBitrate {
+    fn required_version(&self) -> ProtocolVersion { ProtocolVersion::V1 }
+}
+
+impl fmt::Display for ExtXBitrate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        //
+        write!(f, "{}{}", Self::PREFIX, self.0)
+    }
+}
+
+impl TryFrom<&str> for ExtXBitrate {
+    type Error = Error;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        let input = tag(input, Self::PREFIX)?;
+        let bitrate = input.parse().map_err(|e| Error::invalid_value(input, e))?;
+
+        Ok(Self::new(bitrate))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            ExtXBitrate::new(1500).to_string(),
+            "#EXT-X-BITRATE:1500".to_string()
+        );
+    }
+
+    #[test]
+    fn test_parser() {
+        assert_eq!(
+            ExtXBitrate::new(1500),
+            ExtXBitrate::try_from("#EXT-X-BITRATE:1500").unwrap()
+        );
+
+        for input in &["-1", "1.5", "abc", ""] {
+            assert_eq!(
+                ExtXBitrate::try_from(format!("#EXT-X-BITRATE:{}", input).as_str()),
+                Err(Error::invalid_value(
+                    input,
+                    input.parse::<u64>().unwrap_err()
+                ))
+            );
+        }
+
+        // the error keeps the input:
+        assert_eq!(
+            ExtXBitrate::try_from("#EXT-X-BITRATE:abc")
+                .unwrap_err()
+                .to_string(),
+            "invalid input \"abc\": invalid digit found in string".to_string()
+        );
+    }
+
+    #[test]
+    fn test_required_version() {
+        assert_eq!(
+            ExtXBitrate::new(1500).required_version(),
+            ProtocolVersion::V1
+        );
+    }
+
+    #[test]
+    fn test_deref() {
+        assert_eq!(*ExtXBitrate::new(1500), 1500);
+    }
+}
@@ -4,6 +4,8 @@ use std::fmt;
 use core::ops::{Add, AddAssign, Sub, SubAssign};
 
 use derive_more::{AsMut, AsRef, Deref, DerefMut, From};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 use crate::types::{ByteRange, ProtocolVersion};
 use crate::utils::tag;
@@ -34,6 +36,7 @@ use crate::{Error, RequiredVersion};
 #[derive(
     AsRef, AsMut, From, Deref, DerefMut, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord,
 )]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[from(forward)]
 pub struct ExtXByteRange(ByteRange);
 
@@ -114,6 +117,31 @@ impl ExtXByteRange {
     #[must_use]
     pub fn saturating_sub(self, num: usize) -> Self { Self(self.0.saturating_sub(num)) }
 
+    /// Returns an [`ExtXByteRange`] with the given length, that starts
+    /// directly after the end of this range.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::tags::ExtXByteRange;
+    /// let range = ExtXByteRange::from(10..22);
+    ///
+    /// assert_eq!(range.following(8), ExtXByteRange::from(22..30));
+    /// assert_eq!(range.following(8).following(4), ExtXByteRange::from(30..34));
+    /// ```
+    ///
+    /// # Note
+    ///
+    /// A range without a `start` is assumed to start at 0 and the end of the
+    /// returned range saturates at `usize::max_value()`.
+    #[inline]
+    #[must_use]
+    pub fn following(&self, length: usize) -> Self {
+        let start = self.0.end();
+
+        Self::from(start..start.saturating_add(length))
+    }
+
     /// Returns a shared reference to the underlying [`ByteRange`].
     ///
     /// # Example
@@ -228,6 +256,26 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_following() {
+        assert_eq!(
+            ExtXByteRange::from(0..100).following(50),
+            ExtXByteRange::from(100..150)
+        );
+        assert_eq!(
+            ExtXByteRange::from(..100).following(50),
+            ExtXByteRange::from(100..150)
+        );
+        assert_eq!(
+            ExtXByteRange::from(20..20).following(0),
+            ExtXByteRange::from(20..20)
+        );
+        assert_eq!(
+            ExtXByteRange::from(10..usize::max_value()).following(1),
+            ExtXByteRange::from(usize::max_value()..usize::max_value())
+        );
+    }
+
     #[test]
     fn test_deref() {
         let byte_range = ExtXByteRange::from(0..22);
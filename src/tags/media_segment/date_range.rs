@@ -1,5 +1,4 @@
 use std::borrow::Cow;
-use std::collections::BTreeMap;
 use std::convert::TryFrom;
 use std::fmt;
 use std::time::Duration;
@@ -7,12 +6,16 @@ use std::time::Duration;
 #[cfg(feature = "chrono")]
 use chrono::{DateTime, FixedOffset, SecondsFormat};
 use derive_builder::Builder;
+#[cfg(feature = "serde")]
+use serde::de::{self, Deserialize, Deserializer, MapAccess, Visitor};
+#[cfg(feature = "serde")]
+use serde::ser::{self, Serialize, SerializeMap, SerializeStruct, Serializer};
 use shorthand::ShortHand;
 
 use crate::attribute::AttributePairs;
-use crate::types::{ProtocolVersion, Value};
-use crate::utils::{quote, tag, unquote};
-use crate::{Error, RequiredVersion};
+use crate::types::{Cue, Float, ProtocolVersion, Value};
+use crate::utils::{parse_duration, quote, tag, unquote, BoolExt};
+use crate::{Error, ParseOptions, RequiredVersion, Warning};
 
 /// The [`ExtXDateRange`] tag associates a date range (i.e., a range of time
 /// defined by a starting and ending date) with a set of attribute/value pairs.
@@ -58,6 +61,15 @@ pub struct ExtXDateRange<'a> {
     #[cfg(not(feature = "chrono"))]
     #[builder(setter(strip_option), default)]
     start_date: Option<Cow<'a, str>>,
+    /// Indicates when an interstitial should be triggered relative to the
+    /// playback of the primary asset.
+    ///
+    /// ## Note
+    ///
+    /// This field is optional.
+    #[builder(setter(strip_option), default)]
+    #[shorthand(enable(copy), disable(into))]
+    cue: Option<Cue>,
     /// The date at which the [`ExtXDateRange`] ends. It must be equal to or
     /// later than the value of the [`start-date`] attribute.
     ///
@@ -175,12 +187,30 @@ pub struct ExtXDateRange<'a> {
     /// An example of a client-defined attribute is
     /// `X-COM-EXAMPLE-AD-ID="XYZ123"`.
     ///
+    /// The attributes are kept in the order in which they have been inserted
+    /// (or parsed), so they are written in the same order.
+    ///
     /// ## Note
     ///
     /// This field is optional.
     #[builder(default)]
-    #[shorthand(enable(collection_magic), disable(set, get))]
-    pub client_attributes: BTreeMap<Cow<'a, str>, Value<'a>>,
+    #[shorthand(disable(set, get))]
+    pub client_attributes: Vec<(Cow<'a, str>, Value<'a>)>,
+}
+
+/// Inserts the attribute at the end of the list or replaces the value of an
+/// attribute with the same key, keeping its position.
+fn insert_attribute<'a>(
+    attributes: &mut Vec<(Cow<'a, str>, Value<'a>)>,
+    key: Cow<'a, str>,
+    value: Value<'a>,
+) -> Option<Value<'a>> {
+    if let Some((_, old)) = attributes.iter_mut().find(|(k, _)| *k == key) {
+        Some(std::mem::replace(old, value))
+    } else {
+        attributes.push((key, value));
+        None
+    }
 }
 
 impl<'a> ExtXDateRangeBuilder<'a> {
@@ -190,9 +220,9 @@ impl<'a> ExtXDateRangeBuilder<'a> {
         key: K,
         value: V,
     ) -> &mut Self {
-        let attrs = self.client_attributes.get_or_insert_with(BTreeMap::new);
+        let attrs = self.client_attributes.get_or_insert_with(Vec::new);
 
-        attrs.insert(key.into(), value.into());
+        insert_attribute(attrs, key.into(), value.into());
 
         self
     }
@@ -245,6 +275,7 @@ let date_range = ExtXDateRange::new("id", "2010-02-19T14:54:23.031+08:00");
             start_date: Some(start_date),
             #[cfg(not(feature = "chrono"))]
             start_date: Some(start_date.into()),
+            cue: None,
             end_date: None,
             duration: None,
             planned_duration: None,
@@ -252,7 +283,7 @@ let date_range = ExtXDateRange::new("id", "2010-02-19T14:54:23.031+08:00");
             scte35_out: None,
             scte35_in: None,
             end_on_next: false,
-            client_attributes: BTreeMap::new(),
+            client_attributes: vec![],
         }
     }
 
@@ -329,6 +360,7 @@ let date_range = ExtXDateRange::builder()
             start_date: self.start_date.map(|v| Cow::Owned(v.into_owned())),
             #[cfg(feature = "chrono")]
             start_date: self.start_date,
+            cue: self.cue,
             #[cfg(not(feature = "chrono"))]
             end_date: self.end_date.map(|v| Cow::Owned(v.into_owned())),
             #[cfg(feature = "chrono")]
@@ -347,6 +379,293 @@ let date_range = ExtXDateRange::builder()
             planned_duration: self.planned_duration,
         }
     }
+
+    /// Returns the date at which the [`ExtXDateRange`] ends.
+    ///
+    /// This is the [`ExtXDateRange::end_date`] if it is present, otherwise the
+    /// [`ExtXDateRange::start_date`] plus the [`ExtXDateRange::duration`].
+    /// If neither is available `None` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::tags::ExtXDateRange;
+    /// use chrono::{FixedOffset, TimeZone};
+    /// use std::convert::TryFrom;
+    ///
+    /// let date_range = ExtXDateRange::try_from(
+    ///     "#EXT-X-DATERANGE:ID=\"ad\",START-DATE=\"2014-03-05T11:15:00Z\",DURATION=60",
+    /// )?;
+    ///
+    /// assert_eq!(
+    ///     date_range.computed_end(),
+    ///     Some(FixedOffset::east(0).ymd(2014, 3, 5).and_hms(11, 16, 0))
+    /// );
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    #[cfg(feature = "chrono")]
+    #[must_use]
+    pub fn computed_end(&self) -> Option<DateTime<FixedOffset>> {
+        if let Some(end_date) = self.end_date {
+            return Some(end_date);
+        }
+
+        let duration = chrono::Duration::from_std(self.duration?).ok()?;
+
+        self.start_date?.checked_add_signed(duration)
+    }
+
+    /// Returns the [`ExtXDateRange::scte35_cmd`] encoded as base64, which is
+    /// the format expected by most ad decision services.
+    ///
+    /// # Errors
+    ///
+    /// This function fails, if the attribute is not a valid hexadecimal
+    /// sequence.
+    #[cfg(feature = "base64")]
+    pub fn scte35_cmd_base64(&self) -> crate::Result<Option<String>> {
+        Self::scte35_base64(self.scte35_cmd.as_ref())
+    }
+
+    /// Returns the [`ExtXDateRange::scte35_out`] encoded as base64, which is
+    /// the format expected by most ad decision services.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::tags::ExtXDateRange;
+    /// use std::convert::TryFrom;
+    ///
+    /// let date_range =
+    ///     ExtXDateRange::try_from("#EXT-X-DATERANGE:ID=\"splice-6FFFFFF0\",SCTE35-OUT=0xFC3011")?;
+    ///
+    /// assert_eq!(date_range.scte35_out_base64()?, Some("/DAR".to_string()));
+    /// assert_eq!(date_range.scte35_in_base64()?, None);
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function fails, if the attribute is not a valid hexadecimal
+    /// sequence.
+    #[cfg(feature = "base64")]
+    pub fn scte35_out_base64(&self) -> crate::Result<Option<String>> {
+        Self::scte35_base64(self.scte35_out.as_ref())
+    }
+
+    /// Returns the [`ExtXDateRange::scte35_in`] encoded as base64, which is
+    /// the format expected by most ad decision services.
+    ///
+    /// # Errors
+    ///
+    /// This function fails, if the attribute is not a valid hexadecimal
+    /// sequence.
+    #[cfg(feature = "base64")]
+    pub fn scte35_in_base64(&self) -> crate::Result<Option<String>> {
+        Self::scte35_base64(self.scte35_in.as_ref())
+    }
+
+    #[cfg(any(feature = "base64", feature = "serde"))]
+    fn scte35_base64(value: Option<&Cow<'_, str>>) -> crate::Result<Option<String>> {
+        value
+            .map(|value| {
+                let value = value.trim_start_matches("0x").trim_start_matches("0X");
+                hex::decode(value).map(base64::encode).map_err(Error::hex)
+            })
+            .transpose()
+    }
+
+    /// Returns the `X-ASSET-URI` client attribute, which is the uri of a single
+    /// interstitial asset.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::tags::ExtXDateRange;
+    /// use std::convert::TryFrom;
+    ///
+    /// let date_range = ExtXDateRange::try_from(concat!(
+    ///     "#EXT-X-DATERANGE:ID=\"ad1\",CLASS=\"com.apple.hls.interstitial\",",
+    ///     "X-ASSET-URI=\"http://example.com/ad1.m3u8\""
+    /// ))?;
+    ///
+    /// assert_eq!(date_range.asset_uri(), Some("http://example.com/ad1.m3u8"));
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    #[must_use]
+    pub fn asset_uri(&self) -> Option<&str> { self.client_attribute_str("X-ASSET-URI") }
+
+    /// Returns the `X-ASSET-LIST` client attribute, which is the uri of a
+    /// JSON object, that lists the interstitial assets.
+    #[must_use]
+    pub fn asset_list(&self) -> Option<&str> { self.client_attribute_str("X-ASSET-LIST") }
+
+    /// Returns the `X-RESUME-OFFSET` client attribute, which specifies where
+    /// the playback of the primary asset should resume after the interstitial.
+    ///
+    /// ## Note
+    ///
+    /// A negative offset is not representable by a [`Duration`] and will be
+    /// returned as `None`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::tags::ExtXDateRange;
+    /// use std::convert::TryFrom;
+    /// use std::time::Duration;
+    ///
+    /// let date_range = ExtXDateRange::try_from(
+    ///     "#EXT-X-DATERANGE:ID=\"ad1\",X-RESUME-OFFSET=0,X-PLAYOUT-LIMIT=15.5",
+    /// )?;
+    ///
+    /// assert_eq!(date_range.resume_offset(), Some(Duration::from_secs(0)));
+    /// assert_eq!(
+    ///     date_range.playout_limit(),
+    ///     Some(Duration::from_millis(15_500))
+    /// );
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    #[must_use]
+    pub fn resume_offset(&self) -> Option<Duration> {
+        self.client_attribute_duration("X-RESUME-OFFSET")
+    }
+
+    /// Returns the `X-PLAYOUT-LIMIT` client attribute, which limits the
+    /// playback time of the interstitial.
+    #[must_use]
+    pub fn playout_limit(&self) -> Option<Duration> {
+        self.client_attribute_duration("X-PLAYOUT-LIMIT")
+    }
+
+    /// Returns the `X-SNAP` client attribute, which is a list of `OUT` and/or
+    /// `IN`, that indicates if the client should locate the segment boundary
+    /// closest to the start and/or end of the interstitial.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::tags::ExtXDateRange;
+    /// use std::convert::TryFrom;
+    ///
+    /// let date_range = ExtXDateRange::try_from("#EXT-X-DATERANGE:ID=\"ad1\",X-SNAP=\"OUT,IN\"")?;
+    ///
+    /// assert_eq!(date_range.snap(), Some("OUT,IN"));
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    #[must_use]
+    pub fn snap(&self) -> Option<&str> { self.client_attribute_str("X-SNAP") }
+
+    /// Returns the value of the client attribute with the given key.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::tags::ExtXDateRange;
+    /// use hls_m3u8::types::Value;
+    /// use std::convert::TryFrom;
+    ///
+    /// let date_range = ExtXDateRange::try_from(concat!(
+    ///     "#EXT-X-DATERANGE:ID=\"ad\",START-DATE=\"2010-02-19T14:54:23.031+08:00\",",
+    ///     "X-COM-EXAMPLE-AD-ID=\"XYZ123\""
+    /// ))?;
+    ///
+    /// assert_eq!(
+    ///     date_range.client_attribute("X-COM-EXAMPLE-AD-ID"),
+    ///     Some(&Value::String("XYZ123".into()))
+    /// );
+    /// assert_eq!(date_range.client_attribute("X-MISSING"), None);
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    #[must_use]
+    pub fn client_attribute(&self, key: &str) -> Option<&Value<'a>> {
+        self.client_attributes
+            .iter()
+            .find_map(|(k, v)| (k == key).athen_some(v))
+    }
+
+    /// Inserts a client attribute and returns the previous value of the
+    /// attribute, if it has been present.
+    ///
+    /// A new attribute is appended to the end of the
+    /// [`ExtXDateRange::client_attributes`], while an existing attribute keeps
+    /// its position.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::tags::ExtXDateRange;
+    /// use hls_m3u8::types::Value;
+    /// use std::convert::TryFrom;
+    ///
+    /// let mut date_range = ExtXDateRange::try_from(
+    ///     "#EXT-X-DATERANGE:ID=\"ad\",START-DATE=\"2010-02-19T14:54:23.031+08:00\"",
+    /// )?;
+    ///
+    /// assert_eq!(
+    ///     date_range.insert_client_attribute("X-B", "1".to_string()),
+    ///     None
+    /// );
+    /// assert_eq!(
+    ///     date_range.insert_client_attribute("X-A", "2".to_string()),
+    ///     None
+    /// );
+    /// assert_eq!(
+    ///     date_range.insert_client_attribute("X-B", "3".to_string()),
+    ///     Some(Value::from("1".to_string()))
+    /// );
+    ///
+    /// assert_eq!(
+    ///     date_range.client_attributes,
+    ///     vec![
+    ///         ("X-B".into(), Value::from("3".to_string())),
+    ///         ("X-A".into(), Value::from("2".to_string())),
+    ///     ]
+    /// );
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    pub fn insert_client_attribute<K, V>(&mut self, key: K, value: V) -> Option<Value<'a>>
+    where
+        K: Into<Cow<'a, str>>,
+        V: Into<Value<'a>>,
+    {
+        insert_attribute(&mut self.client_attributes, key.into(), value.into())
+    }
+
+    /// Returns `true`, if an attribute is present in both [`ExtXDateRange`]s,
+    /// but has a different value.
+    pub(crate) fn conflicts_with(&self, other: &Self) -> bool {
+        fn differs<T: PartialEq>(left: &Option<T>, right: &Option<T>) -> bool {
+            matches!((left, right), (Some(left), Some(right)) if left != right)
+        }
+
+        differs(&self.class, &other.class)
+            || differs(&self.start_date, &other.start_date)
+            || differs(&self.cue, &other.cue)
+            || differs(&self.end_date, &other.end_date)
+            || differs(&self.duration, &other.duration)
+            || differs(&self.planned_duration, &other.planned_duration)
+            || differs(&self.scte35_cmd, &other.scte35_cmd)
+            || differs(&self.scte35_out, &other.scte35_out)
+            || differs(&self.scte35_in, &other.scte35_in)
+            || self.client_attributes.iter().any(|(key, value)| {
+                other
+                    .client_attribute(key)
+                    .map_or(false, |other| other != value)
+            })
+    }
+
+    fn client_attribute_str(&self, key: &str) -> Option<&str> {
+        self.client_attribute(key).and_then(Value::as_str)
+    }
+
+    fn client_attribute_duration(&self, key: &str) -> Option<Duration> {
+        self.client_attribute(key)
+            .and_then(Value::as_float)
+            .map(Float::as_f64)
+            // negative and too large values are rejected by `parse_duration`
+            .and_then(|value| parse_duration(&value.to_string()).ok())
+    }
 }
 
 /// This tag requires [`ProtocolVersion::V1`].
@@ -354,15 +673,22 @@ impl<'a> RequiredVersion for ExtXDateRange<'a> {
     fn required_version(&self) -> ProtocolVersion { ProtocolVersion::V1 }
 }
 
-impl<'a> TryFrom<&'a str> for ExtXDateRange<'a> {
-    type Error = Error;
-
-    fn try_from(input: &'a str) -> Result<Self, Self::Error> {
+impl<'a> ExtXDateRange<'a> {
+    /// Parses an [`ExtXDateRange`] with the given [`ParseOptions`].
+    ///
+    /// With [`ParseOptions::lenient`], unknown values of the `CUE` attribute
+    /// are skipped and a [`Warning`] is recorded for each of them.
+    pub(crate) fn parse(
+        input: &'a str,
+        options: ParseOptions,
+        warnings: &mut Vec<Warning>,
+    ) -> crate::Result<Self> {
         let input = tag(input, Self::PREFIX)?;
 
         let mut id = None;
         let mut class = None;
         let mut start_date = None;
+        let mut cue = None;
         let mut end_date = None;
         let mut duration = None;
         let mut planned_duration = None;
@@ -371,7 +697,7 @@ impl<'a> TryFrom<&'a str> for ExtXDateRange<'a> {
         let mut scte35_in = None;
         let mut end_on_next = false;
 
-        let mut client_attributes = BTreeMap::new();
+        let mut client_attributes = vec![];
 
         for (key, value) in AttributePairs::new(input) {
             match key {
@@ -387,6 +713,7 @@ impl<'a> TryFrom<&'a str> for ExtXDateRange<'a> {
                         start_date = Some(unquote(value))
                     }
                 }
+                "CUE" => cue = Some(Cue::parse(&unquote(value), options, warnings)?),
                 "END-DATE" => {
                     #[cfg(feature = "chrono")]
                     {
@@ -398,14 +725,10 @@ impl<'a> TryFrom<&'a str> for ExtXDateRange<'a> {
                     }
                 }
                 "DURATION" => {
-                    duration = Some(Duration::from_secs_f64(
-                        value.parse().map_err(|e| Error::parse_float(value, e))?,
-                    ));
+                    duration = Some(parse_duration(value)?);
                 }
                 "PLANNED-DURATION" => {
-                    planned_duration = Some(Duration::from_secs_f64(
-                        value.parse().map_err(|e| Error::parse_float(value, e))?,
-                    ));
+                    planned_duration = Some(parse_duration(value)?);
                 }
                 "SCTE35-CMD" => scte35_cmd = Some(unquote(value)),
                 "SCTE35-OUT" => scte35_out = Some(unquote(value)),
@@ -428,7 +751,11 @@ impl<'a> TryFrom<&'a str> for ExtXDateRange<'a> {
                             ));
                         }
 
-                        client_attributes.insert(Cow::Borrowed(key), Value::try_from(value)?);
+                        insert_attribute(
+                            &mut client_attributes,
+                            Cow::Borrowed(key),
+                            Value::try_from(value)?,
+                        );
                     } else {
                         // [6.3.1. General Client Responsibilities]
                         // > ignore any attribute/value pair with an
@@ -469,6 +796,7 @@ impl<'a> TryFrom<&'a str> for ExtXDateRange<'a> {
             id,
             class,
             start_date,
+            cue,
             end_date,
             duration,
             planned_duration,
@@ -481,6 +809,14 @@ impl<'a> TryFrom<&'a str> for ExtXDateRange<'a> {
     }
 }
 
+impl<'a> TryFrom<&'a str> for ExtXDateRange<'a> {
+    type Error = Error;
+
+    fn try_from(input: &'a str) -> Result<Self, Self::Error> {
+        Self::parse(input, ParseOptions::strict(), &mut vec![])
+    }
+}
+
 impl<'a> fmt::Display for ExtXDateRange<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", Self::PREFIX)?;
@@ -506,6 +842,10 @@ impl<'a> fmt::Display for ExtXDateRange<'a> {
             }
         }
 
+        if let Some(value) = &self.cue {
+            write!(f, ",CUE={}", quote(value))?;
+        }
+
         if let Some(value) = &self.end_date {
             #[cfg(feature = "chrono")]
             {
@@ -554,10 +894,302 @@ impl<'a> fmt::Display for ExtXDateRange<'a> {
     }
 }
 
+/// Serializes the [`ExtXDateRange`] into a structured representation, that
+/// can be consumed by ad systems:
+///
+/// - the dates are serialized as RFC 3339 strings,
+/// - the durations are serialized as seconds,
+/// - the `SCTE35-*` commands are decoded from hex and serialized as base64
+///   strings,
+/// - and the client attributes (`X-<client-attribute>`) are serialized as a
+///   separate object, where strings and numbers are kept as they are and
+///   hexadecimal-sequences are serialized as `{"base64": "<bytes>"}`, so they
+///   can not be confused with strings.
+///
+/// The representation can be deserialized into the same [`ExtXDateRange`].
+///
+/// # Example
+///
+/// ```
+/// # use hls_m3u8::tags::ExtXDateRange;
+/// use std::convert::TryFrom;
+///
+/// let date_range = ExtXDateRange::try_from(concat!(
+///     "#EXT-X-DATERANGE:ID=\"splice-6FFFFFF0\",",
+///     "START-DATE=\"2014-03-05T11:15:00Z\",",
+///     "PLANNED-DURATION=59.993,",
+///     "SCTE35-OUT=0xFC002F0000000000FF00,",
+///     "X-AD-ID=\"XYZ123\""
+/// ))?;
+///
+/// assert_eq!(
+///     serde_json::to_string(&date_range).unwrap(),
+///     concat!(
+///         "{\"id\":\"splice-6FFFFFF0\",",
+///         "\"class\":null,",
+///         "\"start_date\":\"2014-03-05T11:15:00Z\",",
+///         "\"cue\":null,",
+///         "\"end_date\":null,",
+///         "\"duration\":null,",
+///         "\"planned_duration\":59.993,",
+///         "\"scte35_cmd\":null,",
+///         "\"scte35_out\":\"/AAvAAAAAAD/AA==\",",
+///         "\"scte35_in\":null,",
+///         "\"end_on_next\":false,",
+///         "\"client_attributes\":{\"X-AD-ID\":\"XYZ123\"}}"
+///     )
+/// );
+/// # Ok::<(), hls_m3u8::Error>(())
+/// ```
+#[cfg(feature = "serde")]
+impl<'a> Serialize for ExtXDateRange<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        struct ClientAttributes<'b, 'a>(&'b [(Cow<'a, str>, Value<'a>)]);
+
+        impl<'b, 'a> Serialize for ClientAttributes<'b, 'a> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let mut map = serializer.serialize_map(Some(self.0.len()))?;
+
+                for (key, value) in self.0 {
+                    match value {
+                        Value::String(value) => map.serialize_entry(key, value)?,
+                        Value::Hex(value) => {
+                            let base64 = base64::encode(value);
+                            map.serialize_entry(key, &Base64 { base64 })?
+                        }
+                        Value::Float(value) => map.serialize_entry(key, &value.as_f64())?,
+                    }
+                }
+
+                map.end()
+            }
+        }
+
+        fn scte35<E: ser::Error>(value: &Option<Cow<'_, str>>) -> Result<Option<String>, E> {
+            ExtXDateRange::scte35_base64(value.as_ref()).map_err(E::custom)
+        }
+
+        #[cfg(feature = "chrono")]
+        let date =
+            |value: &DateTime<FixedOffset>| value.to_rfc3339_opts(SecondsFormat::AutoSi, true);
+        #[cfg(not(feature = "chrono"))]
+        let date = |value: &Cow<'a, str>| value.to_string();
+
+        let mut result = serializer.serialize_struct("ExtXDateRange", 12)?;
+
+        result.serialize_field("id", &self.id)?;
+        result.serialize_field("class", &self.class)?;
+        result.serialize_field("start_date", &self.start_date.as_ref().map(date))?;
+        result.serialize_field("cue", &self.cue.map(|value| value.to_string()))?;
+        result.serialize_field("end_date", &self.end_date.as_ref().map(date))?;
+        result.serialize_field("duration", &self.duration.map(|d| d.as_secs_f64()))?;
+        result.serialize_field(
+            "planned_duration",
+            &self.planned_duration.map(|d| d.as_secs_f64()),
+        )?;
+        result.serialize_field("scte35_cmd", &scte35(&self.scte35_cmd)?)?;
+        result.serialize_field("scte35_out", &scte35(&self.scte35_out)?)?;
+        result.serialize_field("scte35_in", &scte35(&self.scte35_in)?)?;
+        result.serialize_field("end_on_next", &self.end_on_next)?;
+        result.serialize_field(
+            "client_attributes",
+            &ClientAttributes(&self.client_attributes),
+        )?;
+
+        result.end()
+    }
+}
+
+/// A hexadecimal-sequence client attribute, which is serialized as base64.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Base64 {
+    base64: String,
+}
+
+/// The value of a client attribute, which is either a string, a number or a
+/// hexadecimal-sequence.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum ClientAttributeValue {
+    String(String),
+    Float(f64),
+    Hex(Base64),
+}
+
+/// The client attributes in the order in which they have been serialized.
+#[cfg(feature = "serde")]
+#[derive(Default)]
+struct ClientAttributes(Vec<(String, ClientAttributeValue)>);
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for ClientAttributes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ClientAttributesVisitor;
+
+        impl<'de> Visitor<'de> for ClientAttributesVisitor {
+            type Value = ClientAttributes;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "a map of client attributes")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut result = vec![];
+
+                while let Some(entry) = map.next_entry()? {
+                    result.push(entry);
+                }
+
+                Ok(ClientAttributes(result))
+            }
+        }
+
+        deserializer.deserialize_map(ClientAttributesVisitor)
+    }
+}
+
+/// The serialized representation of an [`ExtXDateRange`].
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct DateRangeRepr {
+    id: String,
+    #[serde(default)]
+    class: Option<String>,
+    #[serde(default)]
+    start_date: Option<String>,
+    #[serde(default)]
+    cue: Option<String>,
+    #[serde(default)]
+    end_date: Option<String>,
+    #[serde(default)]
+    duration: Option<f64>,
+    #[serde(default)]
+    planned_duration: Option<f64>,
+    #[serde(default)]
+    scte35_cmd: Option<String>,
+    #[serde(default)]
+    scte35_out: Option<String>,
+    #[serde(default)]
+    scte35_in: Option<String>,
+    #[serde(default)]
+    end_on_next: bool,
+    #[serde(default)]
+    client_attributes: ClientAttributes,
+}
+
+/// Deserializes the representation, that is produced by the `Serialize`
+/// implementation of [`ExtXDateRange`].
+///
+/// The result is checked like a parsed [`ExtXDateRange`].
+///
+/// # Example
+///
+/// ```
+/// # use hls_m3u8::tags::ExtXDateRange;
+/// use std::convert::TryFrom;
+///
+/// let date_range = ExtXDateRange::try_from(concat!(
+///     "#EXT-X-DATERANGE:ID=\"splice-6FFFFFF0\",",
+///     "START-DATE=\"2014-03-05T11:15:00Z\",",
+///     "PLANNED-DURATION=59.993,",
+///     "SCTE35-OUT=0xFC002F0000000000FF00,",
+///     "X-AD-ID=\"XYZ123\",",
+///     "X-DATA=0x0102"
+/// ))?;
+///
+/// let json = serde_json::to_string(&date_range).unwrap();
+///
+/// assert_eq!(
+///     serde_json::from_str::<ExtXDateRange<'_>>(&json).unwrap(),
+///     date_range
+/// );
+/// # Ok::<(), hls_m3u8::Error>(())
+/// ```
+#[cfg(feature = "serde")]
+impl<'de, 'a> Deserialize<'de> for ExtXDateRange<'a> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        fn scte35<E: de::Error>(value: Option<String>) -> Result<Option<Cow<'static, str>>, E> {
+            value
+                .map(|value| {
+                    base64::decode(value)
+                        .map(|bytes| Cow::Owned(format!("0x{}", hex::encode_upper(bytes))))
+                        .map_err(E::custom)
+                })
+                .transpose()
+        }
+
+        fn seconds<E: de::Error>(value: Option<f64>) -> Result<Option<Duration>, E> {
+            // the shortest representation is parsed, so that for example
+            // `59.993` results in exactly 59.993 seconds
+            value
+                .map(|value| parse_duration(&value.to_string()).map_err(E::custom))
+                .transpose()
+        }
+
+        #[cfg(feature = "chrono")]
+        fn date<E: de::Error>(value: Option<String>) -> Result<Option<DateTime<FixedOffset>>, E> {
+            value
+                .map(|value| DateTime::parse_from_rfc3339(&value).map_err(E::custom))
+                .transpose()
+        }
+
+        #[cfg(not(feature = "chrono"))]
+        #[allow(clippy::unnecessary_wraps)]
+        fn date<E: de::Error>(value: Option<String>) -> Result<Option<Cow<'static, str>>, E> {
+            Ok(value.map(Cow::Owned))
+        }
+
+        let repr = DateRangeRepr::deserialize(deserializer)?;
+
+        let mut client_attributes = vec![];
+
+        for (key, value) in repr.client_attributes.0 {
+            let value = match value {
+                ClientAttributeValue::String(value) => Value::String(Cow::Owned(value)),
+                ClientAttributeValue::Float(value) => {
+                    // the shortest representation is parsed, so that for
+                    // example `1.1` results in the same float as in a playlist
+                    Value::Float(value.to_string().parse().map_err(de::Error::custom)?)
+                }
+                ClientAttributeValue::Hex(Base64 { base64 }) => {
+                    Value::Hex(base64::decode(base64).map_err(de::Error::custom)?)
+                }
+            };
+
+            insert_attribute(&mut client_attributes, Cow::Owned(key), value);
+        }
+
+        let result = Self {
+            id: Cow::Owned(repr.id),
+            class: repr.class.map(Cow::Owned),
+            start_date: date(repr.start_date)?,
+            cue: repr
+                .cue
+                .map(|value| value.parse())
+                .transpose()
+                .map_err(de::Error::custom)?,
+            end_date: date(repr.end_date)?,
+            duration: seconds(repr.duration)?,
+            planned_duration: seconds(repr.planned_duration)?,
+            scte35_cmd: scte35(repr.scte35_cmd)?,
+            scte35_out: scte35(repr.scte35_out)?,
+            scte35_in: scte35(repr.scte35_in)?,
+            end_on_next: repr.end_on_next,
+            client_attributes,
+        };
+
+        // the same rules apply as for a parsed date range
+        ExtXDateRange::try_from(result.to_string().as_str()).map_err(de::Error::custom)?;
+
+        Ok(result)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::types::Float;
     #[cfg(feature = "chrono")]
     use chrono::offset::TimeZone;
     use pretty_assertions::assert_eq;
@@ -582,6 +1214,11 @@ mod test {
                 assert!(ExtXDateRange::try_from("#EXT-X-DATERANGE:END-ON-NEXT=NO")
                     .is_err());
 
+                assert!(ExtXDateRange::try_from(
+                    "#EXT-X-DATERANGE:ID=\"ad1\",CUE=\"PRE,UNKNOWN\""
+                )
+                .is_err());
+
                 assert!(ExtXDateRange::try_from("garbage").is_err());
                 assert!(ExtXDateRange::try_from("").is_err());
 
@@ -675,6 +1312,371 @@ mod test {
                 "X-CUSTOM=45.3",
             )
         },
+        {
+            ExtXDateRange::builder()
+                .id("ad1")
+                .class("com.apple.hls.interstitial")
+                .start_date({
+                    #[cfg(feature = "chrono")]
+                    {
+                        FixedOffset::east(0).ymd(2020, 1, 2).and_hms(21, 55, 44)
+                    }
+                    #[cfg(not(feature = "chrono"))]
+                    {
+                        "2020-01-02T21:55:44Z"
+                    }
+                })
+                .cue(Cue {
+                    is_pre: true,
+                    is_post: false,
+                    is_once: true,
+                })
+                .duration(Duration::from_secs(15))
+                .build()
+                .unwrap(),
+            concat!(
+                "#EXT-X-DATERANGE:",
+                "ID=\"ad1\",",
+                "CLASS=\"com.apple.hls.interstitial\",",
+                "START-DATE=\"2020-01-02T21:55:44Z\",",
+                "CUE=\"PRE,ONCE\",",
+                "DURATION=15"
+            )
+        },
+    }
+
+    #[test]
+    fn test_client_attributes_order() {
+        let input = concat!(
+            "#EXT-X-DATERANGE:",
+            "ID=\"ad\",",
+            "START-DATE=\"2010-02-19T14:54:23.031+08:00\",",
+            "X-B=\"second\",",
+            "X-A=\"first\""
+        );
+
+        let date_range = ExtXDateRange::try_from(input).unwrap();
+
+        assert_eq!(
+            date_range
+                .client_attributes
+                .iter()
+                .map(|(k, _)| k.as_ref())
+                .collect::<Vec<_>>(),
+            vec!["X-B", "X-A"]
+        );
+
+        assert_eq!(date_range.to_string(), input.to_string());
+    }
+
+    #[test]
+    fn test_empty_cue() {
+        let input = concat!(
+            "#EXT-X-DATERANGE:",
+            "ID=\"ad\",",
+            "START-DATE=\"2010-02-19T14:54:23.031+08:00\",",
+            "CUE=\"\""
+        );
+
+        let date_range = ExtXDateRange::try_from(input).unwrap();
+
+        assert_eq!(date_range.cue(), Some(Cue::new()));
+        assert_eq!(date_range.to_string(), input.to_string());
+    }
+
+    #[test]
+    fn test_interstitial_attributes() {
+        let date_range = ExtXDateRange::try_from(concat!(
+            "#EXT-X-DATERANGE:",
+            "ID=\"ad1\",",
+            "CLASS=\"com.apple.hls.interstitial\",",
+            "START-DATE=\"2020-01-02T21:55:44Z\",",
+            "DURATION=15,",
+            "X-ASSET-URI=\"http://example.com/ad1.m3u8\",",
+            "X-ASSET-LIST=\"http://example.com/ads.json\",",
+            "X-RESUME-OFFSET=0,",
+            "X-PLAYOUT-LIMIT=15.5,",
+            "X-SNAP=\"OUT,IN\""
+        ))
+        .unwrap();
+
+        assert_eq!(date_range.asset_uri(), Some("http://example.com/ad1.m3u8"));
+        assert_eq!(date_range.asset_list(), Some("http://example.com/ads.json"));
+        assert_eq!(date_range.resume_offset(), Some(Duration::from_secs(0)));
+        assert_eq!(
+            date_range.playout_limit(),
+            Some(Duration::from_millis(15_500))
+        );
+        assert_eq!(date_range.snap(), Some("OUT,IN"));
+        // the raw values are still available:
+        assert_eq!(
+            date_range.client_attribute("X-PLAYOUT-LIMIT"),
+            Some(&Value::Float(Float::new(15.5)))
+        );
+
+        let date_range = ExtXDateRange::try_from(concat!(
+            "#EXT-X-DATERANGE:",
+            "ID=\"ad2\",",
+            "X-ASSET-URI=15,",
+            "X-RESUME-OFFSET=-1,",
+            "X-PLAYOUT-LIMIT=\"15\""
+        ))
+        .unwrap();
+
+        assert_eq!(date_range.asset_uri(), None);
+        assert_eq!(date_range.asset_list(), None);
+        assert_eq!(date_range.resume_offset(), None);
+        assert_eq!(date_range.playout_limit(), None);
+        assert_eq!(date_range.snap(), None);
+
+        // the durations are as precise as written in the playlist:
+        assert_eq!(
+            ExtXDateRange::try_from("#EXT-X-DATERANGE:ID=\"ad3\",X-PLAYOUT-LIMIT=10.1")
+                .unwrap()
+                .playout_limit(),
+            Some(Duration::from_millis(10_100))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serialize() {
+        let date_range = ExtXDateRange::try_from(concat!(
+            "#EXT-X-DATERANGE:",
+            "ID=\"splice-6FFFFFF0\",",
+            "CLASS=\"com.example.ad\",",
+            "START-DATE=\"2014-03-05T11:15:00Z\",",
+            "CUE=\"PRE,ONCE\",",
+            "DURATION=59.993,",
+            "SCTE35-CMD=0xFC002F,",
+            "SCTE35-IN=0xfc00ff,",
+            "X-AD-ID=\"XYZ123\",",
+            "X-LEVEL=1.5,",
+            "X-RATIO=1.1,",
+            "X-DATA=0x0102"
+        ))
+        .unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&date_range).unwrap(),
+            serde_json::json!({
+                "id": "splice-6FFFFFF0",
+                "class": "com.example.ad",
+                "start_date": "2014-03-05T11:15:00Z",
+                "cue": "PRE,ONCE",
+                "end_date": null,
+                "duration": 59.993,
+                "planned_duration": null,
+                "scte35_cmd": "/AAv",
+                "scte35_out": null,
+                "scte35_in": "/AD/",
+                "end_on_next": false,
+                "client_attributes": {
+                    "X-AD-ID": "XYZ123",
+                    "X-LEVEL": 1.5,
+                    "X-RATIO": 1.1,
+                    "X-DATA": { "base64": "AQI=" }
+                }
+            })
+        );
+
+        // the SCTE-35 commands must be valid hex:
+        assert!(serde_json::to_value(
+            &ExtXDateRange::try_from("#EXT-X-DATERANGE:ID=\"id\",SCTE35-OUT=0xFC0").unwrap()
+        )
+        .is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_deserialize() {
+        let date_range = ExtXDateRange::try_from(concat!(
+            "#EXT-X-DATERANGE:",
+            "ID=\"splice-6FFFFFF0\",",
+            "CLASS=\"com.example.ad\",",
+            "START-DATE=\"2014-03-05T11:15:00Z\",",
+            "END-DATE=\"2014-03-05T11:15:59.993Z\",",
+            "CUE=\"PRE,ONCE\",",
+            "DURATION=59.993,",
+            "PLANNED-DURATION=59.9999999,",
+            "SCTE35-CMD=0xFC002F,",
+            "SCTE35-IN=0xFC00FF,",
+            "X-AD-ID=\"XYZ123\",",
+            "X-LEVEL=1.5,",
+            "X-RATIO=1.1,",
+            "X-DATA=0x0102,",
+            "X-BASE64=\"AQI=\""
+        ))
+        .unwrap();
+
+        let json = serde_json::to_string(&date_range).unwrap();
+
+        // hexadecimal-sequences and strings are kept apart:
+        assert_eq!(
+            serde_json::from_str::<ExtXDateRange<'_>>(&json).unwrap(),
+            date_range
+        );
+
+        // the same rules apply as for a parsed date range:
+        let end_on_next_without_class = serde_json::json!({
+            "id": "id",
+            "end_on_next": true
+        });
+
+        assert!(serde_json::from_value::<ExtXDateRange<'_>>(end_on_next_without_class).is_err());
+
+        let invalid_base64 = serde_json::json!({
+            "id": "id",
+            "scte35_out": "not base64!"
+        });
+
+        assert!(serde_json::from_value::<ExtXDateRange<'_>>(invalid_base64).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "chrono")]
+    fn test_computed_end() {
+        let end = FixedOffset::east(0).ymd(2014, 3, 5).and_hms(11, 16, 0);
+
+        // END-DATE
+        assert_eq!(
+            ExtXDateRange::try_from(concat!(
+                "#EXT-X-DATERANGE:ID=\"id\",START-DATE=\"2014-03-05T11:15:00Z\",",
+                "END-DATE=\"2014-03-05T11:16:00Z\""
+            ))
+            .unwrap()
+            .computed_end(),
+            Some(end)
+        );
+
+        // START-DATE + DURATION
+        assert_eq!(
+            ExtXDateRange::try_from(concat!(
+                "#EXT-X-DATERANGE:ID=\"id\",START-DATE=\"2014-03-05T11:15:00Z\",",
+                "DURATION=60"
+            ))
+            .unwrap()
+            .computed_end(),
+            Some(end)
+        );
+
+        // only a start date
+        assert_eq!(
+            ExtXDateRange::try_from(
+                "#EXT-X-DATERANGE:ID=\"id\",START-DATE=\"2014-03-05T11:15:00Z\""
+            )
+            .unwrap()
+            .computed_end(),
+            None
+        );
+
+        // a duration without a start date
+        assert_eq!(
+            ExtXDateRange::try_from("#EXT-X-DATERANGE:ID=\"id\",DURATION=60")
+                .unwrap()
+                .computed_end(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let input = concat!(
+            "#EXT-X-DATERANGE:ID=\"splice-6FFFFFF0\",CLASS=\"com.apple.hls.scte35\",",
+            "START-DATE=\"2014-03-05T11:15:00Z\",PLANNED-DURATION=59.993,",
+            "SCTE35-OUT=0xFC002F0000000000FF000014056FFFFFF000E011622DCAFF00005263620000000000",
+            "0A0008029896F50000008700000000,",
+            "X-COM-EXAMPLE-AD-ID=\"XYZ123\",X-PLAYOUT-LIMIT=15.5"
+        );
+
+        let date_range = ExtXDateRange::try_from(input).unwrap();
+
+        assert_eq!(
+            date_range
+                .client_attributes
+                .iter()
+                .map(|(key, _)| key.as_ref())
+                .collect::<Vec<_>>(),
+            vec!["X-COM-EXAMPLE-AD-ID", "X-PLAYOUT-LIMIT"]
+        );
+
+        let output = date_range.to_string();
+
+        assert_eq!(output, input.to_string());
+        assert_eq!(
+            ExtXDateRange::try_from(output.as_str()).unwrap(),
+            date_range
+        );
+    }
+
+    #[test]
+    fn test_parser_end_on_next() {
+        assert!(ExtXDateRange::try_from(concat!(
+            "#EXT-X-DATERANGE:ID=\"id\",CLASS=\"class\",",
+            "START-DATE=\"2014-03-05T11:15:00Z\",END-ON-NEXT=YES"
+        ))
+        .is_ok());
+
+        assert_eq!(
+            ExtXDateRange::try_from(concat!(
+                "#EXT-X-DATERANGE:ID=\"id\",CLASS=\"class\",",
+                "START-DATE=\"2014-03-05T11:15:00Z\",DURATION=60,END-ON-NEXT=YES"
+            )),
+            Err(Error::unexpected_attribute("DURATION"))
+        );
+
+        assert_eq!(
+            ExtXDateRange::try_from(concat!(
+                "#EXT-X-DATERANGE:ID=\"id\",CLASS=\"class\",",
+                "START-DATE=\"2014-03-05T11:15:00Z\",END-DATE=\"2014-03-05T11:16:00Z\",",
+                "END-ON-NEXT=YES"
+            )),
+            Err(Error::unexpected_attribute("END-DATE"))
+        );
+    }
+
+    #[test]
+    fn test_builder_durations() {
+        let date_range = ExtXDateRange::builder()
+            .id("id")
+            .duration(Duration::from_millis(60_100))
+            .planned_duration(Duration::from_secs(60))
+            .build()
+            .unwrap();
+
+        assert_eq!(date_range.duration, Some(Duration::from_millis(60_100)));
+        assert_eq!(date_range.planned_duration, Some(Duration::from_secs(60)));
+        assert_eq!(
+            date_range.to_string(),
+            "#EXT-X-DATERANGE:ID=\"id\",DURATION=60.1,PLANNED-DURATION=60".to_string()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "base64")]
+    fn test_scte35_base64() {
+        let date_range = ExtXDateRange::try_from(concat!(
+            "#EXT-X-DATERANGE:ID=\"splice-6FFFFFF0\",START-DATE=\"2014-03-05T11:15:00Z\",",
+            "PLANNED-DURATION=59.993,SCTE35-OUT=0xFC302500000000000000FFF01405000000017FEFFE",
+            "00000000FE0052CCF500000000000A0008435545490000013562DBA30A"
+        ))
+        .unwrap();
+
+        assert_eq!(
+            date_range.scte35_out_base64(),
+            Ok(Some(
+                "/DAlAAAAAAAAAP/wFAUAAAABf+/+AAAAAP4AUsz1AAAAAAAKAAhDVUVJAAABNWLbowo=".to_string()
+            ))
+        );
+        assert_eq!(date_range.scte35_cmd_base64(), Ok(None));
+        assert_eq!(date_range.scte35_in_base64(), Ok(None));
+
+        assert!(
+            ExtXDateRange::try_from("#EXT-X-DATERANGE:ID=\"id\",SCTE35-IN=0xFC0")
+                .unwrap()
+                .scte35_in_base64()
+                .is_err()
+        );
     }
 
     #[test]
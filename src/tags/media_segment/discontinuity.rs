@@ -19,7 +19,7 @@ impl RequiredVersion for ExtXDiscontinuity {
 }
 
 impl fmt::Display for ExtXDiscontinuity {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { Self::PREFIX.fmt(f) }
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str(Self::PREFIX) }
 }
 
 impl TryFrom<&str> for ExtXDiscontinuity {
@@ -46,7 +46,12 @@ mod test {
         assert_eq!(
             ExtXDiscontinuity.to_string(),
             "#EXT-X-DISCONTINUITY".to_string(),
-        )
+        );
+
+        assert_eq!(
+            format!("{:>30}", ExtXDiscontinuity),
+            "#EXT-X-DISCONTINUITY".to_string(),
+        );
     }
 
     #[test]
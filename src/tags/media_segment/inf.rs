@@ -4,17 +4,21 @@ use std::fmt;
 use std::time::Duration;
 
 use derive_more::AsRef;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 use crate::types::ProtocolVersion;
-use crate::utils::tag;
+use crate::utils::{format_duration, parse_duration, tag};
 use crate::{Error, RequiredVersion};
 
 /// Specifies the duration of a [`Media Segment`].
 ///
 /// [`Media Segment`]: crate::media_segment::MediaSegment
-#[derive(AsRef, Default, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(AsRef, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ExtInf<'a> {
     #[as_ref]
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_duration"))]
     duration: Duration,
     title: Option<Cow<'a, str>>,
 }
@@ -22,7 +26,8 @@ pub struct ExtInf<'a> {
 impl<'a> ExtInf<'a> {
     pub(crate) const PREFIX: &'static str = "#EXTINF:";
 
-    /// Makes a new [`ExtInf`] tag.
+    /// Makes a new [`ExtInf`] tag with an empty title, which is written as
+    /// `#EXTINF:<duration>,`.
     ///
     /// # Example
     ///
@@ -36,7 +41,7 @@ impl<'a> ExtInf<'a> {
     pub const fn new(duration: Duration) -> Self {
         Self {
             duration,
-            title: None,
+            title: Some(Cow::Borrowed("")),
         }
     }
 
@@ -94,6 +99,13 @@ impl<'a> ExtInf<'a> {
 
     /// Returns the title of the associated media segment.
     ///
+    /// ## Note
+    ///
+    /// An empty title (`#EXTINF:5,`) is `Some("")`, while a tag without the
+    /// comma after the duration (`#EXTINF:5`) has no title (`None`). The
+    /// comma is only written, if there is a title, so that both forms
+    /// round-trip.
+    ///
     /// # Example
     ///
     /// ```
@@ -126,6 +138,32 @@ impl<'a> ExtInf<'a> {
         self
     }
 
+    /// Removes the title of the associated media segment.
+    ///
+    /// ### Note
+    ///
+    /// A tag without a title is written without the comma after the duration,
+    /// which is required by the specification. Use [`ExtInf::set_title`] with
+    /// an empty title instead, if the tag should only have no title text.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::tags::ExtInf;
+    /// use std::time::Duration;
+    ///
+    /// let mut ext_inf = ExtInf::with_title(Duration::from_secs(5), "title");
+    ///
+    /// ext_inf.clear_title();
+    ///
+    /// assert_eq!(ext_inf.title(), &None);
+    /// assert_eq!(ext_inf.to_string(), "#EXTINF:5".to_string());
+    /// ```
+    pub fn clear_title(&mut self) -> &mut Self {
+        self.title = None;
+        self
+    }
+
     /// Makes the struct independent of its lifetime, by taking ownership of all
     /// internal [`Cow`]s.
     ///
@@ -156,10 +194,10 @@ impl<'a> RequiredVersion for ExtInf<'a> {
 impl<'a> fmt::Display for ExtInf<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", Self::PREFIX)?;
-        write!(f, "{},", self.duration.as_secs_f64())?;
+        write!(f, "{}", format_duration(self.duration))?;
 
         if let Some(value) = &self.title {
-            write!(f, "{}", value)?;
+            write!(f, ",{}", value)?;
         }
         Ok(())
     }
@@ -171,18 +209,9 @@ impl<'a> TryFrom<&'a str> for ExtInf<'a> {
     fn try_from(input: &'a str) -> Result<Self, Self::Error> {
         let mut input = tag(input, Self::PREFIX)?.splitn(2, ',');
 
-        let duration = input.next().unwrap();
-        let duration = Duration::from_secs_f64(
-            duration
-                .parse()
-                .map_err(|e| Error::parse_float(duration, e))?,
-        );
+        let duration = parse_duration(input.next().unwrap())?;
 
-        let title = input
-            .next()
-            .map(str::trim)
-            .filter(|value| !value.is_empty())
-            .map(|v| Cow::Borrowed(v));
+        let title = input.next().map(str::trim).map(Cow::Borrowed);
 
         Ok(Self { duration, title })
     }
@@ -192,6 +221,12 @@ impl<'a> From<Duration> for ExtInf<'a> {
     fn from(value: Duration) -> Self { Self::new(value) }
 }
 
+/// The default [`ExtInf`] has a duration of zero and an empty title, like
+/// [`ExtInf::new`].
+impl<'a> Default for ExtInf<'a> {
+    fn default() -> Self { Self::new(Duration::from_secs(0)) }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -217,12 +252,78 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_round_trip() {
+        let seconds = [0, 1, 4, 59, 3_599, 36_000, 86_399, 1_000_000_007];
+        let nanos = [0, 1, 999, 1_000, 333_333_333, 500_000_000, 999_999_999];
+
+        for &secs in &seconds {
+            for &nanos in &nanos {
+                let inf = ExtInf::new(Duration::new(secs, nanos));
+
+                assert_eq!(ExtInf::try_from(inf.to_string().as_str()).unwrap(), inf);
+            }
+        }
+
+        assert_eq!(
+            ExtInf::new(Duration::from_nanos(3_333_333_333)).to_string(),
+            "#EXTINF:3.333333333,".to_string()
+        );
+    }
+
+    #[test]
+    fn test_display_ignores_flags() {
+        let inf = ExtInf::with_title(Duration::from_millis(5500), "title");
+
+        assert_eq!(format!("{:>20}", inf), "#EXTINF:5.5,title".to_string());
+        assert_eq!(format!("{:<30.1}", inf), "#EXTINF:5.5,title".to_string());
+        assert_eq!(format!("{:^8}", inf), inf.to_string());
+    }
+
+    #[test]
+    fn test_empty_title() {
+        // no title:
+        let ext_inf = ExtInf::try_from("#EXTINF:5").unwrap();
+
+        assert_eq!(ext_inf.title(), &None);
+        assert_eq!(ext_inf.to_string(), "#EXTINF:5".to_string());
+        assert_eq!(
+            ExtInf::try_from(ext_inf.to_string().as_str()).unwrap(),
+            ext_inf
+        );
+
+        // trailing comma without a title:
+        let ext_inf = ExtInf::try_from("#EXTINF:5,").unwrap();
+
+        assert_eq!(ext_inf.title(), &Some("".into()));
+        assert_eq!(ext_inf.to_string(), "#EXTINF:5,".to_string());
+        assert_eq!(
+            ExtInf::try_from(ext_inf.to_string().as_str()).unwrap(),
+            ext_inf
+        );
+
+        // `ExtInf::new` has an empty title:
+        let mut ext_inf = ExtInf::new(Duration::from_secs(5));
+        assert_eq!(ext_inf, ExtInf::with_title(Duration::from_secs(5), ""));
+
+        ext_inf.set_title(Some("title"));
+        assert_eq!(ext_inf.to_string(), "#EXTINF:5,title".to_string());
+
+        ext_inf.set_title(Some(""));
+        assert_eq!(ext_inf.title(), &Some("".into()));
+        assert_eq!(ext_inf.to_string(), "#EXTINF:5,".to_string());
+
+        ext_inf.set_title(None::<&str>);
+        assert_eq!(ext_inf.title(), &None);
+        assert_eq!(ext_inf.to_string(), "#EXTINF:5".to_string());
+    }
+
     #[test]
     fn test_parser() {
         // #EXTINF:<duration>,[<title>]
         assert_eq!(
             ExtInf::try_from("#EXTINF:5").unwrap(),
-            ExtInf::new(Duration::from_secs(5))
+            *ExtInf::new(Duration::from_secs(5)).clear_title()
         );
         assert_eq!(
             ExtInf::try_from("#EXTINF:5,").unwrap(),
@@ -230,7 +331,7 @@ mod test {
         );
         assert_eq!(
             ExtInf::try_from("#EXTINF:5.5").unwrap(),
-            ExtInf::new(Duration::from_millis(5500))
+            *ExtInf::new(Duration::from_millis(5500)).clear_title()
         );
         assert_eq!(
             ExtInf::try_from("#EXTINF:5.5,").unwrap(),
@@ -247,15 +348,118 @@ mod test {
 
         assert!(ExtInf::try_from("#EXTINF:").is_err());
         assert!(ExtInf::try_from("#EXTINF:garbage").is_err());
+
+        assert_eq!(ExtInf::try_from("#EXTINF:-5,"), Err(Error::invalid_input()));
+        assert_eq!(
+            ExtInf::try_from("#EXTINF:-0.5,title"),
+            Err(Error::invalid_input())
+        );
+        assert_eq!(
+            ExtInf::try_from("#EXTINF:NaN,"),
+            Err(Error::invalid_input())
+        );
+
+        // durations, that do not fit into a `Duration` are an error
+        assert_eq!(
+            ExtInf::try_from("#EXTINF:999999999999,")
+                .unwrap()
+                .duration(),
+            Duration::from_secs(999_999_999_999)
+        );
+        assert_eq!(
+            ExtInf::try_from("#EXTINF:99999999999999999999999999999,"),
+            Err(Error::invalid_input())
+        );
+        assert_eq!(
+            ExtInf::try_from("#EXTINF:1e30,"),
+            Err(Error::invalid_input())
+        );
+        assert_eq!(
+            ExtInf::try_from("#EXTINF:inf,"),
+            Err(Error::invalid_input())
+        );
+    }
+
+    #[test]
+    fn test_parser_borrows_title() {
+        let input = String::from("#EXTINF:5,title");
+        let ext_inf = ExtInf::try_from(input.as_str()).unwrap();
+
+        // the title is not copied out of the input
+        assert!(matches!(ext_inf.title(), Some(Cow::Borrowed("title"))));
+
+        // until the tag is made independent of the input
+        assert!(matches!(
+            ext_inf.into_owned().title(),
+            Some(Cow::Owned(title)) if title == "title"
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde() {
+        let ext_inf = ExtInf::with_title(Duration::from_millis(4400), "title");
+
+        assert_eq!(
+            serde_json::to_value(&ext_inf).unwrap(),
+            serde_json::json!({ "duration": "4.4", "title": "title" })
+        );
+
+        assert_eq!(
+            serde_json::from_value::<ExtInf<'_>>(serde_json::json!({
+                "duration": "4.4",
+                "title": "title"
+            }))
+            .unwrap(),
+            ext_inf
+        );
+
+        // the duration must be a string:
+        assert!(serde_json::from_value::<ExtInf<'_>>(serde_json::json!({
+            "duration": 4.4,
+            "title": "title"
+        }))
+        .is_err());
+
+        // no precision is lost:
+        let ext_inf = ExtInf::new(Duration::new(1_000_000_007, 333_333_333));
+
+        assert_eq!(
+            serde_json::from_value::<ExtInf<'_>>(serde_json::to_value(&ext_inf).unwrap()).unwrap(),
+            ext_inf
+        );
+
+        assert!(serde_json::from_value::<ExtInf<'_>>(serde_json::json!({
+            "duration": -1.0,
+            "title": null
+        }))
+        .is_err());
     }
 
     #[test]
     fn test_title() {
-        assert_eq!(ExtInf::new(Duration::from_secs(5)).title(), &None);
+        assert_eq!(
+            ExtInf::new(Duration::from_secs(5)).title(),
+            &Some("".into())
+        );
         assert_eq!(
             ExtInf::with_title(Duration::from_secs(5), "title").title(),
             &Some("title".into())
         );
+
+        // a parsed title can be edited in place:
+        let mut ext_inf = ExtInf::try_from("#EXTINF:5,title").unwrap();
+
+        ext_inf.clear_title();
+        assert_eq!(ext_inf.title(), &None);
+        assert_eq!(ext_inf.to_string(), "#EXTINF:5".to_string());
+
+        ext_inf.set_title(Some("new title"));
+        assert_eq!(ext_inf.title(), &Some("new title".into()));
+        assert_eq!(ext_inf.to_string(), "#EXTINF:5,new title".to_string());
+
+        ext_inf.clear_title().clear_title();
+        assert_eq!(ext_inf.title(), &None);
     }
 
     #[test]
@@ -270,6 +474,31 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_required_version_with_title() {
+        // a title does not influence the required version
+        assert_eq!(
+            ExtInf::with_title(Duration::from_secs(4), "title").required_version(),
+            ProtocolVersion::V1
+        );
+        assert_eq!(
+            ExtInf::with_title(Duration::from_millis(4400), "title").required_version(),
+            ProtocolVersion::V3
+        );
+        assert_eq!(
+            ExtInf::try_from("#EXTINF:4.4,title")
+                .unwrap()
+                .required_version(),
+            ProtocolVersion::V3
+        );
+        assert_eq!(
+            ExtInf::try_from("#EXTINF:4,title")
+                .unwrap()
+                .required_version(),
+            ProtocolVersion::V1
+        );
+    }
+
     #[test]
     fn test_from() {
         assert_eq!(
@@ -277,4 +506,10 @@ mod test {
             ExtInf::new(Duration::from_secs(1))
         );
     }
+
+    #[test]
+    fn test_default() {
+        assert_eq!(ExtInf::default(), ExtInf::new(Duration::from_secs(0)));
+        assert_eq!(ExtInf::default().to_string(), "#EXTINF:0,".to_string());
+    }
 }
@@ -1,6 +1,9 @@
 use std::convert::TryFrom;
 use std::fmt;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use crate::types::{DecryptionKey, ProtocolVersion};
 use crate::utils::tag;
 use crate::{Error, RequiredVersion};
@@ -9,6 +12,7 @@ use crate::{Error, RequiredVersion};
 ///
 /// An unencrypted segment should be marked with [`ExtXKey::empty`].
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ExtXKey<'a>(pub Option<DecryptionKey<'a>>);
 
 impl<'a> ExtXKey<'a> {
@@ -321,6 +325,94 @@ mod test {
         },
     }
 
+    #[test]
+    fn test_parser_uri_with_comma() {
+        let input = concat!(
+            "#EXT-X-KEY:METHOD=AES-128,",
+            "URI=\"https://example.com/key?a=1,b=2\",",
+            "IV=0x10ef8f758ca555115584bb5b3c687f52"
+        );
+
+        let key = ExtXKey::try_from(input).unwrap();
+
+        assert_eq!(
+            key,
+            ExtXKey::new(
+                DecryptionKey::builder()
+                    .method(EncryptionMethod::Aes128)
+                    .uri("https://example.com/key?a=1,b=2")
+                    .iv([16, 239, 143, 117, 140, 165, 85, 17, 85, 132, 187, 91, 60, 104, 127, 82])
+                    .build()
+                    .unwrap()
+            )
+        );
+        assert_eq!(key.to_string(), input.to_string());
+    }
+
+    #[test]
+    fn test_attribute_order() {
+        let input = "#EXT-X-KEY:URI=\"x\",METHOD=AES-128";
+        let key = ExtXKey::try_from(input).unwrap();
+
+        assert_eq!(key.to_string(), input.to_string());
+        // the order does not matter for the equality:
+        assert_eq!(
+            key,
+            ExtXKey::new(DecryptionKey::new(EncryptionMethod::Aes128, "x"))
+        );
+
+        let input = concat!(
+            "#EXT-X-KEY:KEYFORMAT=\"identity\",",
+            "IV=0x10ef8f758ca555115584bb5b3c687f52,",
+            "URI=\"https://www.example.com/hls-key/key.bin\",",
+            "METHOD=AES-128"
+        );
+
+        assert_eq!(
+            ExtXKey::try_from(input).unwrap().to_string(),
+            input.to_string()
+        );
+
+        // attributes, that have not been parsed, are appended in their default order:
+        let mut key = ExtXKey::try_from("#EXT-X-KEY:URI=\"x\",METHOD=AES-128").unwrap();
+
+        if let ExtXKey(Some(decryption_key)) = &mut key {
+            decryption_key.format = Some(KeyFormat::Identity);
+        }
+
+        assert_eq!(
+            key.to_string(),
+            "#EXT-X-KEY:URI=\"x\",METHOD=AES-128,KEYFORMAT=\"identity\"".to_string()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde() {
+        let key = ExtXKey::new(
+            DecryptionKey::builder()
+                .method(EncryptionMethod::Aes128)
+                .uri("https://www.example.com/hls-key/key.bin")
+                .iv([
+                    16, 239, 143, 117, 140, 165, 85, 17, 85, 132, 187, 91, 60, 104, 127, 82,
+                ])
+                .format(KeyFormat::Identity)
+                .versions(vec![1, 2, 3])
+                .build()
+                .unwrap(),
+        );
+
+        let json = serde_json::to_string(&key).unwrap();
+
+        assert_eq!(serde_json::from_str::<ExtXKey<'_>>(&json).unwrap(), key);
+
+        assert_eq!(
+            serde_json::from_str::<ExtXKey<'_>>(&serde_json::to_string(&ExtXKey::empty()).unwrap())
+                .unwrap(),
+            ExtXKey::empty()
+        );
+    }
+
     #[test]
     fn test_required_version() {
         assert_eq!(
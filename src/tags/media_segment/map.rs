@@ -2,7 +2,11 @@ use std::borrow::Cow;
 use std::convert::{TryFrom, TryInto};
 use std::fmt;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use shorthand::ShortHand;
+#[cfg(feature = "url")]
+use url::{ParseError, Url};
 
 use crate::attribute::AttributePairs;
 use crate::tags::ExtXKey;
@@ -33,13 +37,32 @@ use crate::{Decryptable, Error, RequiredVersion};
 /// [`EncryptionMethod::Aes128`]: crate::types::EncryptionMethod::Aes128
 /// [`MediaPlaylist`]: crate::MediaPlaylist
 #[derive(ShortHand, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[shorthand(enable(must_use, into))]
 pub struct ExtXMap<'a> {
     /// The `URI` that identifies a resource, that contains the media
     /// initialization section.
     uri: Cow<'a, str>,
     /// The range of the media initialization section.
-    #[shorthand(enable(copy))]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::tags::ExtXMap;
+    /// use hls_m3u8::types::ByteRange;
+    ///
+    /// let mut map = ExtXMap::new("https://prod.mediaspace.com/init.bin");
+    /// # assert_eq!(map.range(), None);
+    ///
+    /// map.set_range(Some(2..11));
+    /// assert_eq!(map.range(), Some(ByteRange::from(2..11)));
+    ///
+    /// if let Some(range) = map.range_mut() {
+    ///     range.set_len(4);
+    /// }
+    /// assert_eq!(map.range(), Some(ByteRange::from(2..6)));
+    /// ```
+    #[shorthand(enable(copy, get_mut))]
     range: Option<ByteRange>,
     #[shorthand(enable(skip))]
     pub(crate) keys: Vec<ExtXKey<'a>>,
@@ -84,6 +107,83 @@ impl<'a> ExtXMap<'a> {
         }
     }
 
+    /// Removes the range from the [`ExtXMap`], which means that the entire
+    /// resource is the media initialization section.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::tags::ExtXMap;
+    /// let map = ExtXMap::with_range("https://prod.mediaspace.com/init.bin", 2..11);
+    ///
+    /// assert_eq!(
+    ///     map.without_range(),
+    ///     ExtXMap::new("https://prod.mediaspace.com/init.bin")
+    /// );
+    /// ```
+    #[must_use]
+    pub fn without_range(mut self) -> Self {
+        self.range = None;
+        self
+    }
+
+    /// Returns the (exclusive) end of the range (`start + length`), if the
+    /// [`ExtXMap`] has a range with an explicit start.
+    ///
+    /// The last byte of the media initialization section is at
+    /// `byte_range_end() - 1`, which is the value required for the `Range`
+    /// header of an http request.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::tags::ExtXMap;
+    /// let map = ExtXMap::with_range("https://prod.mediaspace.com/init.bin", 2..11);
+    /// assert_eq!(map.byte_range_end(), Some(11));
+    ///
+    /// let map = ExtXMap::with_range("https://prod.mediaspace.com/init.bin", ..11);
+    /// assert_eq!(map.byte_range_end(), None);
+    ///
+    /// let map = ExtXMap::new("https://prod.mediaspace.com/init.bin");
+    /// assert_eq!(map.byte_range_end(), None);
+    /// ```
+    #[must_use]
+    pub fn byte_range_end(&self) -> Option<u64> {
+        self.range
+            .filter(|range| range.start().is_some())
+            .map(|range| range.end() as u64)
+    }
+
+    /// Resolves the [`ExtXMap::uri`] against the `base` url, which should be
+    /// the url of the [`MediaPlaylist`].
+    ///
+    /// `URI`s, which are already absolute, replace the `base` url and are
+    /// normalized by [`Url::join`] (for example `HTTP://Example.com/a/../b`
+    /// becomes `http://example.com/b`).
+    ///
+    /// # Errors
+    ///
+    /// This function fails, if the `URI` can not be resolved into a valid url.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::tags::ExtXMap;
+    /// use url::Url;
+    ///
+    /// let map = ExtXMap::new("/init/main.mp4");
+    /// let base = Url::parse("https://www.example.com/video/index.m3u8").unwrap();
+    ///
+    /// assert_eq!(
+    ///     map.resolve_uri(&base).unwrap().as_str(),
+    ///     "https://www.example.com/init/main.mp4"
+    /// );
+    /// ```
+    ///
+    /// [`MediaPlaylist`]: crate::MediaPlaylist
+    #[cfg(feature = "url")]
+    pub fn resolve_uri(&self, base: &Url) -> Result<Url, ParseError> { base.join(&self.uri) }
+
     /// Makes the struct independent of its lifetime, by taking ownership of all
     /// internal [`Cow`]s.
     ///
@@ -148,7 +248,15 @@ impl<'a> TryFrom<&'a str> for ExtXMap<'a> {
 
         for (key, value) in AttributePairs::new(input) {
             match key {
-                "URI" => uri = Some(unquote(value)),
+                "URI" => {
+                    // the URI must be a quoted-string
+                    // https://tools.ietf.org/html/rfc8216#section-4.3.2.5
+                    if value.len() < 2 || !value.starts_with('"') || !value.ends_with('"') {
+                        return Err(Error::invalid_input());
+                    }
+
+                    uri = Some(unquote(value));
+                }
                 "BYTERANGE" => {
                     range = Some(unquote(value).try_into()?);
                 }
@@ -206,6 +314,67 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_parser_quoted_uri() {
+        assert_eq!(
+            ExtXMap::try_from("#EXT-X-MAP:URI=\"foo\""),
+            Ok(ExtXMap::new("foo"))
+        );
+
+        // the URI must be a quoted-string
+        assert_eq!(
+            ExtXMap::try_from("#EXT-X-MAP:URI=foo"),
+            Err(Error::invalid_input())
+        );
+        assert_eq!(
+            ExtXMap::try_from("#EXT-X-MAP:URI=\"foo"),
+            Err(Error::invalid_input())
+        );
+        assert_eq!(
+            ExtXMap::try_from("#EXT-X-MAP:URI=\""),
+            Err(Error::invalid_input())
+        );
+    }
+
+    #[test]
+    fn test_range() {
+        let mut map = ExtXMap::new("foo");
+
+        map.set_range(Some(ByteRange::from(2..11)));
+        assert_eq!(map, ExtXMap::with_range("foo", 2..11));
+
+        *map.range_mut() = Some(ByteRange::from(..5));
+        assert_eq!(
+            map.to_string(),
+            "#EXT-X-MAP:URI=\"foo\",BYTERANGE=\"5\"".to_string()
+        );
+
+        if let Some(range) = map.range_mut() {
+            range.set_start(Some(3));
+        }
+        assert_eq!(map.range(), Some(ByteRange::from(3..5)));
+
+        assert_eq!(map.clone().without_range(), ExtXMap::new("foo"));
+
+        map.set_range(Option::<ByteRange>::None);
+        assert_eq!(map, ExtXMap::new("foo"));
+    }
+
+    #[test]
+    fn test_byte_range_end() {
+        let map = ExtXMap::try_from("#EXT-X-MAP:URI=\"init.mp4\",BYTERANGE=\"1200@0\"").unwrap();
+
+        assert_eq!(map.byte_range_end(), Some(1200));
+
+        let map = ExtXMap::try_from("#EXT-X-MAP:URI=\"init.mp4\",BYTERANGE=\"1200@300\"").unwrap();
+
+        assert_eq!(map.byte_range_end(), Some(1500));
+
+        let map = ExtXMap::try_from("#EXT-X-MAP:URI=\"init.mp4\"").unwrap();
+
+        assert_eq!(map.byte_range_end(), None);
+    }
+
     #[test]
     fn test_required_version() {
         assert_eq!(ExtXMap::new("foo").required_version(), ProtocolVersion::V6);
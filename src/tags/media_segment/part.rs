@@ -0,0 +1,281 @@
+use std::borrow::Cow;
+use std::convert::{TryFrom, TryInto};
+use std::fmt;
+use std::time::Duration;
+
+use shorthand::ShortHand;
+#[cfg(feature = "url")]
+use url::{ParseError, Url};
+
+use crate::attribute::AttributePairs;
+use crate::types::{ByteRange, ProtocolVersion};
+use crate::utils::{format_duration, parse_duration, parse_yes_or_no, quote, tag, unquote};
+use crate::{Error, RequiredVersion};
+
+/// The [`ExtXPart`] tag identifies a partial segment, which is a (smaller)
+/// part of a [`MediaSegment`].
+///
+/// This tag has been introduced in [`draft-pantos-hls-rfc8216bis`] for low
+/// latency streaming.
+///
+/// [`MediaSegment`]: crate::MediaSegment
+/// [`draft-pantos-hls-rfc8216bis`]: https://tools.ietf.org/html/draft-pantos-hls-rfc8216bis
+#[derive(ShortHand, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[shorthand(enable(must_use, into))]
+pub struct ExtXPart<'a> {
+    /// The `URI` of the partial segment.
+    uri: Cow<'a, str>,
+    /// The duration of the partial segment.
+    #[shorthand(enable(copy))]
+    duration: Duration,
+    /// Indicates, that the partial segment contains an independent frame.
+    ///
+    /// ## Note
+    ///
+    /// This field is optional and defaults to `false`.
+    pub is_independent: bool,
+    /// The sub-range of the resource identified by the `URI`.
+    #[shorthand(enable(copy))]
+    byte_range: Option<ByteRange>,
+    /// Indicates, that the partial segment is not available.
+    ///
+    /// ## Note
+    ///
+    /// This field is optional and defaults to `false`.
+    pub is_gap: bool,
+}
+
+impl<'a> ExtXPart<'a> {
+    pub(crate) const PREFIX: &'static str = "#EXT-X-PART:";
+
+    /// Makes a new [`ExtXPart`] tag.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::tags::ExtXPart;
+    /// use std::time::Duration;
+    ///
+    /// let part = ExtXPart::new("part.1.mp4", Duration::from_millis(333));
+    /// ```
+    #[must_use]
+    pub fn new<T: Into<Cow<'a, str>>>(uri: T, duration: Duration) -> Self {
+        Self {
+            uri: uri.into(),
+            duration,
+            is_independent: false,
+            byte_range: None,
+            is_gap: false,
+        }
+    }
+
+    /// Resolves the [`ExtXPart::uri`] against the `base` url, which should be
+    /// the url of the [`MediaPlaylist`].
+    ///
+    /// `URI`s, which are already absolute, replace the `base` url and are
+    /// normalized by [`Url::join`] (for example `HTTP://Example.com/a/../b`
+    /// becomes `http://example.com/b`).
+    ///
+    /// # Errors
+    ///
+    /// This function fails, if the `URI` can not be resolved into a valid url.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::tags::ExtXPart;
+    /// use std::time::Duration;
+    /// use url::Url;
+    ///
+    /// let part = ExtXPart::new("part.1.mp4", Duration::from_millis(333));
+    /// let base = Url::parse("https://www.example.com/video/index.m3u8").unwrap();
+    ///
+    /// assert_eq!(
+    ///     part.resolve_uri(&base).unwrap().as_str(),
+    ///     "https://www.example.com/video/part.1.mp4"
+    /// );
+    /// ```
+    ///
+    /// [`MediaPlaylist`]: crate::MediaPlaylist
+    #[cfg(feature = "url")]
+    pub fn resolve_uri(&self, base: &Url) -> Result<Url, ParseError> { base.join(&self.uri) }
+
+    /// Makes the struct independent of its lifetime, by taking ownership of all
+    /// internal [`Cow`]s.
+    ///
+    /// # Note
+    ///
+    /// This is a relatively expensive operation.
+    #[must_use]
+    pub fn into_owned(self) -> ExtXPart<'static> {
+        ExtXPart {
+            uri: Cow::Owned(self.uri.into_owned()),
+            duration: self.duration,
+            is_independent: self.is_independent,
+            byte_range: self.byte_range,
+            is_gap: self.is_gap,
+        }
+    }
+}
+
+/// This tag requires [`ProtocolVersion::V1`].
+impl<'a> RequiredVersion for ExtXPart<'a> {
+    fn required_version(&self) -> ProtocolVersion { ProtocolVersion::V1 }
+}
+
+impl<'a> fmt::Display for ExtXPart<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", Self::PREFIX)?;
+        write!(f, "DURATION={}", format_duration(self.duration))?;
+        write!(f, ",URI={}", quote(&self.uri))?;
+
+        if self.is_independent {
+            write!(f, ",INDEPENDENT=YES")?;
+        }
+
+        if let Some(value) = &self.byte_range {
+            write!(f, ",BYTERANGE={}", quote(value))?;
+        }
+
+        if self.is_gap {
+            write!(f, ",GAP=YES")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a str> for ExtXPart<'a> {
+    type Error = Error;
+
+    fn try_from(input: &'a str) -> Result<Self, Self::Error> {
+        let input = tag(input, Self::PREFIX)?;
+
+        let mut uri = None;
+        let mut duration = None;
+        let mut is_independent = false;
+        let mut byte_range = None;
+        let mut is_gap = false;
+
+        for (key, value) in AttributePairs::new(input) {
+            match key {
+                "URI" => uri = Some(unquote(value)),
+                "DURATION" => duration = Some(parse_duration(value)?),
+                "INDEPENDENT" => is_independent = parse_yes_or_no(value)?,
+                "BYTERANGE" => byte_range = Some(unquote(value).try_into()?),
+                "GAP" => is_gap = parse_yes_or_no(value)?,
+                _ => {
+                    // [6.3.1. General Client Responsibilities]
+                    // > ignore any attribute/value pair with an unrecognized
+                    // AttributeName.
+                }
+            }
+        }
+
+        let uri = uri.ok_or_else(|| Error::missing_value("URI"))?;
+        let duration = duration.ok_or_else(|| Error::missing_value("DURATION"))?;
+
+        Ok(Self {
+            uri,
+            duration,
+            is_independent,
+            byte_range,
+            is_gap,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    macro_rules! generate_tests {
+        ( $( { $struct:expr, $str:expr } ),+ $(,)* ) => {
+            #[test]
+            fn test_display() {
+                $(
+                    assert_eq!($struct.to_string(), $str.to_string());
+                )+
+            }
+
+            #[test]
+            fn test_parser() {
+                $(
+                    assert_eq!($struct, TryFrom::try_from($str).unwrap());
+                )+
+            }
+        }
+    }
+
+    generate_tests! {
+        {
+            ExtXPart::new("part.1.mp4", Duration::from_millis(500)),
+            "#EXT-X-PART:DURATION=0.5,URI=\"part.1.mp4\""
+        },
+        {
+            {
+                let mut part = ExtXPart::new("part.2.mp4", Duration::from_millis(500));
+                part.is_independent = true;
+                part
+            },
+            "#EXT-X-PART:DURATION=0.5,URI=\"part.2.mp4\",INDEPENDENT=YES"
+        },
+        {
+            {
+                let mut part = ExtXPart::new("part.3.mp4", Duration::from_millis(500));
+                part.is_gap = true;
+                part
+            },
+            "#EXT-X-PART:DURATION=0.5,URI=\"part.3.mp4\",GAP=YES"
+        },
+        {
+            {
+                let mut part = ExtXPart::new("segment.mp4", Duration::from_secs(1));
+                part.set_byte_range(Some(ByteRange::from(10..20)));
+                part.is_independent = true;
+                part
+            },
+            "#EXT-X-PART:DURATION=1,URI=\"segment.mp4\",INDEPENDENT=YES,BYTERANGE=\"10@10\""
+        },
+    }
+
+    #[test]
+    fn test_parser_defaults() {
+        assert_eq!(
+            ExtXPart::try_from(
+                "#EXT-X-PART:DURATION=0.5,URI=\"part.1.mp4\",INDEPENDENT=NO,GAP=NO,UNKNOWN=IGNORED"
+            )
+            .unwrap(),
+            ExtXPart::new("part.1.mp4", Duration::from_millis(500))
+        );
+    }
+
+    #[test]
+    fn test_parser_error() {
+        assert!(ExtXPart::try_from("#EXT-X-PART:DURATION=0.5").is_err());
+        assert!(ExtXPart::try_from("#EXT-X-PART:URI=\"part.1.mp4\"").is_err());
+        assert!(ExtXPart::try_from("#EXT-X-PART:DURATION=-1,URI=\"part.1.mp4\"").is_err());
+        assert!(ExtXPart::try_from("#EXT-X-PART:DURATION=1,URI=\"a\",GAP=MAYBE").is_err());
+        // too large for a `Duration`
+        assert!(ExtXPart::try_from("#EXT-X-PART:DURATION=1e300,URI=\"part.1.mp4\"").is_err());
+        assert!(ExtXPart::try_from("#EXT-X-PART:DURATION=inf,URI=\"part.1.mp4\"").is_err());
+    }
+
+    #[test]
+    fn test_duration_precision() {
+        let input = "#EXT-X-PART:DURATION=0.333333333,URI=\"part.1.mp4\"";
+        let part = ExtXPart::try_from(input).unwrap();
+
+        assert_eq!(part.duration(), Duration::new(0, 333_333_333));
+        assert_eq!(part.to_string(), input.to_string());
+    }
+
+    #[test]
+    fn test_required_version() {
+        assert_eq!(
+            ExtXPart::new("part.1.mp4", Duration::from_secs(1)).required_version(),
+            ProtocolVersion::V1
+        );
+    }
+}
@@ -8,6 +8,8 @@ use std::marker::PhantomData;
 use chrono::{DateTime, FixedOffset, SecondsFormat};
 #[cfg(feature = "chrono")]
 use derive_more::{Deref, DerefMut};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 use crate::types::ProtocolVersion;
 use crate::utils::tag;
@@ -29,15 +31,18 @@ use crate::{Error, RequiredVersion};
 /// [`MediaSegment`]: crate::MediaSegment
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 #[cfg_attr(feature = "chrono", derive(Deref, DerefMut, Copy))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[non_exhaustive]
 pub struct ExtXProgramDateTime<'a> {
     /// The date-time of the first sample of the associated media segment.
     #[cfg(feature = "chrono")]
     #[cfg_attr(feature = "chrono", deref_mut, deref)]
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serde_date_time"))]
     pub date_time: DateTime<FixedOffset>,
     /// The date-time of the first sample of the associated media segment.
     #[cfg(not(feature = "chrono"))]
     pub date_time: Cow<'a, str>,
+    #[cfg_attr(feature = "serde", serde(skip))]
     _p: PhantomData<&'a str>,
 }
 
@@ -85,6 +90,49 @@ impl<'a> ExtXProgramDateTime<'a> {
         }
     }
 
+    /// Formats the tag with the given precision of the fractional seconds.
+    ///
+    /// The offset is always written as `Z` for UTC and as `+hh:mm` otherwise.
+    ///
+    /// ## Note
+    ///
+    /// The [`Display`] implementation always uses [`SecondsFormat::Millis`],
+    /// which is what most packagers output.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::tags::ExtXProgramDateTime;
+    /// use chrono::{SecondsFormat, TimeZone, Utc};
+    ///
+    /// let program_date_time = ExtXProgramDateTime::new(
+    ///     Utc.ymd(2010, 2, 19)
+    ///         .and_hms_micro(14, 54, 23, 31_250)
+    ///         .into(),
+    /// );
+    ///
+    /// assert_eq!(
+    ///     program_date_time.to_string_with(SecondsFormat::Micros),
+    ///     "#EXT-X-PROGRAM-DATE-TIME:2010-02-19T14:54:23.031250Z".to_string()
+    /// );
+    ///
+    /// assert_eq!(
+    ///     program_date_time.to_string_with(SecondsFormat::Secs),
+    ///     "#EXT-X-PROGRAM-DATE-TIME:2010-02-19T14:54:23Z".to_string()
+    /// );
+    /// ```
+    ///
+    /// [`Display`]: core::fmt::Display
+    #[must_use]
+    #[cfg(feature = "chrono")]
+    pub fn to_string_with(&self, format: SecondsFormat) -> String {
+        format!(
+            "{}{}",
+            Self::PREFIX,
+            self.date_time.to_rfc3339_opts(format, true)
+        )
+    }
+
     /// Makes the struct independent of its lifetime, by taking ownership of all
     /// internal [`Cow`]s.
     ///
@@ -175,6 +223,41 @@ mod test {
         );
     }
 
+    #[test]
+    #[cfg(feature = "chrono")]
+    fn test_display_precision() {
+        let program_date_time = ExtXProgramDateTime::new(
+            FixedOffset::east(8 * HOURS_IN_SECS)
+                .ymd(2010, 2, 19)
+                .and_hms_micro(14, 54, 23, 31_415),
+        );
+
+        assert_eq!(
+            program_date_time.to_string(),
+            "#EXT-X-PROGRAM-DATE-TIME:2010-02-19T14:54:23.031+08:00".to_string()
+        );
+
+        assert_eq!(
+            program_date_time.to_string_with(SecondsFormat::Millis),
+            program_date_time.to_string()
+        );
+
+        assert_eq!(
+            program_date_time.to_string_with(SecondsFormat::Micros),
+            "#EXT-X-PROGRAM-DATE-TIME:2010-02-19T14:54:23.031415+08:00".to_string()
+        );
+
+        assert_eq!(
+            ExtXProgramDateTime::new(
+                FixedOffset::east(0)
+                    .ymd(2010, 2, 19)
+                    .and_hms_micro(14, 54, 23, 31_415),
+            )
+            .to_string(),
+            "#EXT-X-PROGRAM-DATE-TIME:2010-02-19T14:54:23.031Z".to_string()
+        );
+    }
+
     #[test]
     fn test_parser() {
         assert_eq!(
@@ -195,6 +278,82 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_round_trip() {
+        for input in &[
+            "#EXT-X-PROGRAM-DATE-TIME:2010-02-19T14:54:23.031Z",
+            "#EXT-X-PROGRAM-DATE-TIME:2010-02-19T14:54:23.031-05:00",
+            "#EXT-X-PROGRAM-DATE-TIME:2010-02-19T14:54:23.031+05:30",
+        ] {
+            assert_eq!(
+                ExtXProgramDateTime::try_from(*input).unwrap().to_string(),
+                input.to_string()
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "chrono")]
+    fn test_parser_offset() {
+        // `Z` and `+00:00` both denote UTC
+        assert_eq!(
+            ExtXProgramDateTime::try_from("#EXT-X-PROGRAM-DATE-TIME:2010-02-19T14:54:23.031Z")
+                .unwrap(),
+            ExtXProgramDateTime::new(
+                FixedOffset::east(0)
+                    .ymd(2010, 2, 19)
+                    .and_hms_milli(14, 54, 23, 31)
+            )
+        );
+
+        assert_eq!(
+            ExtXProgramDateTime::try_from("#EXT-X-PROGRAM-DATE-TIME:2010-02-19T14:54:23.031+00:00")
+                .unwrap(),
+            ExtXProgramDateTime::new(
+                FixedOffset::east(0)
+                    .ymd(2010, 2, 19)
+                    .and_hms_milli(14, 54, 23, 31)
+            )
+        );
+
+        // the offset is kept and not converted to UTC
+        let program_date_time =
+            ExtXProgramDateTime::try_from("#EXT-X-PROGRAM-DATE-TIME:2010-02-19T14:54:23.031-05:00")
+                .unwrap();
+
+        assert_eq!(
+            program_date_time.offset(),
+            &FixedOffset::west(5 * HOURS_IN_SECS)
+        );
+        assert_eq!(
+            program_date_time.to_string(),
+            "#EXT-X-PROGRAM-DATE-TIME:2010-02-19T14:54:23.031-05:00".to_string()
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "serde", feature = "chrono"))]
+    fn test_serde() {
+        let program_date_time = ExtXProgramDateTime::new(
+            FixedOffset::west(5 * HOURS_IN_SECS)
+                .ymd(2010, 2, 19)
+                .and_hms_milli(14, 54, 23, 31),
+        );
+
+        assert_eq!(
+            serde_json::to_value(&program_date_time).unwrap(),
+            serde_json::json!({ "date_time": "2010-02-19T14:54:23.031-05:00" })
+        );
+
+        assert_eq!(
+            serde_json::from_value::<ExtXProgramDateTime<'_>>(serde_json::json!({
+                "date_time": "2010-02-19T14:54:23.031-05:00"
+            }))
+            .unwrap(),
+            program_date_time
+        );
+    }
+
     #[test]
     fn test_required_version() {
         assert_eq!(
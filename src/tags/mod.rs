@@ -11,5 +11,6 @@ pub(crate) mod shared;
 pub use basic::*;
 pub use master_playlist::*;
 pub(crate) use media_playlist::*;
+pub use media_playlist::{ExtXAllowCache, ExtXServerControl};
 pub use media_segment::*;
 pub use shared::*;
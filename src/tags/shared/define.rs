@@ -0,0 +1,219 @@
+use std::borrow::Cow;
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::attribute::AttributePairs;
+use crate::types::ProtocolVersion;
+use crate::utils::{quote, tag, unquote};
+use crate::{Error, RequiredVersion};
+
+/// Provides a variable, that can be referenced in the `URI`s of a playlist
+/// with `{$name}`.
+///
+/// The variables of a [`MediaPlaylist`] can be substituted with
+/// [`MediaPlaylist::normalize_uris`].
+///
+/// [`MediaPlaylist`]: crate::MediaPlaylist
+/// [`MediaPlaylist::normalize_uris`]: crate::MediaPlaylist::normalize_uris
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ExtXDefine<'a> {
+    /// Defines a variable with the given value.
+    Value {
+        /// The name of the variable.
+        name: Cow<'a, str>,
+        /// The value of the variable.
+        value: Cow<'a, str>,
+    },
+    /// Imports a variable, that has been defined in the [`MasterPlaylist`],
+    /// which references the [`MediaPlaylist`].
+    ///
+    /// [`MasterPlaylist`]: crate::MasterPlaylist
+    /// [`MediaPlaylist`]: crate::MediaPlaylist
+    Import {
+        /// The name of the imported variable.
+        name: Cow<'a, str>,
+    },
+}
+
+impl<'a> ExtXDefine<'a> {
+    pub(crate) const PREFIX: &'static str = "#EXT-X-DEFINE:";
+
+    /// Makes a new [`ExtXDefine`] tag, which defines a variable with the
+    /// given value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::tags::ExtXDefine;
+    /// let define = ExtXDefine::new("host", "https://www.example.com");
+    /// ```
+    #[must_use]
+    pub fn new<N, V>(name: N, value: V) -> Self
+    where
+        N: Into<Cow<'a, str>>,
+        V: Into<Cow<'a, str>>,
+    {
+        Self::Value {
+            name: name.into(),
+            value: value.into(),
+        }
+    }
+
+    /// Makes a new [`ExtXDefine`] tag, which imports the variable with the
+    /// given name.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::tags::ExtXDefine;
+    /// let define = ExtXDefine::import("host");
+    /// ```
+    #[must_use]
+    pub fn import<N: Into<Cow<'a, str>>>(name: N) -> Self { Self::Import { name: name.into() } }
+
+    /// Returns the name of the variable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::tags::ExtXDefine;
+    /// assert_eq!(
+    ///     ExtXDefine::new("host", "https://www.example.com").name(),
+    ///     "host"
+    /// );
+    /// assert_eq!(ExtXDefine::import("host").name(), "host");
+    /// ```
+    #[must_use]
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Value { name, .. } | Self::Import { name } => name,
+        }
+    }
+
+    /// Makes the struct independent of its lifetime, by taking ownership of all
+    /// internal [`Cow`]s.
+    ///
+    /// # Note
+    ///
+    /// This is a relatively expensive operation.
+    #[must_use]
+    pub fn into_owned(self) -> ExtXDefine<'static> {
+        match self {
+            Self::Value { name, value } => {
+                ExtXDefine::Value {
+                    name: Cow::Owned(name.into_owned()),
+                    value: Cow::Owned(value.into_owned()),
+                }
+            }
+            Self::Import { name } => {
+                ExtXDefine::Import {
+                    name: Cow::Owned(name.into_owned()),
+                }
+            }
+        }
+    }
+}
+
+/// This tag requires [`ProtocolVersion::V8`].
+impl<'a> RequiredVersion for ExtXDefine<'a> {
+    fn required_version(&self) -> ProtocolVersion { ProtocolVersion::V8 }
+}
+
+impl<'a> fmt::Display for ExtXDefine<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", Self::PREFIX)?;
+
+        match self {
+            Self::Value { name, value } => {
+                write!(f, "NAME={},VALUE={}", quote(name), quote(value))
+            }
+            Self::Import { name } => write!(f, "IMPORT={}", quote(name)),
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a str> for ExtXDefine<'a> {
+    type Error = Error;
+
+    fn try_from(input: &'a str) -> Result<Self, Self::Error> {
+        let input = tag(input, Self::PREFIX)?;
+
+        let mut name = None;
+        let mut value = None;
+        let mut import = None;
+
+        for (key, attribute) in AttributePairs::new(input) {
+            match key {
+                "NAME" => name = Some(unquote(attribute)),
+                "VALUE" => value = Some(unquote(attribute)),
+                "IMPORT" => import = Some(unquote(attribute)),
+                _ => {
+                    // [6.3.1. General Client Responsibilities]
+                    // > ignore any attribute/value pair with an unrecognized
+                    // AttributeName.
+                }
+            }
+        }
+
+        match (name, value, import) {
+            (Some(name), Some(value), None) => Ok(Self::Value { name, value }),
+            (None, None, Some(name)) => Ok(Self::Import { name }),
+            (Some(_), None, None) => Err(Error::missing_value("VALUE")),
+            (None, None, None) => Err(Error::missing_value("NAME")),
+            _ => {
+                Err(Error::custom(
+                    "expected either `NAME` and `VALUE` or `IMPORT` attribute",
+                ))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            ExtXDefine::new("host", "https://www.example.com").to_string(),
+            "#EXT-X-DEFINE:NAME=\"host\",VALUE=\"https://www.example.com\"".to_string()
+        );
+
+        assert_eq!(
+            ExtXDefine::import("host").to_string(),
+            "#EXT-X-DEFINE:IMPORT=\"host\"".to_string()
+        );
+    }
+
+    #[test]
+    fn test_parser() {
+        assert_eq!(
+            ExtXDefine::new("host", "https://www.example.com"),
+            ExtXDefine::try_from("#EXT-X-DEFINE:NAME=\"host\",VALUE=\"https://www.example.com\"")
+                .unwrap()
+        );
+
+        assert_eq!(
+            ExtXDefine::import("host"),
+            ExtXDefine::try_from("#EXT-X-DEFINE:IMPORT=\"host\",UNKNOWN=TAG").unwrap()
+        );
+
+        assert!(ExtXDefine::try_from("#EXT-X-DEFINE:NAME=\"host\"").is_err());
+        assert!(ExtXDefine::try_from("#EXT-X-DEFINE:VALUE=\"value\"").is_err());
+        assert!(ExtXDefine::try_from("#EXT-X-DEFINE:").is_err());
+        assert!(ExtXDefine::try_from(
+            "#EXT-X-DEFINE:NAME=\"host\",VALUE=\"value\",IMPORT=\"host\""
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_required_version() {
+        assert_eq!(
+            ExtXDefine::import("host").required_version(),
+            ProtocolVersion::V8
+        );
+    }
+}
@@ -22,7 +22,7 @@ impl RequiredVersion for ExtXIndependentSegments {
 }
 
 impl fmt::Display for ExtXIndependentSegments {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { Self::PREFIX.fmt(f) }
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str(Self::PREFIX) }
 }
 
 impl TryFrom<&str> for ExtXIndependentSegments {
@@ -1,5 +1,7 @@
+pub(crate) mod define;
 pub(crate) mod independent_segments;
 pub(crate) mod start;
 
+pub use define::*;
 pub(crate) use independent_segments::ExtXIndependentSegments;
 pub use start::*;
@@ -13,6 +13,9 @@ use crate::{Error, RequiredVersion};
 ///
 /// By default, clients should start playback at this point when beginning a
 /// playback session.
+///
+/// The alternate format (`{:#}`) writes the `PRECISE` attribute, even if it
+/// is equal to its default value.
 #[derive(ShortHand, PartialOrd, Debug, Clone, Copy, PartialEq, Eq, Ord, Hash)]
 #[shorthand(enable(must_use))]
 pub struct ExtXStart {
@@ -105,6 +108,8 @@ impl fmt::Display for ExtXStart {
 
         if self.is_precise {
             write!(f, ",PRECISE=YES")?;
+        } else if f.alternate() {
+            write!(f, ",PRECISE=NO")?;
         }
 
         Ok(())
@@ -157,6 +162,11 @@ mod test {
             ExtXStart::with_precise(Float::new(1.23), true).to_string(),
             "#EXT-X-START:TIME-OFFSET=1.23,PRECISE=YES".to_string(),
         );
+
+        assert_eq!(
+            format!("{:#}", ExtXStart::new(Float::new(-1.23))),
+            "#EXT-X-START:TIME-OFFSET=-1.23,PRECISE=NO".to_string(),
+        );
     }
 
     #[test]
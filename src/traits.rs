@@ -1,13 +1,24 @@
 use std::collections::{BTreeMap, HashMap};
+use std::slice;
 
 use stable_vec::StableVec;
 
+use crate::tags::{
+    ExtInf, ExtXBitrate, ExtXByteRange, ExtXDateRange, ExtXMap, ExtXPart, ExtXProgramDateTime,
+};
 use crate::types::{DecryptionKey, ProtocolVersion};
+use crate::MediaSegment;
 
 mod private {
     pub trait Sealed {}
     impl<'a> Sealed for crate::MediaSegment<'a> {}
     impl<'a> Sealed for crate::tags::ExtXMap<'a> {}
+    impl<'a> Sealed for crate::tags::ExtInf<'a> {}
+    impl Sealed for crate::tags::ExtXBitrate {}
+    impl Sealed for crate::tags::ExtXByteRange {}
+    impl<'a> Sealed for crate::tags::ExtXDateRange<'a> {}
+    impl<'a> Sealed for crate::tags::ExtXPart<'a> {}
+    impl<'a> Sealed for crate::tags::ExtXProgramDateTime<'a> {}
 }
 
 /// Signals that a type or some of the asssociated data might need to be
@@ -57,6 +68,101 @@ pub trait Decryptable<'a>: private::Sealed {
     fn is_empty(&self) -> bool { <Self as Decryptable>::len(self) == 0 }
 }
 
+/// A tag, that belongs to a single [`MediaSegment`].
+///
+/// This makes it possible to collect all tags of one kind from a playlist with
+/// [`MediaPlaylist::tags`].
+///
+/// # Note
+///
+/// You are not supposed to implement this trait, therefore it is "sealed".
+///
+/// [`MediaPlaylist::tags`]: crate::MediaPlaylist::tags
+pub trait SegmentTag<'a>: private::Sealed + Sized {
+    /// Returns all tags of this kind, that belong to the given
+    /// [`MediaSegment`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hls_m3u8::tags::{ExtInf, ExtXDateRange};
+    /// use hls_m3u8::{MediaSegment, SegmentTag};
+    /// use std::time::Duration;
+    ///
+    /// let segment = MediaSegment::builder()
+    ///     .duration(Duration::from_secs(10))
+    ///     .uri("segment.ts")
+    ///     .build()?;
+    ///
+    /// assert_eq!(
+    ///     ExtInf::from_segment(&segment),
+    ///     &[ExtInf::new(Duration::from_secs(10))]
+    /// );
+    /// assert!(ExtXDateRange::from_segment(&segment).is_empty());
+    /// # Ok::<(), String>(())
+    /// ```
+    #[must_use]
+    fn from_segment<'b>(segment: &'b MediaSegment<'a>) -> &'b [Self];
+
+    /// Returns all tags of this kind in the given [`ExtXPart`]s of the
+    /// incomplete [`MediaSegment`], which has no `URI` yet (see
+    /// [`MediaPlaylist::incomplete_parts`]).
+    ///
+    /// This is only non-empty for [`ExtXPart`].
+    ///
+    /// [`MediaPlaylist::incomplete_parts`]: crate::MediaPlaylist::incomplete_parts
+    #[must_use]
+    fn from_incomplete_parts<'b>(parts: &'b [ExtXPart<'a>]) -> &'b [Self] {
+        let _ = parts;
+        &[]
+    }
+}
+
+impl<'a> SegmentTag<'a> for ExtInf<'a> {
+    fn from_segment<'b>(segment: &'b MediaSegment<'a>) -> &'b [Self] {
+        slice::from_ref(&segment.duration)
+    }
+}
+
+impl<'a> SegmentTag<'a> for ExtXBitrate {
+    fn from_segment<'b>(segment: &'b MediaSegment<'a>) -> &'b [Self] {
+        segment.bitrate.as_ref().map_or(&[], slice::from_ref)
+    }
+}
+
+impl<'a> SegmentTag<'a> for ExtXByteRange {
+    fn from_segment<'b>(segment: &'b MediaSegment<'a>) -> &'b [Self] {
+        segment.byte_range.as_ref().map_or(&[], slice::from_ref)
+    }
+}
+
+impl<'a> SegmentTag<'a> for ExtXDateRange<'a> {
+    fn from_segment<'b>(segment: &'b MediaSegment<'a>) -> &'b [Self] {
+        segment.date_range.as_ref().map_or(&[], slice::from_ref)
+    }
+}
+
+impl<'a> SegmentTag<'a> for ExtXMap<'a> {
+    fn from_segment<'b>(segment: &'b MediaSegment<'a>) -> &'b [Self] {
+        segment.map.as_ref().map_or(&[], slice::from_ref)
+    }
+}
+
+impl<'a> SegmentTag<'a> for ExtXPart<'a> {
+    fn from_segment<'b>(segment: &'b MediaSegment<'a>) -> &'b [Self] { &segment.parts }
+
+    fn from_incomplete_parts(parts: &[Self]) -> &[Self] { parts }
+}
+
+impl<'a> SegmentTag<'a> for ExtXProgramDateTime<'a> {
+    fn from_segment<'b>(segment: &'b MediaSegment<'a>) -> &'b [Self] {
+        segment
+            .program_date_time
+            .as_ref()
+            .map_or(&[], slice::from_ref)
+    }
+}
+
 #[doc(hidden)]
 pub trait RequiredVersion {
     /// Returns the protocol compatibility version that this tag requires.
@@ -6,6 +6,8 @@ use core::ops::{
 };
 use std::borrow::Cow;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use shorthand::ShortHand;
 
 use crate::Error;
@@ -21,6 +23,7 @@ use crate::Error;
 /// let range = ByteRange::from(..20);
 /// ```
 #[derive(ShortHand, Copy, Hash, Eq, Ord, Debug, PartialEq, Clone, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[shorthand(enable(must_use, copy), disable(option_as_ref, set))]
 pub struct ByteRange {
     /// Returns the `start` of the [`ByteRange`], if there is one.
@@ -247,6 +250,47 @@ impl ByteRange {
     #[inline]
     #[must_use]
     pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    /// Returns `true` if `other` lies completely inside of this range. If the
+    /// `start` of a range is `None` a 0 is assumed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::types::ByteRange;
+    /// let range = ByteRange::from(10..20);
+    ///
+    /// assert!(range.contains(&ByteRange::from(12..20)));
+    /// assert!(!range.contains(&ByteRange::from(5..15)));
+    /// assert!(ByteRange::from(..20).contains(&range));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn contains(&self, other: &Self) -> bool {
+        self.start.unwrap_or(0) <= other.start.unwrap_or(0) && other.end <= self.end
+    }
+
+    /// Returns `true` if this range and `other` have at least one byte in
+    /// common. If the `start` of a range is `None` a 0 is assumed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::types::ByteRange;
+    /// let range = ByteRange::from(10..20);
+    ///
+    /// assert!(range.overlaps(&ByteRange::from(15..25)));
+    /// // the end is exclusive, so adjacent ranges do not overlap:
+    /// assert!(!range.overlaps(&ByteRange::from(20..30)));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn overlaps(&self, other: &Self) -> bool {
+        !self.is_empty()
+            && !other.is_empty()
+            && self.start.unwrap_or(0) < other.end
+            && other.start.unwrap_or(0) < self.end
+    }
 }
 
 impl Sub<usize> for ByteRange {
@@ -415,12 +459,18 @@ impl TryFrom<&str> for ByteRange {
         let mut input = input.splitn(2, '@');
 
         let length = input.next().unwrap();
+        let start = input.next();
+
+        // neither the length nor an explicitly provided start can be empty
+        if length.is_empty() || start == Some("") {
+            return Err(Error::invalid_input());
+        }
+
         let length = length
             .parse::<usize>()
             .map_err(|e| Error::parse_int(length, e))?;
 
-        let start = input
-            .next()
+        let start = start
             .map(|v| v.parse::<usize>().map_err(|e| Error::parse_int(v, e)))
             .transpose()?;
 
@@ -685,6 +735,102 @@ mod tests {
             Err(Error::parse_int("a", "a".parse::<usize>().unwrap_err()))
         );
 
-        assert!(ByteRange::try_from("").is_err());
+        assert_eq!(ByteRange::try_from(""), Err(Error::invalid_input()));
+        assert_eq!(ByteRange::try_from("@5"), Err(Error::invalid_input()));
+        assert_eq!(ByteRange::try_from("3@"), Err(Error::invalid_input()));
+
+        assert_eq!(ByteRange::try_from("3"), Ok(ByteRange::from(..3)));
+        assert_eq!(ByteRange::try_from("3@5"), Ok(ByteRange::from(5..8)));
+    }
+
+    #[test]
+    fn test_contains() {
+        let range = ByteRange::from(10..20);
+
+        // nested
+        assert!(range.contains(&range));
+        assert!(range.contains(&ByteRange::from(12..18)));
+        assert!(range.contains(&ByteRange::from(10..15)));
+        assert!(!ByteRange::from(12..18).contains(&range));
+
+        // adjacent
+        assert!(!range.contains(&ByteRange::from(5..10)));
+        assert!(!range.contains(&ByteRange::from(20..25)));
+
+        // disjoint
+        assert!(!range.contains(&ByteRange::from(30..40)));
+
+        // missing start
+        assert!(ByteRange::from(..20).contains(&range));
+        assert!(ByteRange::from(0..20).contains(&ByteRange::from(..5)));
+        assert!(!range.contains(&ByteRange::from(..15)));
+    }
+
+    #[test]
+    fn test_overlaps() {
+        let range = ByteRange::from(10..20);
+
+        // nested
+        assert!(range.overlaps(&range));
+        assert!(range.overlaps(&ByteRange::from(12..18)));
+        assert!(ByteRange::from(12..18).overlaps(&range));
+
+        // partially
+        assert!(range.overlaps(&ByteRange::from(5..11)));
+        assert!(range.overlaps(&ByteRange::from(19..25)));
+
+        // adjacent
+        assert!(!range.overlaps(&ByteRange::from(5..10)));
+        assert!(!range.overlaps(&ByteRange::from(20..25)));
+
+        // disjoint
+        assert!(!range.overlaps(&ByteRange::from(30..40)));
+
+        // empty
+        assert!(!range.overlaps(&ByteRange::from(15..15)));
+
+        // missing start
+        assert!(range.overlaps(&ByteRange::from(..15)));
+        assert!(!range.overlaps(&ByteRange::from(..10)));
+    }
+
+    #[test]
+    fn test_ord() {
+        let mut ranges = vec![
+            ByteRange::from(10..20),
+            ByteRange::from(0..5),
+            ByteRange::from(10..15),
+            ByteRange::from(..10),
+        ];
+
+        ranges.sort();
+
+        assert_eq!(
+            ranges,
+            vec![
+                ByteRange::from(..10),
+                ByteRange::from(0..5),
+                ByteRange::from(10..15),
+                ByteRange::from(10..20),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_zero_start() {
+        // an explicit start of 0 is distinct from a missing start
+        let range = ByteRange::try_from("3@0").unwrap();
+
+        assert_eq!(range, ByteRange::from(0..3));
+        assert_eq!(range.start(), Some(0));
+        assert_eq!(range.to_string(), "3@0".to_string());
+        assert_eq!(ByteRange::try_from(range.to_string().as_str()), Ok(range));
+
+        let range_without_start = ByteRange::try_from("3").unwrap();
+
+        assert_eq!(range_without_start.start(), None);
+        assert_eq!(range_without_start.to_string(), "3".to_string());
+        assert_ne!(range, range_without_start);
+        assert_eq!(range.len(), range_without_start.len());
     }
 }
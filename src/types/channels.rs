@@ -11,6 +11,17 @@ use crate::Error;
 /// For example, an `AC-3 5.1` rendition would have a maximum channel number of
 /// 6.
 ///
+/// The number can be followed by additional parameters, which are separated by
+/// a `/`, for example `16/JOC` for a Dolby Atmos rendition (`JOC` stands for
+/// Joint Object Coding).
+///
+/// # Note
+///
+/// The parameters are stored inline, so that [`Channels`] can be `Copy`.
+/// Therefore they are limited to 64 bytes in total (including the `/` between
+/// them). Longer parameters are rejected by [`Channels::set_parameters`] and
+/// by the [`FromStr`] implementation.
+///
 /// [`MediaSegment`]: crate::MediaSegment
 #[derive(ShortHand, Debug, Clone, Copy, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
 #[shorthand(enable(must_use))]
@@ -28,6 +39,54 @@ pub struct Channels {
     /// assert_eq!(channels.number(), 5);
     /// ```
     number: u64,
+    /// The parameters, that follow the number of channels, separated by `/`.
+    #[shorthand(enable(skip))]
+    parameters: Option<Parameters>,
+}
+
+/// The maximum length of the parameters of [`Channels`] in bytes (without the
+/// leading `/`).
+const PARAMETERS_CAPACITY: usize = 64;
+
+/// The parameters of [`Channels`], which are stored inline, so that
+/// [`Channels`] can be `Copy`.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+struct Parameters {
+    len: u8,
+    bytes: [u8; PARAMETERS_CAPACITY],
+}
+
+impl Parameters {
+    fn new(value: &str) -> crate::Result<Self> {
+        if value.len() > PARAMETERS_CAPACITY {
+            return Err(Error::custom(format!(
+                "the parameters of the channels must not be longer than {} bytes: {:?}",
+                PARAMETERS_CAPACITY, value
+            )));
+        }
+
+        if value.contains('"') {
+            return Err(Error::invalid_input());
+        }
+
+        let mut bytes = [0; PARAMETERS_CAPACITY];
+        bytes[..value.len()].copy_from_slice(value.as_bytes());
+
+        #[allow(clippy::cast_possible_truncation)]
+        Ok(Self {
+            len: value.len() as u8,
+            bytes,
+        })
+    }
+
+    fn as_str(&self) -> &str {
+        // the bytes have been copied from a `str`
+        core::str::from_utf8(&self.bytes[..usize::from(self.len)]).unwrap_or_default()
+    }
+}
+
+impl fmt::Debug for Parameters {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { self.as_str().fmt(f) }
 }
 
 impl Channels {
@@ -44,16 +103,110 @@ impl Channels {
     /// ```
     //#[inline]
     #[must_use]
-    pub const fn new(number: u64) -> Self { Self { number } }
+    pub const fn new(number: u64) -> Self {
+        Self {
+            number,
+            parameters: None,
+        }
+    }
+
+    /// Returns the parameters, that follow the number of channels, in the
+    /// order in which they appear (for example the audio coding identifiers
+    /// like `JOC` or spatial audio identifiers like `BINAURAL`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::types::Channels;
+    /// let channels = "16/JOC".parse::<Channels>()?;
+    ///
+    /// assert_eq!(channels.number(), 16);
+    /// assert_eq!(channels.parameters().collect::<Vec<_>>(), vec!["JOC"]);
+    ///
+    /// assert_eq!(Channels::new(2).parameters().next(), None);
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    pub fn parameters(&self) -> impl Iterator<Item = &str> + '_ {
+        self.parameters
+            .iter()
+            .flat_map(|parameters| parameters.as_str().split('/'))
+    }
+
+    /// Sets the parameters, that follow the number of channels.
+    ///
+    /// # Errors
+    ///
+    /// The parameters are stored inline, so this function fails, if they are
+    /// longer than 64 bytes in total (including the `/` between them) or if
+    /// a parameter contains a `/` or a `"`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::types::Channels;
+    /// let mut channels = Channels::new(2);
+    ///
+    /// channels.set_parameters(&["-", "BINAURAL"])?;
+    /// assert_eq!(channels.to_string(), "2/-/BINAURAL".to_string());
+    ///
+    /// channels.set_parameters(Vec::<String>::new())?;
+    /// assert_eq!(channels.to_string(), "2".to_string());
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    pub fn set_parameters<I, T>(&mut self, parameters: I) -> crate::Result<&mut Self>
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        let mut value = String::new();
+        let mut is_empty = true;
+
+        for parameter in parameters {
+            let parameter = parameter.as_ref();
+
+            if parameter.contains('/') {
+                return Err(Error::invalid_input());
+            }
+
+            if !is_empty {
+                value.push('/');
+            }
+
+            value.push_str(parameter);
+            is_empty = false;
+        }
+
+        self.parameters = {
+            if is_empty {
+                None
+            } else {
+                Some(Parameters::new(&value)?)
+            }
+        };
+
+        Ok(self)
+    }
 }
 
+/// Parses the number of channels and the optional parameters (for example
+/// `16/JOC`).
+///
+/// # Errors
+///
+/// This fails, if the number of channels is not a valid integer or if the
+/// parameters are longer than 64 bytes or contain a `"`.
 impl FromStr for Channels {
     type Err = Error;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        Ok(Self::new(
-            input.parse().map_err(|e| Error::parse_int(input, e))?,
-        ))
+        let mut fields = input.splitn(2, '/');
+        // `splitn` always returns at least one element
+        let number = fields.next().unwrap_or_default();
+
+        Ok(Self {
+            number: number.parse().map_err(|e| Error::parse_int(number, e))?,
+            parameters: fields.next().map(Parameters::new).transpose()?,
+        })
     }
 }
 
@@ -61,6 +214,10 @@ impl fmt::Display for Channels {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.number)?;
 
+        if let Some(parameters) = &self.parameters {
+            write!(f, "/{}", parameters.as_str())?;
+        }
+
         Ok(())
     }
 }
@@ -75,6 +232,15 @@ mod tests {
         assert_eq!(Channels::new(6).to_string(), "6".to_string());
 
         assert_eq!(Channels::new(7).to_string(), "7".to_string());
+
+        let mut channels = Channels::new(16);
+        channels.set_parameters(&["JOC"]).unwrap();
+        assert_eq!(channels.to_string(), "16/JOC".to_string());
+
+        assert!(channels.set_parameters(&["JOC/BINAURAL"]).is_err());
+        assert!(channels.set_parameters(&["\"JOC\""]).is_err());
+        assert!(channels.set_parameters(&["A".repeat(65)]).is_err());
+        assert_eq!(channels.to_string(), "16/JOC".to_string());
     }
 
     #[test]
@@ -83,5 +249,64 @@ mod tests {
 
         assert!(Channels::from_str("garbage").is_err());
         assert!(Channels::from_str("").is_err());
+        assert!(Channels::from_str("JOC/16").is_err());
+    }
+
+    #[test]
+    fn test_copy() {
+        let channels = Channels::from_str("16/JOC").unwrap();
+        let copy = channels;
+
+        assert_eq!(copy, channels);
+        assert_eq!(format!("{:?}", channels.parameters), "Some(\"JOC\")");
+    }
+
+    #[test]
+    fn test_parser_parameters() {
+        let channels = Channels::from_str("16/JOC").unwrap();
+
+        assert_eq!(channels.number(), 16);
+        assert_eq!(channels.parameters().collect::<Vec<_>>(), vec!["JOC"]);
+        assert_eq!(channels.to_string(), "16/JOC".to_string());
+
+        let channels = Channels::from_str("2/-/BINAURAL").unwrap();
+
+        assert_eq!(channels.number(), 2);
+        assert_eq!(
+            channels.parameters().collect::<Vec<_>>(),
+            vec!["-", "BINAURAL"]
+        );
+        assert_eq!(channels.to_string(), "2/-/BINAURAL".to_string());
+
+        // the parameters are stored inline:
+        assert!(Channels::from_str(&format!("2/{}", "A".repeat(64))).is_ok());
+        assert!(Channels::from_str(&format!("2/{}", "A".repeat(65))).is_err());
+    }
+
+    #[test]
+    fn test_parameters_too_long() {
+        let parameters = format!("{}/{}", "A".repeat(32), "B".repeat(32));
+
+        assert_eq!(
+            Channels::from_str(&format!("2/{}", parameters)),
+            Err(Error::custom(format!(
+                "the parameters of the channels must not be longer than 64 bytes: {:?}",
+                parameters
+            )))
+        );
+
+        // the previous parameters are kept, if the new ones are too long:
+        let mut channels = Channels::from_str("16/JOC").unwrap();
+
+        assert!(channels
+            .set_parameters(&["A".repeat(32), "B".repeat(32)])
+            .is_err());
+        assert_eq!(channels.parameters().collect::<Vec<_>>(), vec!["JOC"]);
+
+        // exactly 64 bytes (including the `/`) are allowed:
+        assert!(channels
+            .set_parameters(&["A".repeat(32), "B".repeat(31)])
+            .is_ok());
+        assert_eq!(channels.to_string().len(), "16/".len() + 64);
     }
 }
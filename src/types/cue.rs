@@ -0,0 +1,210 @@
+use core::fmt;
+use core::str::FromStr;
+
+use crate::{Error, ParseOptions, Warning};
+
+/// The `CUE` attribute of an [`ExtXDateRange`], which indicates when an
+/// interstitial should be triggered.
+///
+/// [`ExtXDateRange`]: crate::tags::ExtXDateRange
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Cue {
+    /// Indicates that the interstitial should be triggered before playback of
+    /// the primary asset (`PRE`).
+    pub is_pre: bool,
+    /// Indicates that the interstitial should be triggered after playback of
+    /// the primary asset (`POST`).
+    pub is_post: bool,
+    /// Indicates that the interstitial should be triggered only once
+    /// (`ONCE`).
+    pub is_once: bool,
+}
+
+impl Cue {
+    /// Makes a new [`Cue`], where no flag is set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::types::Cue;
+    /// let mut cue = Cue::new();
+    ///
+    /// cue.is_pre = true;
+    /// cue.is_once = true;
+    ///
+    /// assert_eq!(cue.to_string(), "PRE,ONCE".to_string());
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            is_pre: false,
+            is_post: false,
+            is_once: false,
+        }
+    }
+
+    /// Returns `true`, if no flag is set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::types::Cue;
+    /// let mut cue = Cue::new();
+    /// assert!(cue.is_empty());
+    ///
+    /// cue.is_post = true;
+    /// assert!(!cue.is_empty());
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn is_empty(&self) -> bool { !(self.is_pre || self.is_post || self.is_once) }
+
+    /// Parses a [`Cue`] with the given [`ParseOptions`].
+    ///
+    /// With [`ParseOptions::lenient`], unknown values are skipped and a
+    /// [`Warning`] is recorded for each of them.
+    pub(crate) fn parse(
+        input: &str,
+        options: ParseOptions,
+        warnings: &mut Vec<Warning>,
+    ) -> crate::Result<Self> {
+        let mut result = Self::new();
+
+        // an empty `CUE` attribute has no flags
+        for value in input.split(',').map(str::trim).filter(|v| !v.is_empty()) {
+            match value {
+                "PRE" => result.is_pre = true,
+                "POST" => result.is_post = true,
+                "ONCE" => result.is_once = true,
+                _ => {
+                    if options.is_lenient {
+                        warnings.push(Warning::UnknownCue {
+                            value: value.to_string(),
+                        });
+                    } else {
+                        return Err(Error::custom(format!("unknown cue: {:?}", value)));
+                    }
+                }
+            }
+        }
+
+        if result.is_pre && result.is_post {
+            return Err(Error::custom("`PRE` and `POST` can not both be present"));
+        }
+
+        Ok(result)
+    }
+}
+
+impl fmt::Display for Cue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let flags = [
+            (self.is_pre, "PRE"),
+            (self.is_post, "POST"),
+            (self.is_once, "ONCE"),
+        ];
+
+        let mut is_first = true;
+
+        for (_, value) in flags.iter().filter(|(is_set, _)| *is_set) {
+            if !is_first {
+                write!(f, ",")?;
+            }
+
+            write!(f, "{}", value)?;
+            is_first = false;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Cue {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Self::parse(input, ParseOptions::strict(), &mut vec![])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Cue::new().to_string(), "".to_string());
+
+        assert_eq!(
+            Cue {
+                is_pre: true,
+                is_post: false,
+                is_once: true,
+            }
+            .to_string(),
+            "PRE,ONCE".to_string()
+        );
+
+        assert_eq!(
+            Cue {
+                is_pre: false,
+                is_post: true,
+                is_once: false,
+            }
+            .to_string(),
+            "POST".to_string()
+        );
+    }
+
+    #[test]
+    fn test_parser() {
+        assert_eq!(
+            Cue::from_str("PRE,ONCE").unwrap(),
+            Cue {
+                is_pre: true,
+                is_post: false,
+                is_once: true,
+            }
+        );
+
+        assert_eq!(
+            Cue::from_str("ONCE, POST").unwrap(),
+            Cue {
+                is_pre: false,
+                is_post: true,
+                is_once: true,
+            }
+        );
+
+        // `Cue::new` is written as an empty string:
+        assert_eq!(Cue::from_str("").unwrap(), Cue::new());
+        assert_eq!(Cue::from_str(&Cue::new().to_string()).unwrap(), Cue::new());
+
+        assert!(Cue::from_str("PRE,MIDDLE").is_err());
+        assert!(Cue::from_str("PRE,POST").is_err());
+    }
+
+    #[test]
+    fn test_parse_lenient() {
+        let mut warnings = vec![];
+
+        assert_eq!(
+            Cue::parse("PRE,MIDDLE", ParseOptions::lenient(), &mut warnings).unwrap(),
+            Cue {
+                is_pre: true,
+                is_post: false,
+                is_once: false,
+            }
+        );
+        assert_eq!(
+            warnings,
+            vec![Warning::UnknownCue {
+                value: "MIDDLE".to_string()
+            }]
+        );
+
+        assert!(Cue::parse("PRE,POST", ParseOptions::lenient(), &mut warnings).is_err());
+    }
+}
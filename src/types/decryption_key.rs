@@ -1,9 +1,15 @@
 use std::borrow::Cow;
+use std::cmp::Ordering;
 use std::convert::TryFrom;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 
 use derive_builder::Builder;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use shorthand::ShortHand;
+#[cfg(feature = "url")]
+use url::{ParseError, Url};
 
 use crate::attribute::AttributePairs;
 use crate::types::{
@@ -14,6 +20,7 @@ use crate::{Error, RequiredVersion};
 
 /// Specifies how to decrypt encrypted data from the server.
 #[derive(ShortHand, Builder, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[builder(setter(into), build_fn(validate = "Self::validate"))]
 #[shorthand(enable(skip, must_use, into))]
 #[non_exhaustive]
@@ -79,6 +86,58 @@ pub struct DecryptionKey<'a> {
     /// This field is optional.
     #[builder(setter(into, strip_option), default)]
     pub versions: Option<KeyFormatVersions>,
+    #[builder(setter(skip), default)]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) attribute_order: AttributeOrder,
+}
+
+/// The order in which the attributes of a [`DecryptionKey`] appeared in the
+/// parsed input, so that they can be written in the same order.
+///
+/// The order does not change the meaning of a [`DecryptionKey`], so it is
+/// ignored when comparing or hashing keys.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct AttributeOrder(Vec<&'static str>);
+
+impl AttributeOrder {
+    const ATTRIBUTES: [&'static str; 5] = ["METHOD", "URI", "IV", "KEYFORMAT", "KEYFORMATVERSIONS"];
+
+    fn push(&mut self, attribute: &str) {
+        if let Some(name) = Self::ATTRIBUTES.iter().find(|name| **name == attribute) {
+            if !self.0.contains(name) {
+                self.0.push(name);
+            }
+        }
+    }
+
+    /// Returns all attributes, starting with the ones in the recorded order,
+    /// followed by the remaining ones in their default order.
+    fn iter(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.0.iter().copied().chain(
+            Self::ATTRIBUTES
+                .iter()
+                .copied()
+                .filter(move |name| !self.0.contains(name)),
+        )
+    }
+}
+
+impl PartialEq for AttributeOrder {
+    fn eq(&self, _: &Self) -> bool { true }
+}
+
+impl Eq for AttributeOrder {}
+
+impl Hash for AttributeOrder {
+    fn hash<H: Hasher>(&self, _: &mut H) {}
+}
+
+impl PartialOrd for AttributeOrder {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+
+impl Ord for AttributeOrder {
+    fn cmp(&self, _: &Self) -> Ordering { Ordering::Equal }
 }
 
 impl<'a> DecryptionKey<'a> {
@@ -102,6 +161,7 @@ impl<'a> DecryptionKey<'a> {
             iv: InitializationVector::default(),
             format: None,
             versions: None,
+            attribute_order: AttributeOrder::default(),
         }
     }
 
@@ -128,6 +188,123 @@ impl<'a> DecryptionKey<'a> {
     #[inline]
     pub fn builder() -> DecryptionKeyBuilder<'a> { DecryptionKeyBuilder::default() }
 
+    /// Resolves the [`DecryptionKey::uri`] against the `base` url, which should
+    /// be the url of the playlist.
+    ///
+    /// `URI`s, which are already absolute, replace the `base` url and are
+    /// normalized by [`Url::join`] (for example `HTTP://Example.com/a/../b`
+    /// becomes `http://example.com/b`).
+    ///
+    /// # Errors
+    ///
+    /// This function fails, if the `URI` can not be resolved into a valid url.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::types::DecryptionKey;
+    /// use hls_m3u8::types::EncryptionMethod;
+    /// use url::Url;
+    ///
+    /// let key = DecryptionKey::new(EncryptionMethod::Aes128, "https://keys.example.com/key");
+    /// let base = Url::parse("https://www.example.com/video/index.m3u8").unwrap();
+    ///
+    /// assert_eq!(
+    ///     key.resolve_uri(&base).unwrap().as_str(),
+    ///     "https://keys.example.com/key"
+    /// );
+    /// ```
+    #[cfg(feature = "url")]
+    pub fn resolve_uri(&self, base: &Url) -> Result<Url, ParseError> { base.join(&self.uri) }
+
+    /// Returns the IV, which should be used to decrypt the [`MediaSegment`]
+    /// with the given [`MediaSegment::number`].
+    ///
+    /// - If the [`DecryptionKey::iv`] is present, it will be returned.
+    /// - If the IV is missing, an [`EncryptionMethod::Aes128`] key with
+    ///   [`KeyFormat::Identity`] (or without a format) uses the
+    ///   [`MediaSegment::number`] as IV.
+    /// - If the IV is missing, an [`EncryptionMethod::SampleAes`] key does not
+    ///   have an IV in the playlist and `None` is returned. The IV is then part
+    ///   of the media itself (for example the `cbcs` scheme of fMP4 segments
+    ///   carries it in the `tenc`/`senc` boxes).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::types::DecryptionKey;
+    /// use hls_m3u8::types::EncryptionMethod;
+    ///
+    /// let key = DecryptionKey::new(EncryptionMethod::Aes128, "https://www.example.com/");
+    ///
+    /// assert_eq!(key.effective_iv(5), Some(5_u128.to_be_bytes()));
+    ///
+    /// let key = DecryptionKey::new(EncryptionMethod::SampleAes, "https://www.example.com/");
+    ///
+    /// assert_eq!(key.effective_iv(5), None);
+    /// ```
+    ///
+    /// [`MediaSegment`]: crate::MediaSegment
+    /// [`MediaSegment::number`]: crate::MediaSegment::number
+    #[must_use]
+    pub fn effective_iv(&self, number: usize) -> Option<[u8; 0x10]> {
+        if let Some(iv) = self.iv.to_slice() {
+            return Some(iv);
+        }
+
+        match (self.method, &self.format) {
+            (EncryptionMethod::Aes128, None)
+            | (EncryptionMethod::Aes128, Some(KeyFormat::Identity)) => {
+                Some((number as u128).to_be_bytes())
+            }
+            // SAMPLE-AES does not derive the IV from the media sequence number
+            _ => None,
+        }
+    }
+
+    /// Returns `true`, if both [`DecryptionKey`]s refer to the same key, which
+    /// is the case if they have the same [`DecryptionKey::method`],
+    /// [`DecryptionKey::uri`] and [`DecryptionKey::format`] (like [`KeyId`]).
+    ///
+    /// The [`DecryptionKey::iv`] often changes with every [`MediaSegment`],
+    /// while the key itself stays the same, so this can be used to avoid
+    /// fetching an unchanged key again. The [`DecryptionKey::versions`] do not
+    /// change the key either, so they are ignored as well.
+    ///
+    /// A missing [`DecryptionKey::format`] is the same as
+    /// [`KeyFormat::Identity`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::types::DecryptionKey;
+    /// use hls_m3u8::types::EncryptionMethod;
+    ///
+    /// let mut first = DecryptionKey::new(EncryptionMethod::Aes128, "https://www.example.com/");
+    /// first.iv = [1; 16].into();
+    ///
+    /// let mut second = first.clone();
+    /// second.iv = [2; 16].into();
+    ///
+    /// assert!(first.same_key_material(&second));
+    /// ```
+    ///
+    /// [`MediaSegment`]: crate::MediaSegment
+    #[must_use]
+    pub fn same_key_material(&self, other: &Self) -> bool {
+        self.method == other.method
+            && self.uri == other.uri
+            && self.effective_format() == other.effective_format()
+    }
+
+    /// Returns the [`DecryptionKey::format`], where a missing format is the
+    /// same as [`KeyFormat::Identity`].
+    pub(crate) fn effective_format(&self) -> &KeyFormat {
+        static IDENTITY: KeyFormat = KeyFormat::Identity;
+
+        self.format.as_ref().unwrap_or(&IDENTITY)
+    }
+
     /// Makes the struct independent of its lifetime, by taking ownership of all
     /// internal [`Cow`]s.
     ///
@@ -142,6 +319,7 @@ impl<'a> DecryptionKey<'a> {
             iv: self.iv,
             format: self.format,
             versions: self.versions,
+            attribute_order: self.attribute_order,
         }
     }
 }
@@ -172,8 +350,11 @@ impl<'a> TryFrom<&'a str> for DecryptionKey<'a> {
         let mut iv = None;
         let mut format = None;
         let mut versions = None;
+        let mut attribute_order = AttributeOrder::default();
 
         for (key, value) in AttributePairs::new(input) {
+            attribute_order.push(key);
+
             match key {
                 "METHOD" => method = Some(value.parse().map_err(Error::strum)?),
                 "URI" => {
@@ -204,25 +385,39 @@ impl<'a> TryFrom<&'a str> for DecryptionKey<'a> {
             iv,
             format,
             versions,
+            attribute_order,
         })
     }
 }
 
 impl<'a> fmt::Display for DecryptionKey<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "METHOD={},URI={}", self.method, quote(&self.uri))?;
+        // the attributes are written in the order in which they have been parsed
+        let mut separator = "";
 
-        if let InitializationVector::Aes128(_) = &self.iv {
-            write!(f, ",IV={}", &self.iv)?;
-        }
-
-        if let Some(value) = &self.format {
-            write!(f, ",KEYFORMAT={}", quote(value))?;
-        }
+        for name in self.attribute_order.iter() {
+            let value = match name {
+                "METHOD" => Some(self.method.to_string()),
+                "URI" => Some(quote(&self.uri)),
+                "IV" => {
+                    match &self.iv {
+                        InitializationVector::Aes128(_) => Some(self.iv.to_string()),
+                        _ => None,
+                    }
+                }
+                "KEYFORMAT" => self.format.as_ref().map(quote),
+                "KEYFORMATVERSIONS" => {
+                    self.versions
+                        .as_ref()
+                        .filter(|value| !value.is_default())
+                        .map(ToString::to_string)
+                }
+                _ => None,
+            };
 
-        if let Some(value) = &self.versions {
-            if !value.is_default() {
-                write!(f, ",KEYFORMATVERSIONS={}", value)?;
+            if let Some(value) = value {
+                write!(f, "{}{}={}", separator, name, value)?;
+                separator = ",";
             }
         }
 
@@ -230,6 +425,64 @@ impl<'a> fmt::Display for DecryptionKey<'a> {
     }
 }
 
+/// A wrapper around a [`DecryptionKey`], which compares and hashes the key
+/// only by its [`DecryptionKey::method`], [`DecryptionKey::uri`] and
+/// [`DecryptionKey::format`] (a missing format is [`KeyFormat::Identity`]).
+///
+/// The [`DecryptionKey::iv`] and the [`DecryptionKey::versions`] do not change
+/// the key, that is referenced by the `URI`, so they are ignored.
+///
+/// This can be used to deduplicate the keys of all [`MediaSegment`]s, for
+/// example with a [`HashSet`].
+///
+/// # Example
+///
+/// ```
+/// # use hls_m3u8::types::{DecryptionKey, KeyId};
+/// use hls_m3u8::types::EncryptionMethod;
+/// use std::collections::HashSet;
+///
+/// let mut first = DecryptionKey::new(EncryptionMethod::Aes128, "https://www.example.com/");
+/// first.iv = [1; 16].into();
+///
+/// let mut second = first.clone();
+/// second.iv = [2; 16].into();
+///
+/// let keys = vec![first, second]
+///     .into_iter()
+///     .map(KeyId)
+///     .collect::<HashSet<_>>();
+///
+/// assert_eq!(keys.len(), 1);
+/// ```
+///
+/// [`MediaSegment`]: crate::MediaSegment
+/// [`HashSet`]: std::collections::HashSet
+#[derive(Debug, Clone)]
+pub struct KeyId<'a>(pub DecryptionKey<'a>);
+
+impl<'a> PartialEq for KeyId<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.method == other.0.method
+            && self.0.uri == other.0.uri
+            && self.0.effective_format() == other.0.effective_format()
+    }
+}
+
+impl<'a> Eq for KeyId<'a> {}
+
+impl<'a> Hash for KeyId<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.method.hash(state);
+        self.0.uri.hash(state);
+        self.0.effective_format().hash(state);
+    }
+}
+
+impl<'a> From<DecryptionKey<'a>> for KeyId<'a> {
+    fn from(value: DecryptionKey<'a>) -> Self { Self(value) }
+}
+
 impl<'a> DecryptionKeyBuilder<'a> {
     fn validate(&self) -> Result<(), String> {
         // a decryption key must contain a uri and a method
@@ -248,6 +501,7 @@ mod test {
     use super::*;
     use crate::types::{EncryptionMethod, KeyFormat};
     use pretty_assertions::assert_eq;
+    use std::collections::HashSet;
 
     macro_rules! generate_tests {
         ( $( { $struct:expr, $str:expr } ),+ $(,)* ) => {
@@ -350,6 +604,38 @@ mod test {
         },
     }
 
+    #[test]
+    fn test_parser_iv_length() {
+        // the IV has to be exactly 128 bits long
+        assert_eq!(
+            DecryptionKey::try_from(concat!(
+                "METHOD=AES-128,",
+                "URI=\"https://www.example.com/\",",
+                "IV=0x000102030405060708090a0b0c0d0e0f"
+            ))
+            .unwrap()
+            .iv,
+            InitializationVector::Aes128([
+                0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d,
+                0x0e, 0x0f
+            ])
+        );
+
+        assert!(DecryptionKey::try_from(concat!(
+            "METHOD=AES-128,",
+            "URI=\"https://www.example.com/\",",
+            "IV=0x000102"
+        ))
+        .is_err());
+
+        assert!(DecryptionKey::try_from(concat!(
+            "METHOD=AES-128,",
+            "URI=\"https://www.example.com/\",",
+            "IV=0x000102030405060708090a0b0c0d0e0f10"
+        ))
+        .is_err());
+    }
+
     #[test]
     fn test_required_version() {
         assert_eq!(
@@ -381,4 +667,129 @@ mod test {
             ProtocolVersion::V2
         );
     }
+
+    #[test]
+    fn test_same_key_material() {
+        let key = DecryptionKey::builder()
+            .method(EncryptionMethod::Aes128)
+            .uri("https://www.example.com/")
+            .iv([1; 16])
+            .build()
+            .unwrap();
+
+        // the IV is ignored:
+        let mut other = key.clone();
+        other.iv = [2; 16].into();
+        assert!(key.same_key_material(&other));
+
+        other.iv = InitializationVector::Missing;
+        assert!(key.same_key_material(&other));
+
+        let mut other = key.clone();
+        other.set_uri("https://www.example.com/other");
+        assert!(!key.same_key_material(&other));
+
+        let mut other = key.clone();
+        other.method = EncryptionMethod::SampleAes;
+        assert!(!key.same_key_material(&other));
+
+        // a missing format is the identity format:
+        let mut other = key.clone();
+        other.format = Some(KeyFormat::Identity);
+        assert!(key.same_key_material(&other));
+        assert!(other.same_key_material(&key));
+
+        // the versions are ignored:
+        let mut other = key.clone();
+        other.versions = Some(KeyFormatVersions::from([1, 2, 3]));
+        assert!(key.same_key_material(&other));
+    }
+
+    #[test]
+    fn test_key_id() {
+        let key = DecryptionKey::builder()
+            .method(EncryptionMethod::Aes128)
+            .uri("https://www.example.com/")
+            .iv([1; 16])
+            .build()
+            .unwrap();
+
+        let mut other = key.clone();
+        other.iv = [2; 16].into();
+
+        let mut set = HashSet::new();
+
+        assert!(set.insert(KeyId(key.clone())));
+        assert!(!set.insert(KeyId(other)));
+        assert_eq!(set.len(), 1);
+
+        // the versions and a missing format do not change the key:
+        let mut other = key.clone();
+        other.format = Some(KeyFormat::Identity);
+        other.versions = Some(vec![1, 2].into());
+
+        assert!(!set.insert(KeyId(other)));
+        assert_eq!(set.len(), 1);
+
+        let mut other = key;
+        other.set_uri("https://www.example.com/other");
+
+        assert!(set.insert(KeyId::from(other)));
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn test_effective_iv() {
+        let iv = [1, 2, 3, 4, 5, 6, 7, 8, 9, 1, 2, 3, 4, 5, 6, 7];
+
+        // an explicit IV is always used
+        for method in &[EncryptionMethod::Aes128, EncryptionMethod::SampleAes] {
+            assert_eq!(
+                DecryptionKey::builder()
+                    .method(*method)
+                    .uri("https://www.example.com/")
+                    .iv(iv)
+                    .build()
+                    .unwrap()
+                    .effective_iv(5),
+                Some(iv)
+            );
+        }
+
+        // AES-128 falls back to the media sequence number
+        assert_eq!(
+            DecryptionKey::new(EncryptionMethod::Aes128, "https://www.example.com/")
+                .effective_iv(5),
+            Some(5_u128.to_be_bytes())
+        );
+
+        assert_eq!(
+            DecryptionKey::builder()
+                .method(EncryptionMethod::Aes128)
+                .uri("https://www.example.com/")
+                .format(KeyFormat::Identity)
+                .build()
+                .unwrap()
+                .effective_iv(5),
+            Some(5_u128.to_be_bytes())
+        );
+
+        // SAMPLE-AES without an IV has to take it from the media
+        assert_eq!(
+            DecryptionKey::new(EncryptionMethod::SampleAes, "https://www.example.com/")
+                .effective_iv(5),
+            None
+        );
+
+        assert_eq!(
+            DecryptionKey::builder()
+                .method(EncryptionMethod::SampleAes)
+                .uri("https://www.example.com/")
+                .format(KeyFormat::Identity)
+                .build()
+                .unwrap()
+                .effective_iv(5),
+            None
+        );
+    }
 }
@@ -0,0 +1,20 @@
+use std::time::Duration;
+
+/// Statistics about the durations of the [`MediaSegment`]s in a
+/// [`MediaPlaylist`], which are returned by
+/// [`MediaPlaylist::duration_stats`].
+///
+/// [`MediaSegment`]: crate::MediaSegment
+/// [`MediaPlaylist`]: crate::MediaPlaylist
+/// [`MediaPlaylist::duration_stats`]: crate::MediaPlaylist::duration_stats
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DurationStats {
+    /// The duration of the shortest segment.
+    pub min: Duration,
+    /// The duration of the longest segment.
+    pub max: Duration,
+    /// The mean of all segment durations (rounded down to the nanosecond).
+    pub mean: Duration,
+    /// The number of segments.
+    pub count: usize,
+}
@@ -62,6 +62,25 @@ impl Float {
     /// ```
     #[must_use]
     pub const fn as_f32(self) -> f32 { self.0 }
+
+    /// Returns the underlying float as an [`f64`], which has the same decimal
+    /// representation as the [`f32`] (a plain conversion would turn `1.1_f32`
+    /// into `1.100000023841858`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::types::Float;
+    /// assert_eq!(Float::new(1.1_f32).as_f64(), 1.1_f64);
+    /// ```
+    #[must_use]
+    pub fn as_f64(self) -> f64 {
+        // the shortest representation of an `f32` is always a valid `f64`
+        self.0
+            .to_string()
+            .parse()
+            .unwrap_or_else(|_| f64::from(self.0))
+    }
 }
 
 impl FromStr for Float {
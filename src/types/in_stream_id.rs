@@ -123,6 +123,12 @@ mod tests {
                     assert_eq!($enum, $string.parse::<InStreamId>().unwrap());
                 )*
                 assert!("invalid_input".parse::<InStreamId>().is_err());
+
+                // out of range
+                assert!("CC0".parse::<InStreamId>().is_err());
+                assert!("CC5".parse::<InStreamId>().is_err());
+                assert!("SERVICE0".parse::<InStreamId>().is_err());
+                assert!("SERVICE64".parse::<InStreamId>().is_err());
             }
         };
     }
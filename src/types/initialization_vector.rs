@@ -1,6 +1,9 @@
 use core::fmt;
 use core::str::FromStr;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use crate::Error;
 
 /// An initialization vector (IV) is a fixed size input that can be used along
@@ -14,6 +17,7 @@ use crate::Error;
 /// identical. The IV prevents the appearance of corresponding duplicate
 /// character sequences in the ciphertext.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[non_exhaustive]
 pub enum InitializationVector {
     /// An IV for use with Aes128.
@@ -262,6 +266,16 @@ mod tests {
             "0xffffffffffffffffffffffffffffffff".to_string()
         );
 
+        // leading zero bytes are kept, so that the IV is always 16 bytes long:
+        let iv =
+            InitializationVector::Aes128([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x0A, 0x01])
+                .to_string();
+
+        assert_eq!(iv, "0x00000000000000000000000000000a01".to_string());
+        assert_eq!(iv.len(), 2 + 32);
+        assert!(iv[2..].chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(iv.parse::<InitializationVector>().unwrap().to_string(), iv);
+
         assert_eq!(
             InitializationVector::Number(5).to_string(),
             "InitializationVector::Number(5)".to_string()
@@ -1,14 +1,32 @@
 use std::fmt;
 use std::str::FromStr;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use crate::types::ProtocolVersion;
-use crate::utils::{quote, tag, unquote};
+use crate::utils::{quote, unquote};
 use crate::{Error, RequiredVersion};
 
 /// Specifies how the key is represented in the resource identified by the
 /// `URI`.
+///
+/// # Example
+///
+/// ```
+/// # use hls_m3u8::types::KeyFormat;
+/// assert_eq!("\"identity\"".parse::<KeyFormat>()?, KeyFormat::Identity);
+///
+/// // for example FairPlay:
+/// assert_eq!(
+///     "\"com.apple.streamingkeydelivery\"".parse::<KeyFormat>()?,
+///     KeyFormat::Other("com.apple.streamingkeydelivery".to_string())
+/// );
+/// # Ok::<(), hls_m3u8::Error>(())
+/// ```
 #[non_exhaustive]
-#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum KeyFormat {
     /// An [`EncryptionMethod::Aes128`] uses 16-octet (16 byte/128 bit) keys. If
     /// the format is [`KeyFormat::Identity`], the key file is a single packed
@@ -16,6 +34,9 @@ pub enum KeyFormat {
     ///
     /// [`EncryptionMethod::Aes128`]: crate::types::EncryptionMethod::Aes128
     Identity,
+    /// Any other format, which is usually defined by a DRM system (for
+    /// example `com.apple.streamingkeydelivery` for FairPlay).
+    Other(String),
 }
 
 impl Default for KeyFormat {
@@ -26,14 +47,23 @@ impl FromStr for KeyFormat {
     type Err = Error;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        tag(&unquote(input), "identity")?; // currently only KeyFormat::Identity exists!
+        let input = unquote(input);
 
-        Ok(Self::Identity)
+        match &*input {
+            "identity" => Ok(Self::Identity),
+            "" => Err(Error::invalid_input()),
+            _ => Ok(Self::Other(input.into_owned())),
+        }
     }
 }
 
 impl fmt::Display for KeyFormat {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "{}", quote(&"identity")) }
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Identity => write!(f, "{}", quote(&"identity")),
+            Self::Other(value) => write!(f, "{}", quote(value)),
+        }
+    }
 }
 
 /// This tag requires [`ProtocolVersion::V5`].
@@ -49,6 +79,11 @@ mod tests {
     #[test]
     fn test_display() {
         assert_eq!(KeyFormat::Identity.to_string(), quote("identity"));
+
+        assert_eq!(
+            KeyFormat::Other("com.apple.streamingkeydelivery".to_string()).to_string(),
+            quote("com.apple.streamingkeydelivery")
+        );
     }
 
     #[test]
@@ -57,7 +92,13 @@ mod tests {
 
         assert_eq!(KeyFormat::Identity, "identity".parse().unwrap());
 
-        assert!("garbage".parse::<KeyFormat>().is_err());
+        assert_eq!(
+            KeyFormat::Other("com.apple.streamingkeydelivery".to_string()),
+            quote("com.apple.streamingkeydelivery").parse().unwrap()
+        );
+
+        assert!("".parse::<KeyFormat>().is_err());
+        assert!(quote("").parse::<KeyFormat>().is_err());
     }
 
     #[test]
@@ -6,6 +6,11 @@ use std::ops::{Index, IndexMut};
 use std::slice::SliceIndex;
 use std::str::FromStr;
 
+#[cfg(feature = "serde")]
+use serde::de::{self, Deserialize, Deserializer};
+#[cfg(feature = "serde")]
+use serde::{Serialize, Serializer};
+
 use crate::types::ProtocolVersion;
 use crate::utils::{quote, unquote};
 use crate::Error;
@@ -412,6 +417,33 @@ impl fmt::Display for KeyFormatVersions {
     }
 }
 
+/// [`KeyFormatVersions`] are serialized as a sequence of the versions.
+#[cfg(feature = "serde")]
+impl Serialize for KeyFormatVersions {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.as_ref())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for KeyFormatVersions {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let versions = Vec::<u8>::deserialize(deserializer)?;
+        let mut result = Self::new();
+
+        if versions.len() > result.capacity() {
+            return Err(de::Error::invalid_length(
+                versions.len(),
+                &"at most 9 versions",
+            ));
+        }
+
+        result.extend(versions);
+
+        Ok(result)
+    }
+}
+
 impl<T: AsRef<[usize]>> From<T> for KeyFormatVersions {
     fn from(value: T) -> Self { Self::from_iter(value.as_ref().iter().map(|i| *i as u8)) }
 }
@@ -514,28 +546,28 @@ mod tests {
 
     #[test]
     fn test_as_ref() {
-        assert_eq!(KeyFormatVersions::new().as_ref(), &[]);
+        assert_eq!(KeyFormatVersions::new().as_ref(), &[] as &[u8]);
         assert_eq!(KeyFormatVersions::from([1, 2, 3]).as_ref(), &[1, 2, 3]);
-        assert_eq!(KeyFormatVersions::from([]).as_ref(), &[]);
+        assert_eq!(KeyFormatVersions::from([]).as_ref(), &[] as &[u8]);
     }
 
     #[test]
     fn test_as_mut() {
-        assert_eq!(KeyFormatVersions::new().as_mut(), &mut []);
+        assert_eq!(KeyFormatVersions::new().as_mut(), &mut [] as &mut [u8]);
         assert_eq!(KeyFormatVersions::from([1, 2, 3]).as_mut(), &mut [1, 2, 3]);
-        assert_eq!(KeyFormatVersions::from([]).as_mut(), &mut []);
+        assert_eq!(KeyFormatVersions::from([]).as_mut(), &mut [] as &mut [u8]);
     }
 
     #[test]
     fn test_index() {
         // test index
-        assert_eq!(&KeyFormatVersions::new()[..], &[]);
+        assert_eq!(&KeyFormatVersions::new()[..], &[] as &[u8]);
         assert_eq!(&KeyFormatVersions::from([1, 2, 3])[..2], &[1, 2]);
         assert_eq!(&KeyFormatVersions::from([1, 2, 3])[1..2], &[2]);
         assert_eq!(&KeyFormatVersions::from([1, 2, 3])[..], &[1, 2, 3]);
 
         // test index_mut
-        assert_eq!(&mut KeyFormatVersions::new()[..], &mut []);
+        assert_eq!(&mut KeyFormatVersions::new()[..], &mut [] as &mut [u8]);
         assert_eq!(&mut KeyFormatVersions::from([1, 2, 3])[..2], &mut [1, 2]);
         assert_eq!(&mut KeyFormatVersions::from([1, 2, 3])[1..2], &mut [2]);
         assert_eq!(&mut KeyFormatVersions::from([1, 2, 3])[..], &mut [1, 2, 3]);
@@ -691,4 +723,26 @@ mod tests {
 
         assert_eq!(KeyFormatVersions::from([2]), key_format_versions);
     }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde() {
+        let versions = KeyFormatVersions::from([1, 2, 5]);
+
+        assert_eq!(
+            serde_json::to_value(&versions).unwrap(),
+            serde_json::json!([1, 2, 5])
+        );
+        assert_eq!(
+            serde_json::from_value::<KeyFormatVersions>(serde_json::json!([1, 2, 5])).unwrap(),
+            versions
+        );
+
+        assert!(
+            serde_json::from_value::<KeyFormatVersions>(serde_json::json!([
+                1, 2, 3, 4, 5, 6, 7, 8, 9, 10
+            ]))
+            .is_err()
+        );
+    }
 }
@@ -3,7 +3,9 @@ pub(crate) mod byte_range;
 pub(crate) mod channels;
 pub(crate) mod closed_captions;
 pub(crate) mod codecs;
+pub(crate) mod cue;
 pub(crate) mod decryption_key;
+pub(crate) mod duration_stats;
 pub(crate) mod encryption_method;
 pub(crate) mod hdcp_level;
 pub(crate) mod in_stream_id;
@@ -16,6 +18,7 @@ pub(crate) mod protocol_version;
 pub(crate) mod resolution;
 pub(crate) mod stream_data;
 pub(crate) mod value;
+pub(crate) mod video_range;
 
 pub(crate) mod float;
 pub(crate) mod ufloat;
@@ -24,7 +27,9 @@ pub use byte_range::*;
 pub use channels::*;
 pub use closed_captions::*;
 pub use codecs::*;
-pub use decryption_key::DecryptionKey;
+pub use cue::*;
+pub use decryption_key::{DecryptionKey, KeyId};
+pub use duration_stats::DurationStats;
 pub use encryption_method::*;
 pub use hdcp_level::*;
 pub use in_stream_id::*;
@@ -37,6 +42,7 @@ pub use protocol_version::*;
 pub use resolution::*;
 pub use stream_data::StreamData;
 pub use value::*;
+pub use video_range::*;
 
 pub use float::Float;
 pub use ufloat::UFloat;
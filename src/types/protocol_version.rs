@@ -1,6 +1,9 @@
 use std::fmt;
 use std::str::FromStr;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use crate::Error;
 
 /// The [`ProtocolVersion`] specifies which `m3u8` revision is required, to
@@ -8,6 +11,7 @@ use crate::Error;
 #[non_exhaustive]
 #[allow(missing_docs)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ProtocolVersion {
     V1,
     V2,
@@ -16,6 +20,7 @@ pub enum ProtocolVersion {
     V5,
     V6,
     V7,
+    V8,
 }
 
 impl ProtocolVersion {
@@ -26,11 +31,38 @@ impl ProtocolVersion {
     ///
     /// ```
     /// # use hls_m3u8::types::ProtocolVersion;
-    /// assert_eq!(ProtocolVersion::latest(), ProtocolVersion::V7);
+    /// assert_eq!(ProtocolVersion::latest(), ProtocolVersion::V8);
     /// ```
     #[must_use]
     #[inline]
-    pub const fn latest() -> Self { Self::V7 }
+    pub const fn latest() -> Self { Self::V8 }
+
+    /// Returns an iterator over all [`ProtocolVersion`]s, that are supported
+    /// by this library, in ascending order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::types::ProtocolVersion;
+    /// let mut versions = ProtocolVersion::all();
+    ///
+    /// assert_eq!(versions.next(), Some(ProtocolVersion::V1));
+    /// assert_eq!(versions.last(), Some(ProtocolVersion::latest()));
+    /// ```
+    pub fn all() -> impl Iterator<Item = Self> + Clone {
+        [
+            Self::V1,
+            Self::V2,
+            Self::V3,
+            Self::V4,
+            Self::V5,
+            Self::V6,
+            Self::V7,
+            Self::V8,
+        ]
+        .iter()
+        .copied()
+    }
 }
 
 impl fmt::Display for ProtocolVersion {
@@ -43,6 +75,7 @@ impl fmt::Display for ProtocolVersion {
             Self::V5 => write!(f, "5"),
             Self::V6 => write!(f, "6"),
             Self::V7 => write!(f, "7"),
+            Self::V8 => write!(f, "8"),
         }
     }
 }
@@ -60,6 +93,7 @@ impl FromStr for ProtocolVersion {
                 "5" => Self::V5,
                 "6" => Self::V6,
                 "7" => Self::V7,
+                "8" => Self::V8,
                 _ => return Err(Error::unknown_protocol_version(input)),
             }
         })
@@ -85,6 +119,7 @@ mod tests {
         assert_eq!(ProtocolVersion::V5.to_string(), "5".to_string());
         assert_eq!(ProtocolVersion::V6.to_string(), "6".to_string());
         assert_eq!(ProtocolVersion::V7.to_string(), "7".to_string());
+        assert_eq!(ProtocolVersion::V8.to_string(), "8".to_string());
     }
 
     #[test]
@@ -96,6 +131,7 @@ mod tests {
         assert_eq!(ProtocolVersion::V5, "5".parse().unwrap());
         assert_eq!(ProtocolVersion::V6, "6".parse().unwrap());
         assert_eq!(ProtocolVersion::V7, "7".parse().unwrap());
+        assert_eq!(ProtocolVersion::V8, "8".parse().unwrap());
 
         assert_eq!(ProtocolVersion::V7, " 7 ".parse().unwrap());
         assert!("garbage".parse::<ProtocolVersion>().is_err());
@@ -108,6 +144,22 @@ mod tests {
 
     #[test]
     fn test_latest() {
-        assert_eq!(ProtocolVersion::latest(), ProtocolVersion::V7);
+        assert_eq!(ProtocolVersion::latest(), ProtocolVersion::V8);
+        assert_eq!(
+            ProtocolVersion::all().max(),
+            Some(ProtocolVersion::latest())
+        );
+    }
+
+    #[test]
+    fn test_all() {
+        let versions = ProtocolVersion::all().collect::<Vec<_>>();
+
+        assert_eq!(versions.len(), 8);
+        assert!(versions.windows(2).all(|w| w[0] < w[1]));
+
+        for version in versions {
+            assert_eq!(version, version.to_string().parse().unwrap());
+        }
     }
 }
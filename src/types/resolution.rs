@@ -76,6 +76,11 @@ impl FromStr for Resolution {
             .ok_or_else(|| Error::custom("missing height for `Resolution` or an invalid input"))
             .and_then(|v| v.parse().map_err(|e| Error::parse_int(v, e)))?;
 
+        // a resolution without a width or a height is nonsensical
+        if width == 0 || height == 0 {
+            return Err(Error::invalid_input());
+        }
+
         Ok(Self { width, height })
     }
 }
@@ -111,6 +116,11 @@ mod tests {
         );
 
         assert!("1280".parse::<Resolution>().is_err());
+
+        // zero dimensions are invalid
+        assert_eq!("0x1080".parse::<Resolution>(), Err(Error::invalid_input()));
+        assert_eq!("1920x0".parse::<Resolution>(), Err(Error::invalid_input()));
+        assert_eq!("0x0".parse::<Resolution>(), Err(Error::invalid_input()));
     }
 
     #[test]
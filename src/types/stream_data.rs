@@ -6,7 +6,7 @@ use derive_builder::Builder;
 use shorthand::ShortHand;
 
 use crate::attribute::AttributePairs;
-use crate::types::{Codecs, HdcpLevel, ProtocolVersion, Resolution};
+use crate::types::{Codecs, HdcpLevel, ProtocolVersion, Resolution, VideoRange};
 use crate::utils::{quote, unquote};
 use crate::{Error, RequiredVersion};
 
@@ -183,6 +183,30 @@ pub struct StreamData<'a> {
     #[builder(default)]
     #[shorthand(enable(copy), disable(into))]
     hdcp_level: Option<HdcpLevel>,
+    /// The dynamic range of the video in the [`VariantStream`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::types::StreamData;
+    /// use hls_m3u8::types::VideoRange;
+    /// #
+    /// let mut stream = StreamData::new(20);
+    ///
+    /// stream.set_video_range(Some(VideoRange::Pq));
+    /// assert_eq!(stream.video_range(), Some(VideoRange::Pq));
+    /// ```
+    ///
+    /// # Note
+    ///
+    /// This field is optional. A missing value means [`VideoRange::Sdr`], so
+    /// it does not have to be specified for SDR content (see
+    /// [`StreamData::video_range_or_default`]).
+    ///
+    /// [`VariantStream`]: crate::tags::VariantStream
+    #[builder(default)]
+    #[shorthand(enable(copy), disable(into))]
+    video_range: Option<VideoRange>,
     /// It indicates the set of video renditions, that should be used when
     /// playing the presentation.
     ///
@@ -212,6 +236,32 @@ pub struct StreamData<'a> {
     /// [`ExtXMedia::media_type`]: crate::tags::ExtXMedia::media_type
     #[builder(default, setter(into))]
     video: Option<Cow<'a, str>>,
+    /// The pathway (for example a CDN), which provides this [`VariantStream`].
+    ///
+    /// If a [`MasterPlaylist`] has an [`ExtXContentSteering`] tag, the
+    /// [`VariantStream`]s without a pathway belong to the default pathway
+    /// `"."`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::types::StreamData;
+    /// #
+    /// let mut stream = StreamData::new(20);
+    ///
+    /// stream.set_pathway_id(Some("CDN-A"));
+    /// assert_eq!(stream.pathway_id(), Some(&"CDN-A".into()));
+    /// ```
+    ///
+    /// # Note
+    ///
+    /// This field is optional.
+    ///
+    /// [`VariantStream`]: crate::tags::VariantStream
+    /// [`MasterPlaylist`]: crate::MasterPlaylist
+    /// [`ExtXContentSteering`]: crate::tags::ExtXContentSteering
+    #[builder(default, setter(into))]
+    pathway_id: Option<Cow<'a, str>>,
 }
 
 impl<'a> StreamData<'a> {
@@ -232,7 +282,9 @@ impl<'a> StreamData<'a> {
             codecs: None,
             resolution: None,
             hdcp_level: None,
+            video_range: None,
             video: None,
+            pathway_id: None,
         }
     }
 
@@ -256,6 +308,26 @@ impl<'a> StreamData<'a> {
     #[must_use]
     pub fn builder() -> StreamDataBuilder<'a> { StreamDataBuilder::default() }
 
+    /// Returns the [`StreamData::video_range`] or [`VideoRange::Sdr`], which
+    /// is the default if the `VIDEO-RANGE` attribute is missing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::types::StreamData;
+    /// use hls_m3u8::types::VideoRange;
+    ///
+    /// let mut stream = StreamData::new(20);
+    /// assert_eq!(stream.video_range_or_default(), VideoRange::Sdr);
+    ///
+    /// stream.set_video_range(Some(VideoRange::Hlg));
+    /// assert_eq!(stream.video_range_or_default(), VideoRange::Hlg);
+    /// ```
+    #[must_use]
+    pub fn video_range_or_default(&self) -> VideoRange {
+        self.video_range.unwrap_or(VideoRange::Sdr)
+    }
+
     /// Makes the struct independent of its lifetime, by taking ownership of all
     /// internal [`Cow`]s.
     ///
@@ -270,7 +342,9 @@ impl<'a> StreamData<'a> {
             codecs: self.codecs.map(Codecs::into_owned),
             resolution: self.resolution,
             hdcp_level: self.hdcp_level,
+            video_range: self.video_range,
             video: self.video.map(|v| Cow::Owned(v.into_owned())),
+            pathway_id: self.pathway_id.map(|v| Cow::Owned(v.into_owned())),
         }
     }
 }
@@ -291,23 +365,47 @@ impl<'a> fmt::Display for StreamData<'a> {
         if let Some(value) = &self.hdcp_level {
             write!(f, ",HDCP-LEVEL={}", value)?;
         }
+        if let Some(value) = &self.video_range {
+            write!(f, ",VIDEO-RANGE={}", value)?;
+        }
         if let Some(value) = &self.video {
             write!(f, ",VIDEO={}", quote(value))?;
         }
+        if let Some(value) = &self.pathway_id {
+            write!(f, ",PATHWAY-ID={}", quote(value))?;
+        }
         Ok(())
     }
 }
 
-impl<'a> TryFrom<&'a str> for StreamData<'a> {
-    type Error = Error;
+impl<'a> StreamData<'a> {
+    /// The names of all attributes, that are parsed by [`StreamData::parse`].
+    pub(crate) const ATTRIBUTES: &'static [&'static str] = &[
+        "BANDWIDTH",
+        "AVERAGE-BANDWIDTH",
+        "CODECS",
+        "RESOLUTION",
+        "HDCP-LEVEL",
+        "VIDEO-RANGE",
+        "VIDEO",
+        "PATHWAY-ID",
+    ];
 
-    fn try_from(input: &'a str) -> Result<Self, Self::Error> {
+    /// Parses the [`StreamData`] from the attribute list. If the `BANDWIDTH`
+    /// attribute is missing, the value returned by `missing_bandwidth` will be
+    /// used instead.
+    pub(crate) fn parse<F>(input: &'a str, missing_bandwidth: F) -> crate::Result<Self>
+    where
+        F: FnOnce() -> crate::Result<u64>,
+    {
         let mut bandwidth = None;
         let mut average_bandwidth = None;
         let mut codecs = None;
         let mut resolution = None;
         let mut hdcp_level = None;
+        let mut video_range = None;
         let mut video = None;
+        let mut pathway_id = None;
 
         for (key, value) in AttributePairs::new(input) {
             match key {
@@ -330,7 +428,11 @@ impl<'a> TryFrom<&'a str> for StreamData<'a> {
                 "HDCP-LEVEL" => {
                     hdcp_level = Some(value.parse::<HdcpLevel>().map_err(Error::strum)?)
                 }
+                "VIDEO-RANGE" => {
+                    video_range = Some(value.parse::<VideoRange>().map_err(Error::strum)?)
+                }
                 "VIDEO" => video = Some(unquote(value)),
+                "PATHWAY-ID" => pathway_id = Some(unquote(value)),
                 _ => {
                     // [6.3.1. General Client Responsibilities]
                     // > ignore any attribute/value pair with an unrecognized
@@ -339,7 +441,7 @@ impl<'a> TryFrom<&'a str> for StreamData<'a> {
             }
         }
 
-        let bandwidth = bandwidth.ok_or_else(|| Error::missing_value("BANDWIDTH"))?;
+        let bandwidth = bandwidth.map_or_else(missing_bandwidth, Ok)?;
 
         Ok(Self {
             bandwidth,
@@ -347,11 +449,21 @@ impl<'a> TryFrom<&'a str> for StreamData<'a> {
             codecs,
             resolution,
             hdcp_level,
+            video_range,
             video,
+            pathway_id,
         })
     }
 }
 
+impl<'a> TryFrom<&'a str> for StreamData<'a> {
+    type Error = Error;
+
+    fn try_from(input: &'a str) -> Result<Self, Self::Error> {
+        Self::parse(input, || Err(Error::missing_value("BANDWIDTH")))
+    }
+}
+
 /// This struct requires [`ProtocolVersion::V1`].
 impl<'a> RequiredVersion for StreamData<'a> {
     fn required_version(&self) -> ProtocolVersion { ProtocolVersion::V1 }
@@ -377,7 +489,9 @@ mod tests {
         stream_data.set_codecs(Some(&["mp4a.40.2", "avc1.4d401e"]));
         stream_data.set_resolution(Some((1920, 1080)));
         stream_data.set_hdcp_level(Some(HdcpLevel::Type0));
+        stream_data.set_video_range(Some(VideoRange::Pq));
         stream_data.set_video(Some("video"));
+        stream_data.set_pathway_id(Some("CDN-A"));
 
         assert_eq!(
             stream_data.to_string(),
@@ -387,7 +501,9 @@ mod tests {
                 "CODECS=\"mp4a.40.2,avc1.4d401e\",",
                 "RESOLUTION=1920x1080,",
                 "HDCP-LEVEL=TYPE-0,",
-                "VIDEO=\"video\""
+                "VIDEO-RANGE=PQ,",
+                "VIDEO=\"video\",",
+                "PATHWAY-ID=\"CDN-A\""
             )
             .to_string()
         );
@@ -400,7 +516,9 @@ mod tests {
         stream_data.set_codecs(Some(&["mp4a.40.2", "avc1.4d401e"]));
         stream_data.set_resolution(Some((1920, 1080)));
         stream_data.set_hdcp_level(Some(HdcpLevel::Type0));
+        stream_data.set_video_range(Some(VideoRange::Pq));
         stream_data.set_video(Some("video"));
+        stream_data.set_pathway_id(Some("CDN-A"));
 
         assert_eq!(
             stream_data,
@@ -410,11 +528,35 @@ mod tests {
                 "CODECS=\"mp4a.40.2,avc1.4d401e\",",
                 "RESOLUTION=1920x1080,",
                 "HDCP-LEVEL=TYPE-0,",
-                "VIDEO=\"video\""
+                "VIDEO-RANGE=PQ,",
+                "VIDEO=\"video\",",
+                "PATHWAY-ID=\"CDN-A\""
             ))
             .unwrap()
         );
 
         assert!(StreamData::try_from("garbage").is_err());
     }
+
+    #[test]
+    fn test_video_range_or_default() {
+        assert_eq!(
+            StreamData::try_from("BANDWIDTH=200").unwrap().video_range(),
+            None
+        );
+        assert_eq!(
+            StreamData::try_from("BANDWIDTH=200")
+                .unwrap()
+                .video_range_or_default(),
+            VideoRange::Sdr
+        );
+        assert_eq!(
+            StreamData::try_from("BANDWIDTH=200,VIDEO-RANGE=HLG")
+                .unwrap()
+                .video_range_or_default(),
+            VideoRange::Hlg
+        );
+
+        assert!(StreamData::try_from("BANDWIDTH=200,VIDEO-RANGE=HDR").is_err());
+    }
 }
@@ -1,6 +1,7 @@
 use core::cmp::Ordering;
 use core::convert::TryFrom;
 use core::str::FromStr;
+use std::time::Duration;
 
 use derive_more::{AsRef, Deref, Display};
 
@@ -65,6 +66,59 @@ impl UFloat {
     /// ```
     #[must_use]
     pub const fn as_f32(self) -> f32 { self.0 }
+
+    /// Makes a new [`UFloat`] from the seconds of a [`Duration`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::types::UFloat;
+    /// use std::time::Duration;
+    ///
+    /// assert_eq!(UFloat::from_duration(Duration::from_millis(8500)), 8.5);
+    /// assert_eq!(UFloat::from_duration(Duration::from_millis(1111)), 1.111);
+    /// ```
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn from_duration(duration: Duration) -> Self {
+        // `Duration::as_secs_f32` rounds the seconds and the nanoseconds
+        // separately (for example 1.111 seconds become 1.1110001)
+        Self(duration.as_secs_f64() as f32)
+    }
+
+    /// Makes a new [`UFloat`] from the seconds of a [`Duration`], which are
+    /// rounded to the given number of fractional digits.
+    ///
+    /// This is useful for packagers, that need to write durations with a
+    /// fixed precision.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::types::UFloat;
+    /// use std::time::Duration;
+    ///
+    /// let float = UFloat::from_duration_rounded(Duration::from_secs_f64(8.3337), 3);
+    ///
+    /// assert_eq!(float, 8.334);
+    /// assert_eq!(float.to_string(), "8.334".to_string());
+    /// ```
+    ///
+    /// ## Note
+    ///
+    /// A [`Duration`] has nanosecond precision, so more than 9 digits do not
+    /// change the value.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn from_duration_rounded(duration: Duration, digits: u32) -> Self {
+        if digits >= 9 {
+            return Self::from_duration(duration);
+        }
+
+        let factor = f64::from(10_u32.pow(digits));
+
+        Self(((duration.as_secs_f64() * factor).round() / factor) as f32)
+    }
 }
 
 impl FromStr for UFloat {
@@ -292,6 +346,40 @@ mod tests {
         assert_eq!(UFloat::from(1_u16), UFloat::new(1.0));
     }
 
+    #[test]
+    fn test_from_duration() {
+        assert_eq!(UFloat::from_duration(Duration::from_secs(10)), 10.0);
+        assert_eq!(UFloat::from_duration(Duration::from_millis(8500)), 8.5);
+
+        // the duration is rounded only once:
+        for millis in &[1111, 1116, 1134, 4004, 10_010] {
+            let duration = Duration::from_millis(*millis);
+            let expected = format!("{}.{:03}", millis / 1000, millis % 1000);
+
+            assert_eq!(
+                UFloat::from_duration(duration),
+                UFloat::from_str(&expected).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_duration_rounded() {
+        let duration = Duration::from_secs_f64(8.3337);
+
+        assert_eq!(UFloat::from_duration_rounded(duration, 3), 8.334);
+        assert_eq!(UFloat::from_duration_rounded(duration, 2), 8.33);
+        assert_eq!(UFloat::from_duration_rounded(duration, 0), 8.0);
+        assert_eq!(
+            UFloat::from_duration_rounded(duration, 3).to_string(),
+            "8.334".to_string()
+        );
+        assert_eq!(
+            UFloat::from_duration_rounded(duration, 20),
+            UFloat::from_duration(duration)
+        );
+    }
+
     #[test]
     fn test_try_from() {
         assert_eq!(UFloat::try_from(1.1_f32).unwrap(), UFloat::new(1.1));
@@ -19,6 +19,81 @@ pub enum Value<'a> {
 }
 
 impl<'a> Value<'a> {
+    /// Returns the string, if the [`Value`] is a [`Value::String`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::types::Value;
+    /// use std::convert::TryFrom;
+    ///
+    /// let value = Value::try_from("\"https://www.example.com/\"")?;
+    ///
+    /// assert_eq!(value.as_str(), Some("https://www.example.com/"));
+    /// assert_eq!(Value::from(1_u8).as_str(), None);
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn as_str(&self) -> Option<&str> {
+        if let Self::String(value) = self {
+            Some(value.as_ref())
+        } else {
+            None
+        }
+    }
+
+    /// Returns the number, if the [`Value`] is a [`Value::Float`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::types::Value;
+    /// use hls_m3u8::types::Float;
+    /// use std::convert::TryFrom;
+    ///
+    /// let value = Value::try_from("15.5")?;
+    ///
+    /// assert_eq!(value.as_float(), Some(Float::new(15.5)));
+    /// assert_eq!(Value::Hex(vec![1]).as_float(), None);
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn as_float(&self) -> Option<Float> {
+        if let Self::Float(value) = self {
+            Some(*value)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the decoded bytes, if the [`Value`] is a hexadecimal-sequence.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use hls_m3u8::types::Value;
+    /// use std::convert::TryFrom;
+    ///
+    /// let value = Value::try_from("0x000102")?;
+    ///
+    /// assert_eq!(value.as_hex(), Some(&[0, 1, 2][..]));
+    /// assert_eq!(value.as_hex().map(<[u8]>::len), Some(3));
+    ///
+    /// assert_eq!(Value::from(1_u8).as_hex(), None);
+    /// # Ok::<(), hls_m3u8::Error>(())
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn as_hex(&self) -> Option<&[u8]> {
+        if let Self::Hex(value) = self {
+            Some(value.as_slice())
+        } else {
+            None
+        }
+    }
+
     /// Makes the struct independent of its lifetime, by taking ownership of all
     /// internal [`Cow`]s.
     ///
@@ -114,6 +189,34 @@ mod tests {
         assert!(Value::try_from("0x010203Z").is_err());
     }
 
+    #[test]
+    fn test_as_str() {
+        assert_eq!(Value::try_from("\"&str\"").unwrap().as_str(), Some("&str"));
+        assert_eq!(Value::Hex(vec![1]).as_str(), None);
+        assert_eq!(Value::Float(Float::new(1.0)).as_str(), None);
+    }
+
+    #[test]
+    fn test_as_float() {
+        assert_eq!(
+            Value::try_from("1.5").unwrap().as_float(),
+            Some(Float::new(1.5))
+        );
+        assert_eq!(Value::Hex(vec![1]).as_float(), None);
+        assert_eq!(Value::String("1.5".into()).as_float(), None);
+    }
+
+    #[test]
+    fn test_as_hex() {
+        let value = Value::try_from("0x000102").unwrap();
+
+        assert_eq!(value.as_hex(), Some(&[0, 1, 2][..]));
+        assert_eq!(value.as_hex().map(<[u8]>::len), Some(3));
+        assert_eq!(Value::Hex(vec![]).as_hex(), Some(&[][..]));
+        assert_eq!(Value::String("0x00".into()).as_hex(), None);
+        assert_eq!(Value::Float(Float::new(1.0)).as_hex(), None);
+    }
+
     #[test]
     fn test_from() {
         assert_eq!(Value::from(1_u8), Value::Float(Float::new(1.0)));
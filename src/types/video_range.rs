@@ -0,0 +1,43 @@
+use strum::{Display, EnumString};
+
+/// The dynamic range of the video in a [`VariantStream`].
+///
+/// [`VariantStream`]: crate::tags::VariantStream
+#[non_exhaustive]
+#[derive(Ord, PartialOrd, Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+#[strum(serialize_all = "UPPERCASE")]
+pub enum VideoRange {
+    /// The video is encoded with a standard dynamic range.
+    ///
+    /// This is the default, if a [`VariantStream`] has no `VIDEO-RANGE`.
+    ///
+    /// [`VariantStream`]: crate::tags::VariantStream
+    Sdr,
+    /// The video is encoded with the Hybrid Log-Gamma (HLG) transfer function.
+    Hlg,
+    /// The video is encoded with the Perceptual Quantizer (PQ) transfer
+    /// function.
+    Pq,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(VideoRange::Sdr.to_string(), "SDR".to_string());
+        assert_eq!(VideoRange::Hlg.to_string(), "HLG".to_string());
+        assert_eq!(VideoRange::Pq.to_string(), "PQ".to_string());
+    }
+
+    #[test]
+    fn test_parser() {
+        assert_eq!(VideoRange::Sdr, "SDR".parse().unwrap());
+        assert_eq!(VideoRange::Hlg, "HLG".parse().unwrap());
+        assert_eq!(VideoRange::Pq, "PQ".parse().unwrap());
+
+        assert!("HDR".parse::<VideoRange>().is_err());
+    }
+}
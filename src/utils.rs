@@ -1,5 +1,6 @@
 use core::iter;
 use std::borrow::Cow;
+use std::time::Duration;
 
 use crate::Error;
 
@@ -53,6 +54,172 @@ macro_rules! required_version {
     }
 }
 
+/// Parses a duration in (fractional) seconds.
+///
+/// Plain decimal numbers (like `3.333333333`) are parsed exactly, all other
+/// numbers (like `1e3`) are parsed as a `f64`.
+///
+/// # Error
+///
+/// This function fails, if the input is not a number or if the duration is
+/// negative or can not be represented by a [`Duration`] (for example
+/// `1e30`), instead of panicking like [`Duration::from_secs_f64`].
+pub(crate) fn parse_duration(input: &str) -> crate::Result<Duration> {
+    if let Some(duration) = parse_decimal_duration(input) {
+        return Ok(duration);
+    }
+
+    let seconds: f64 = input.parse().map_err(|e| Error::parse_float(input, e))?;
+
+    // a `Duration` can not be negative and is limited to `u64::MAX` seconds
+    #[allow(clippy::cast_precision_loss)]
+    if seconds.is_nan() || seconds < 0.0 || seconds >= u64::MAX as f64 {
+        return Err(Error::invalid_input());
+    }
+
+    Ok(Duration::from_secs_f64(seconds))
+}
+
+/// Parses a decimal number of seconds (`<seconds>[.<fraction>]`) without the
+/// rounding errors of a `f64`. Digits beyond nanosecond precision are rounded.
+fn parse_decimal_duration(input: &str) -> Option<Duration> {
+    let mut parts = input.splitn(2, '.');
+    let seconds = parts.next()?;
+    let fraction = parts.next().unwrap_or("");
+
+    if seconds.is_empty()
+        || !seconds.bytes().all(|b| b.is_ascii_digit())
+        || !fraction.bytes().all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let seconds: u64 = seconds.parse().ok()?;
+
+    let mut nanos = 0;
+    let mut digits = fraction.bytes();
+
+    for _ in 0..9 {
+        nanos = nanos * 10 + digits.next().map_or(0, |b| u64::from(b - b'0'));
+    }
+
+    if digits.next().map_or(false, |b| b >= b'5') {
+        nanos += 1;
+    }
+
+    Duration::from_secs(seconds).checked_add(Duration::from_nanos(nanos))
+}
+
+/// Formats a [`Duration`] as (fractional) seconds without trailing zeros.
+///
+/// Unlike [`Duration::as_secs_f64`] this does not lose any precision, so
+/// [`parse_duration`] will return exactly the same [`Duration`].
+pub(crate) fn format_duration(duration: Duration) -> String {
+    let nanos = duration.subsec_nanos();
+
+    if nanos == 0 {
+        duration.as_secs().to_string()
+    } else {
+        let fraction = format!("{:09}", nanos);
+        format!("{}.{}", duration.as_secs(), fraction.trim_end_matches('0'))
+    }
+}
+
+/// Returns a key to order a date-time in the format of [RFC3339] by the
+/// instant it represents: the seconds since `1970-01-01T00:00:00Z` and the
+/// nanoseconds.
+///
+/// This is used instead of parsing the date with `chrono`, if the `chrono`
+/// feature is disabled. Returns `None` if the input is not a valid date-time.
+///
+/// [RFC3339]: https://tools.ietf.org/html/rfc3339#section-5.6
+#[cfg(not(feature = "chrono"))]
+pub(crate) fn date_time_sort_key(input: &str) -> Option<(i64, u32)> {
+    fn number(value: Option<&str>) -> Option<i64> {
+        let value = value?;
+
+        if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+
+        value.parse().ok()
+    }
+
+    // YYYY-MM-DDTHH:MM:SS
+    let separators = [(4, b'-'), (7, b'-'), (13, b':'), (16, b':')];
+    let bytes = input.as_bytes();
+
+    if bytes.len() < 20
+        || !separators
+            .iter()
+            .all(|&(i, separator)| bytes[i] == separator)
+        || !matches!(bytes[10], b'T' | b't' | b' ')
+    {
+        return None;
+    }
+
+    let year = number(input.get(0..4))?;
+    let month = number(input.get(5..7))?;
+    let day = number(input.get(8..10))?;
+    let hour = number(input.get(11..13))?;
+    let minute = number(input.get(14..16))?;
+    let second = number(input.get(17..19))?;
+
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    let mut rest = &input[19..];
+    let mut nanos = 0;
+
+    if let Some(fraction) = rest.strip_prefix('.') {
+        let digits = fraction.bytes().take_while(u8::is_ascii_digit).count();
+
+        if digits == 0 {
+            return None;
+        }
+
+        let mut bytes = fraction[..digits].bytes();
+
+        for _ in 0..9 {
+            nanos = nanos * 10 + bytes.next().map_or(0, |b| u32::from(b - b'0'));
+        }
+
+        rest = &fraction[digits..];
+    }
+
+    let offset = match rest {
+        "Z" | "z" => 0,
+        _ => {
+            let sign = match rest.as_bytes().first()? {
+                b'+' => 1,
+                b'-' => -1,
+                _ => return None,
+            };
+
+            if rest.len() != 6 || rest.as_bytes()[3] != b':' {
+                return None;
+            }
+
+            sign * (number(rest.get(1..3))? * 3600 + number(rest.get(4..6))? * 60)
+        }
+    };
+
+    // the number of days since the unix epoch, see
+    // http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+
+    Some((
+        days * 86400 + hour * 3600 + minute * 60 + second - offset,
+        nanos,
+    ))
+}
+
 pub(crate) fn parse_yes_or_no<T: AsRef<str>>(s: T) -> crate::Result<bool> {
     match s.as_ref() {
         "YES" => Ok(true),
@@ -70,24 +237,20 @@ pub(crate) fn parse_yes_or_no<T: AsRef<str>>(s: T) -> crate::Result<bool> {
 /// Therefore it is safe to simply remove any occurence of those characters.
 /// [rfc8216#section-4.2](https://tools.ietf.org/html/rfc8216#section-4.2)
 pub(crate) fn unquote(value: &str) -> Cow<'_, str> {
-    if value.starts_with('"') && value.ends_with('"') {
-        let result = Cow::Borrowed(&value[1..value.len() - 1]);
-
-        if result
-            .chars()
-            .find(|c| *c == '"' || *c == '\n' || *c == '\r')
-            .is_none()
-        {
-            return result;
-        }
-    }
+    let is_unwanted = |c: char| c == '"' || c == '\n' || c == '\r';
 
-    Cow::Owned(
+    let inner = if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') {
+        &value[1..value.len() - 1]
+    } else {
         value
-            .chars()
-            .filter(|c| *c != '"' && *c != '\n' && *c != '\r')
-            .collect(),
-    )
+    };
+
+    // the value is only copied, if it has to be cleaned up:
+    if !inner.contains(is_unwanted) {
+        return Cow::Borrowed(inner);
+    }
+
+    Cow::Owned(value.chars().filter(|c| !is_unwanted(*c)).collect())
 }
 
 /// Puts a string inside quotes.
@@ -119,11 +282,243 @@ where
     Ok(input.trim().split_at(tag.as_ref().len()).1)
 }
 
+/// Replaces every variable reference (`{$name}`) in the input with the value
+/// returned by `lookup`.
+///
+/// # Error
+///
+/// This function fails, if a variable is not defined or a reference is not
+/// terminated.
+pub(crate) fn substitute_variables<'a, F>(input: &str, lookup: F) -> crate::Result<Cow<'_, str>>
+where
+    F: Fn(&str) -> Option<&'a str>,
+{
+    if !input.contains("{$") {
+        return Ok(Cow::Borrowed(input));
+    }
+
+    let mut result = String::with_capacity(input.len());
+    let mut rest = input;
+
+    while let Some(start) = rest.find("{$") {
+        result.push_str(&rest[..start]);
+        rest = &rest[start + 2..];
+
+        let end = rest.find('}').ok_or_else(|| {
+            Error::custom(format!("unterminated variable reference in {:?}", input))
+        })?;
+
+        let name = &rest[..end];
+        let value =
+            lookup(name).ok_or_else(|| Error::custom(format!("undefined variable {:?}", name)))?;
+
+        result.push_str(value);
+        rest = &rest[end + 1..];
+    }
+
+    result.push_str(rest);
+
+    Ok(Cow::Owned(result))
+}
+
+/// (De)serializes a [`Duration`] as a string of seconds, like it is written
+/// in a playlist (for example `"4.4"`), so that no precision is lost.
+///
+/// Only strings are deserialized, so that formats, which are not
+/// self-describing (like `bincode`), are supported as well.
+///
+/// This is used with `#[serde(with = "crate::utils::serde_duration")]`.
+#[cfg(feature = "serde")]
+pub(crate) mod serde_duration {
+    use std::fmt;
+    use std::time::Duration;
+
+    use serde::de::{self, Deserializer, Visitor};
+    use serde::Serializer;
+
+    pub(crate) fn serialize<S: Serializer>(
+        value: &Duration,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&super::format_duration(*value))
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Duration, D::Error> {
+        deserializer.deserialize_str(DurationVisitor)
+    }
+
+    struct DurationVisitor;
+
+    impl<'de> Visitor<'de> for DurationVisitor {
+        type Value = Duration;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "a string of a non-negative number of seconds")
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+            super::parse_duration(value).map_err(E::custom)
+        }
+    }
+}
+
+/// (De)serializes a [`DateTime`] as a RFC 3339 string.
+///
+/// This is used with `#[serde(with = "crate::utils::serde_date_time")]`.
+///
+/// [`DateTime`]: chrono::DateTime
+#[cfg(all(feature = "serde", feature = "chrono"))]
+pub(crate) mod serde_date_time {
+    use chrono::{DateTime, FixedOffset, SecondsFormat};
+    use serde::de::{self, Deserialize, Deserializer};
+    use serde::Serializer;
+
+    pub(crate) fn serialize<S: Serializer>(
+        value: &DateTime<FixedOffset>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&value.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<DateTime<FixedOffset>, D::Error> {
+        let value = String::deserialize(deserializer)?;
+
+        DateTime::parse_from_rfc3339(&value).map_err(de::Error::custom)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use pretty_assertions::assert_eq;
 
+    #[test]
+    #[cfg(not(feature = "chrono"))]
+    fn test_date_time_sort_key() {
+        assert_eq!(date_time_sort_key("1970-01-01T00:00:00Z"), Some((0, 0)));
+        assert_eq!(
+            date_time_sort_key("2020-01-01T00:00:10Z"),
+            Some((1_577_836_810, 0))
+        );
+        assert_eq!(
+            date_time_sort_key("2020-01-01T00:00:10.5Z"),
+            Some((1_577_836_810, 500_000_000))
+        );
+        assert_eq!(
+            date_time_sort_key("2020-01-01T01:00:10+01:00"),
+            date_time_sort_key("2020-01-01T00:00:10Z")
+        );
+        assert_eq!(
+            date_time_sort_key("2019-12-31T23:00:00-01:00"),
+            date_time_sort_key("2020-01-01T00:00:00Z")
+        );
+        assert_eq!(
+            date_time_sort_key("2000-03-01T00:00:00.0000000001Z"),
+            Some((951_868_800, 0))
+        );
+        assert_eq!(
+            date_time_sort_key("2000-03-01T00:00:00.1+01:00"),
+            Some((951_865_200, 100_000_000))
+        );
+
+        assert_eq!(date_time_sort_key(""), None);
+        assert_eq!(date_time_sort_key("2020-01-01"), None);
+        assert_eq!(date_time_sort_key("2020-01-01T00:00:00"), None);
+        assert_eq!(date_time_sort_key("2020-01-01T00:00:00.Z"), None);
+        assert_eq!(date_time_sort_key("2020-13-01T00:00:00Z"), None);
+        assert_eq!(date_time_sort_key("2020-01-01T00:00:00+0100"), None);
+        assert_eq!(date_time_sort_key("2020-01-01T00:00:00ü"), None);
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("5.5").unwrap(), Duration::from_millis(5500));
+        assert_eq!(
+            parse_duration("999999999999").unwrap(),
+            Duration::from_secs(999_999_999_999)
+        );
+
+        assert_eq!(parse_duration("-1"), Err(Error::invalid_input()));
+        assert_eq!(parse_duration("NaN"), Err(Error::invalid_input()));
+        assert_eq!(parse_duration("inf"), Err(Error::invalid_input()));
+        assert_eq!(parse_duration("1e30"), Err(Error::invalid_input()));
+        assert_eq!(
+            parse_duration("18446744073709551616"),
+            Err(Error::invalid_input())
+        );
+        assert!(parse_duration("garbage").is_err());
+    }
+
+    #[test]
+    fn test_parse_duration_exact() {
+        assert_eq!(
+            parse_duration("3.333333333").unwrap(),
+            Duration::from_nanos(3_333_333_333)
+        );
+        assert_eq!(
+            parse_duration("86399.000000001").unwrap(),
+            Duration::new(86399, 1)
+        );
+        // digits beyond nanosecond precision are rounded:
+        assert_eq!(
+            parse_duration("1.9999999995").unwrap(),
+            Duration::from_secs(2)
+        );
+        assert_eq!(
+            parse_duration("1.0000000004").unwrap(),
+            Duration::from_secs(1)
+        );
+        // other notations are still supported:
+        assert_eq!(parse_duration("5.").unwrap(), Duration::from_secs(5));
+        assert_eq!(parse_duration(".5").unwrap(), Duration::from_millis(500));
+        assert_eq!(parse_duration("1e3").unwrap(), Duration::from_secs(1000));
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_secs(5)), "5".to_string());
+        assert_eq!(
+            format_duration(Duration::from_millis(5500)),
+            "5.5".to_string()
+        );
+        assert_eq!(
+            format_duration(Duration::from_nanos(3_333_333_333)),
+            "3.333333333".to_string()
+        );
+        assert_eq!(
+            format_duration(Duration::new(0, 1)),
+            "0.000000001".to_string()
+        );
+    }
+
+    #[test]
+    fn test_substitute_variables() {
+        let lookup = |name: &str| {
+            match name {
+                "host" => Some("https://www.example.com"),
+                "id" => Some("42"),
+                _ => None,
+            }
+        };
+
+        assert_eq!(
+            substitute_variables("segment.ts", lookup).unwrap(),
+            Cow::Borrowed("segment.ts")
+        );
+
+        assert_eq!(
+            substitute_variables("{$host}/{$id}/segment-{$id}.ts", lookup).unwrap(),
+            "https://www.example.com/42/segment-42.ts"
+        );
+
+        assert!(substitute_variables("{$missing}/segment.ts", lookup).is_err());
+        assert!(substitute_variables("{$host/segment.ts", lookup).is_err());
+    }
+
     #[test]
     fn test_parse_yes_or_no() {
         assert!(parse_yes_or_no("YES").unwrap());
@@ -136,6 +531,15 @@ mod tests {
         assert_eq!(unquote("\"TestValue\""), "TestValue".to_string());
         assert_eq!(unquote("\"TestValue\n\""), "TestValue".to_string());
         assert_eq!(unquote("\"TestValue\n\r\""), "TestValue".to_string());
+
+        // only values, that have to be cleaned up, are allocated:
+        assert!(matches!(
+            unquote("\"TestValue\""),
+            Cow::Borrowed("TestValue")
+        ));
+        assert!(matches!(unquote("TestValue"), Cow::Borrowed("TestValue")));
+        assert_eq!(unquote("\""), "".to_string());
+        assert!(matches!(unquote("\"TestValue\n\""), Cow::Owned(_)));
     }
 
     #[test]
@@ -0,0 +1,262 @@
+use std::fmt;
+
+/// A non-fatal issue, that has been encountered while parsing a playlist
+/// with [`ParseOptions::lenient`] or while validating a playlist.
+///
+/// [`ParseOptions::lenient`]: crate::ParseOptions::lenient
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Warning {
+    /// A required attribute is missing and a default value has been used
+    /// instead.
+    MissingAttribute {
+        /// The tag, that is missing the attribute (for example
+        /// `#EXT-X-STREAM-INF`).
+        tag: String,
+        /// The name of the missing attribute.
+        attribute: String,
+        /// The value, that has been used instead.
+        default: String,
+    },
+    /// An attribute is not known to this library and has been ignored.
+    UnknownAttribute {
+        /// The tag, that contains the attribute (for example
+        /// `#EXT-X-STREAM-INF`).
+        tag: String,
+        /// The name of the unknown attribute.
+        attribute: String,
+    },
+    /// The last [`MediaSegment`] of a [`MediaPlaylist`] has no `#EXTINF` tag
+    /// and has been dropped.
+    ///
+    /// [`MediaSegment`]: crate::MediaSegment
+    /// [`MediaPlaylist`]: crate::MediaPlaylist
+    IncompleteSegment {
+        /// The `URI` of the dropped segment.
+        uri: String,
+    },
+    /// The [`VariantStream`]s of an audio group declare different audio
+    /// codecs, which prevents seamless switching between them.
+    ///
+    /// [`VariantStream`]: crate::tags::VariantStream
+    InconsistentAudioCodecs {
+        /// The `GROUP-ID` of the audio group.
+        group: String,
+        /// All audio codecs, that are declared by the streams of the group.
+        codecs: Vec<String>,
+    },
+    /// The [`MediaSegment::program_date_time`] of a [`MediaSegment`] is before
+    /// the one of a previous [`MediaSegment`], without a discontinuity
+    /// between them.
+    ///
+    /// [`MediaSegment`]: crate::MediaSegment
+    /// [`MediaSegment::program_date_time`]:
+    /// crate::MediaSegment::program_date_time
+    ProgramDateTimeOutOfOrder {
+        /// The [`MediaSegment::number`] of the segment.
+        ///
+        /// [`MediaSegment::number`]: crate::MediaSegment::number
+        number: usize,
+        /// The program date time of the previous segment.
+        previous: String,
+        /// The program date time of the segment.
+        current: String,
+    },
+    /// A [`MediaPlaylist`] contains [`ExtXPart`] tags, but no
+    /// `EXT-X-PART-INF` tag ([`MediaPlaylist::part_target`]).
+    ///
+    /// [`MediaPlaylist`]: crate::MediaPlaylist
+    /// [`MediaPlaylist::part_target`]: crate::MediaPlaylist::part_target
+    /// [`ExtXPart`]: crate::tags::ExtXPart
+    MissingPartInf,
+    /// A [`MediaPlaylist`] contains [`ExtXPart`] tags, but its
+    /// [`ExtXServerControl`] has no `PART-HOLD-BACK` attribute.
+    ///
+    /// [`MediaPlaylist`]: crate::MediaPlaylist
+    /// [`ExtXPart`]: crate::tags::ExtXPart
+    /// [`ExtXServerControl`]: crate::tags::ExtXServerControl
+    MissingPartHoldBack,
+    /// A [`MediaPlaylist`] with [`PlaylistType::Vod`] has no `EXT-X-ENDLIST`
+    /// tag ([`MediaPlaylist::has_end_list`]).
+    ///
+    /// [`MediaPlaylist`]: crate::MediaPlaylist
+    /// [`MediaPlaylist::has_end_list`]: crate::MediaPlaylist::has_end_list
+    /// [`PlaylistType::Vod`]: crate::types::PlaylistType::Vod
+    MissingEndList,
+    /// Two [`ExtXDateRange`] tags with the same [`ExtXDateRange::id`] have
+    /// different values for the same attribute.
+    ///
+    /// [`ExtXDateRange`]: crate::tags::ExtXDateRange
+    /// [`ExtXDateRange::id`]: crate::tags::ExtXDateRange::id
+    ConflictingDateRange {
+        /// The `ID` of the date ranges.
+        id: String,
+    },
+    /// A value of the `CUE` attribute of an [`ExtXDateRange`] is not known to
+    /// this library and has been ignored.
+    ///
+    /// [`ExtXDateRange`]: crate::tags::ExtXDateRange
+    UnknownCue {
+        /// The unknown value.
+        value: String,
+    },
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingAttribute {
+                tag,
+                attribute,
+                default,
+            } => {
+                write!(
+                    f,
+                    "missing attribute \"{}\" in `{}`, using \"{}\" instead",
+                    attribute, tag, default
+                )
+            }
+            Self::UnknownAttribute { tag, attribute } => {
+                write!(f, "unknown attribute \"{}\" in `{}`", attribute, tag)
+            }
+            Self::IncompleteSegment { uri } => {
+                write!(
+                    f,
+                    "dropped incomplete segment \"{}\" without `#EXTINF`",
+                    uri
+                )
+            }
+            Self::InconsistentAudioCodecs { group, codecs } => {
+                write!(
+                    f,
+                    "inconsistent audio codecs ({}) in audio group \"{}\"",
+                    codecs.join(", "),
+                    group
+                )
+            }
+            Self::ProgramDateTimeOutOfOrder {
+                number,
+                previous,
+                current,
+            } => {
+                write!(
+                    f,
+                    "program date time \"{}\" of segment {} is before \"{}\"",
+                    current, number, previous
+                )
+            }
+            Self::MissingPartInf => {
+                write!(
+                    f,
+                    "a playlist with `EXT-X-PART` tags must contain an `EXT-X-PART-INF` tag"
+                )
+            }
+            Self::MissingPartHoldBack => {
+                write!(
+                    f,
+                    concat!(
+                        "a playlist with `EXT-X-PART` tags must contain an `EXT-X-SERVER-CONTROL`",
+                        " tag with a `PART-HOLD-BACK` attribute"
+                    )
+                )
+            }
+            Self::MissingEndList => {
+                write!(f, "a `VOD` playlist must contain an `EXT-X-ENDLIST` tag")
+            }
+            Self::ConflictingDateRange { id } => {
+                write!(
+                    f,
+                    "conflicting attributes for the date range with the id \"{}\"",
+                    id
+                )
+            }
+            Self::UnknownCue { value } => write!(f, "unknown cue \"{}\"", value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            Warning::MissingAttribute {
+                tag: "#EXT-X-STREAM-INF".to_string(),
+                attribute: "BANDWIDTH".to_string(),
+                default: "0".to_string(),
+            }
+            .to_string(),
+            "missing attribute \"BANDWIDTH\" in `#EXT-X-STREAM-INF`, using \"0\" instead"
+                .to_string()
+        );
+
+        assert_eq!(
+            Warning::UnknownAttribute {
+                tag: "#EXT-X-STREAM-INF".to_string(),
+                attribute: "X-FOO".to_string(),
+            }
+            .to_string(),
+            "unknown attribute \"X-FOO\" in `#EXT-X-STREAM-INF`".to_string()
+        );
+
+        assert_eq!(
+            Warning::IncompleteSegment {
+                uri: "segment.ts".to_string(),
+            }
+            .to_string(),
+            "dropped incomplete segment \"segment.ts\" without `#EXTINF`".to_string()
+        );
+
+        assert_eq!(
+            Warning::InconsistentAudioCodecs {
+                group: "aac".to_string(),
+                codecs: vec!["mp4a.40.2".to_string(), "ec-3".to_string()],
+            }
+            .to_string(),
+            "inconsistent audio codecs (mp4a.40.2, ec-3) in audio group \"aac\"".to_string()
+        );
+
+        assert_eq!(
+            Warning::ProgramDateTimeOutOfOrder {
+                number: 3,
+                previous: "2020-01-01T00:00:10.000Z".to_string(),
+                current: "2020-01-01T00:00:05.000Z".to_string(),
+            }
+            .to_string(),
+            concat!(
+                "program date time \"2020-01-01T00:00:05.000Z\" of segment 3 is before ",
+                "\"2020-01-01T00:00:10.000Z\""
+            )
+            .to_string()
+        );
+
+        assert_eq!(
+            Warning::MissingPartInf.to_string(),
+            "a playlist with `EXT-X-PART` tags must contain an `EXT-X-PART-INF` tag".to_string()
+        );
+
+        assert_eq!(
+            Warning::MissingEndList.to_string(),
+            "a `VOD` playlist must contain an `EXT-X-ENDLIST` tag".to_string()
+        );
+
+        assert_eq!(
+            Warning::ConflictingDateRange {
+                id: "ad1".to_string(),
+            }
+            .to_string(),
+            "conflicting attributes for the date range with the id \"ad1\"".to_string()
+        );
+
+        assert_eq!(
+            Warning::UnknownCue {
+                value: "MIDDLE".to_string(),
+            }
+            .to_string(),
+            "unknown cue \"MIDDLE\"".to_string()
+        );
+    }
+}
@@ -129,3 +129,33 @@ generate_tests! {
         )
     }
 }
+
+#[test]
+fn test_large_master_playlist() {
+    let mut input = String::from("#EXTM3U\n");
+
+    for i in 0..50 {
+        input.push_str(&format!(
+            "#EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"audio-{0}\",NAME=\"audio-{0}\",URI=\"audio/{0}.m3u8\"\n",
+            i
+        ));
+    }
+
+    for i in 0..5000 {
+        input.push_str(&format!(
+            concat!(
+                "#EXT-X-STREAM-INF:BANDWIDTH={},CODECS=\"avc1.42e00a,mp4a.40.2\",",
+                "RESOLUTION=416x234,AUDIO=\"audio-{}\"\n",
+                "video/{}.m3u8\n"
+            ),
+            150_000 + i,
+            i % 50,
+            i
+        ));
+    }
+
+    let master_playlist = input.parse::<MasterPlaylist<'_>>().unwrap();
+
+    assert_eq!(master_playlist.media.len(), 50);
+    assert_eq!(master_playlist.variant_streams.len(), 5000);
+}
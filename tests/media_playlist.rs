@@ -6,9 +6,9 @@
 use std::convert::TryFrom;
 use std::time::Duration;
 
-use hls_m3u8::tags::{ExtInf, ExtXByteRange};
-use hls_m3u8::types::PlaylistType;
-use hls_m3u8::{MediaPlaylist, MediaSegment};
+use hls_m3u8::tags::{ExtInf, ExtXByteRange, ExtXKey};
+use hls_m3u8::types::{DecryptionKey, EncryptionMethod, KeyFormat, PlaylistType, ProtocolVersion};
+use hls_m3u8::{peek_version, MediaPlaylist, MediaSegment};
 use pretty_assertions::assert_eq;
 
 macro_rules! generate_tests {
@@ -230,10 +230,8 @@ generate_tests! {
                     .unwrap(),
             ])
             .has_end_list(true)
-            .unknown(vec![
-                // deprecated tag:
-                "#EXT-X-ALLOW-CACHE:YES".into()
-            ])
+            // deprecated tag:
+            .allow_cache(true)
             .build()
             .unwrap(),
         concat!(
@@ -242,6 +240,7 @@ generate_tests! {
             "#EXT-X-TARGETDURATION:10\n",
             "#EXT-X-MEDIA-SEQUENCE:1\n",
             "#EXT-X-PLAYLIST-TYPE:VOD\n",
+            "#EXT-X-ALLOW-CACHE:YES\n",
 
             "#EXT-X-BYTERANGE:522828@0\n",
             "#EXTINF:10,\n",
@@ -311,8 +310,151 @@ generate_tests! {
             "#EXTINF:1.4167,\n",
             "hls_450k_video.ts\n",
 
-            "#EXT-X-ALLOW-CACHE:YES\n",
             "#EXT-X-ENDLIST\n"
         )
     },
+    // Every key applies until the next key with the same format (a missing
+    // `KEYFORMAT` is the same as `identity`), so only the rotated FairPlay key
+    // has to be written again.
+    test_multiple_key_formats => {
+        MediaPlaylist::builder()
+            .target_duration(Duration::from_secs(10))
+            .segments(vec![
+                MediaSegment::builder()
+                    .duration(ExtInf::new(Duration::from_secs(10)))
+                    .keys(vec![
+                        ExtXKey::new(DecryptionKey::new(
+                            EncryptionMethod::Aes128,
+                            "https://example.com/key0",
+                        )),
+                        ExtXKey::new(
+                            DecryptionKey::builder()
+                                .method(EncryptionMethod::SampleAes)
+                                .uri("skd://key0")
+                                .format(KeyFormat::Other("com.apple.streamingkeydelivery".into()))
+                                .build()
+                                .unwrap(),
+                        ),
+                    ])
+                    .uri("segment0.ts")
+                    .build()
+                    .unwrap(),
+                MediaSegment::builder()
+                    .duration(ExtInf::new(Duration::from_secs(10)))
+                    .keys(vec![
+                        ExtXKey::new(DecryptionKey::new(
+                            EncryptionMethod::Aes128,
+                            "https://example.com/key0",
+                        )),
+                        ExtXKey::new(
+                            DecryptionKey::builder()
+                                .method(EncryptionMethod::SampleAes)
+                                .uri("skd://key1")
+                                .format(KeyFormat::Other("com.apple.streamingkeydelivery".into()))
+                                .build()
+                                .unwrap(),
+                        ),
+                    ])
+                    .uri("segment1.ts")
+                    .build()
+                    .unwrap(),
+                MediaSegment::builder()
+                    .duration(ExtInf::new(Duration::from_secs(10)))
+                    .keys(vec![
+                        ExtXKey::new(DecryptionKey::new(
+                            EncryptionMethod::Aes128,
+                            "https://example.com/key0",
+                        )),
+                        ExtXKey::new(
+                            DecryptionKey::builder()
+                                .method(EncryptionMethod::SampleAes)
+                                .uri("skd://key1")
+                                .format(KeyFormat::Other("com.apple.streamingkeydelivery".into()))
+                                .build()
+                                .unwrap(),
+                        ),
+                    ])
+                    .uri("segment2.ts")
+                    .build()
+                    .unwrap(),
+            ])
+            .build()
+            .unwrap(),
+        concat!(
+            "#EXTM3U\n",
+            "#EXT-X-VERSION:5\n",
+            "#EXT-X-TARGETDURATION:10\n",
+            "#EXT-X-KEY:METHOD=AES-128,URI=\"https://example.com/key0\"\n",
+            "#EXT-X-KEY:METHOD=SAMPLE-AES,URI=\"skd://key0\",KEYFORMAT=\"com.apple.streamingkeydelivery\"\n",
+            "#EXTINF:10,\n",
+            "segment0.ts\n",
+            "#EXT-X-KEY:METHOD=SAMPLE-AES,URI=\"skd://key1\",KEYFORMAT=\"com.apple.streamingkeydelivery\"\n",
+            "#EXTINF:10,\n",
+            "segment1.ts\n",
+            "#EXTINF:10,\n",
+            "segment2.ts\n",
+        )
+    },
+}
+
+#[test]
+fn test_peek_version() {
+    assert_eq!(
+        peek_version(concat!(
+            "#EXTM3U\r\n",
+            "#EXT-X-TARGETDURATION:10\r\n",
+            "#EXT-X-VERSION:3\r\n",
+            "#EXTINF:9.009,\r\n",
+            "http://media.example.com/first.ts\r\n",
+        )),
+        Some(ProtocolVersion::V3)
+    );
+
+    assert_eq!(
+        peek_version(concat!(
+            "#EXTM3U\n",
+            "#EXT-X-TARGETDURATION:10\n",
+            "#EXTINF:9.009,\n",
+            "http://media.example.com/first.ts\n",
+        )),
+        Some(ProtocolVersion::V1)
+    );
+
+    assert_eq!(peek_version(""), None);
+    assert_eq!(peek_version("#EXTM3U\n#EXT-X-VERSION:X\n"), None);
+}
+
+#[test]
+#[cfg(feature = "flate2")]
+fn test_from_gzip_reader() {
+    let input: &[u8] = include_bytes!("assets/media_playlist.m3u8.gz");
+
+    assert_eq!(
+        MediaPlaylist::from_gzip_reader(input).unwrap(),
+        MediaPlaylist::builder()
+            .target_duration(Duration::from_secs(10))
+            .segments(vec![
+                MediaSegment::builder()
+                    .duration(ExtInf::new(Duration::from_secs_f64(9.009)))
+                    .uri("http://media.example.com/first.ts")
+                    .build()
+                    .unwrap(),
+                MediaSegment::builder()
+                    .duration(ExtInf::new(Duration::from_secs_f64(9.009)))
+                    .uri("http://media.example.com/second.ts")
+                    .build()
+                    .unwrap(),
+                MediaSegment::builder()
+                    .duration(ExtInf::new(Duration::from_secs_f64(3.003)))
+                    .uri("http://media.example.com/third.ts")
+                    .build()
+                    .unwrap(),
+            ])
+            .has_end_list(true)
+            .build()
+            .unwrap()
+    );
+
+    // the input is not compressed:
+    assert!(MediaPlaylist::from_gzip_reader(&b"#EXTM3U\n"[..]).is_err());
 }